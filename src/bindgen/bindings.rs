@@ -7,17 +7,19 @@ use std::cell::RefCell;
 use std::collections::HashMap;
 use std::fs;
 use std::fs::File;
-use std::io::{BufWriter, Read, Write};
+use std::io::{self, BufWriter, Read, Write};
 use std::path;
 use std::rc::Rc;
 
 use crate::bindgen::config::{Config, Language};
+use crate::bindgen::diagnostic::Diagnostic;
 use crate::bindgen::ir::{
     Constant, Function, ItemContainer, ItemMap, Path as BindgenPath, Static, Struct, Type, Typedef,
 };
 use crate::bindgen::language_backend::{
     CLikeLanguageBackend, CythonLanguageBackend, LanguageBackend,
 };
+use crate::bindgen::provenance::{self, DependencyGraphFormat, Provenance};
 use crate::bindgen::writer::SourceWriter;
 
 /// A bindings header that can be written.
@@ -37,6 +39,14 @@ pub struct Bindings {
     /// and shouldn't do anything when written anywhere.
     noop: bool,
     pub package_version: String,
+    /// The problems found while generating the bindings that didn't prevent
+    /// them from being generated.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Where the items come from and why they are part of the bindings.
+    pub provenance: Provenance,
+    /// Additional files returned by plugins, relative to the directory of
+    /// the header, written along with it by `write_to_file`.
+    pub plugin_files: Vec<(path::PathBuf, String)>,
 }
 
 impl Bindings {
@@ -52,6 +62,8 @@ impl Bindings {
         source_files: Vec<path::PathBuf>,
         noop: bool,
         package_version: String,
+        diagnostics: Vec<Diagnostic>,
+        provenance: Provenance,
     ) -> Bindings {
         Bindings {
             config,
@@ -65,6 +77,9 @@ impl Bindings {
             source_files,
             noop,
             package_version,
+            diagnostics,
+            provenance,
+            plugin_files: Vec::new(),
         }
     }
 
@@ -75,6 +90,20 @@ impl Bindings {
         any
     }
 
+    /// Replaces the structs at `path` in the struct map with the ones of the
+    /// bindings, after a plugin changed their fields.
+    pub(crate) fn refresh_struct(&mut self, path: &BindgenPath) {
+        self.struct_map.filter(|x| x.path == *path);
+        for item in &self.items {
+            if let ItemContainer::Struct(ref item) = *item {
+                if item.path == *path {
+                    self.struct_map.try_insert(item.clone());
+                }
+            }
+        }
+        self.struct_fileds_memo.borrow_mut().clear();
+    }
+
     /// Peels through typedefs to allow resolving structs.
     fn resolved_struct_path<'a>(&self, path: &'a BindgenPath) -> Cow<'a, BindgenPath> {
         let mut resolved_path = Cow::Borrowed(path);
@@ -158,17 +187,50 @@ impl Bindings {
         write!(&mut writer, "}};").expect("writing symbol file footer failed");
     }
 
+    /// Describes whether an item named `name` was found, where it comes from,
+    /// and why it is or isn't part of the bindings.
+    pub fn explain(&self, name: &str) -> String {
+        provenance::explain(self, name)
+    }
+
+    /// Writes the graph of which items pulled which other items into the
+    /// bindings.
+    pub fn write_dependency_graph<F: Write>(
+        &self,
+        format: DependencyGraphFormat,
+        mut out: F,
+    ) -> io::Result<()> {
+        self.provenance.write_dependency_graph(format, &mut out)
+    }
+
     pub fn generate_depfile<P: AsRef<path::Path>>(&self, header_path: P, depfile_path: P) {
+        self.generate_depfile_for_outputs(&[header_path], depfile_path)
+    }
+
+    /// Writes a depfile listing several headers generated from the same
+    /// sources, such as the outputs of `Builder::generate_many`.
+    pub fn generate_depfile_for_outputs<P: AsRef<path::Path>, Q: AsRef<path::Path>>(
+        &self,
+        header_paths: &[P],
+        depfile_path: Q,
+    ) {
         if let Some(dir) = depfile_path.as_ref().parent() {
             if !dir.exists() {
                 std::fs::create_dir_all(dir).unwrap()
             }
         }
-        let canon_header_path = header_path.as_ref().canonicalize().unwrap();
+        let canon_header_paths: Vec<_> = header_paths
+            .iter()
+            .map(|path| {
+                let path = path.as_ref().canonicalize().unwrap();
+                path.to_string_lossy().replace(' ', "\\ ")
+            })
+            .collect();
         let mut canon_source_files: Vec<_> = self
             .source_files
             .iter()
             .chain(self.config.config_path.as_ref())
+            .chain(self.config.extended_config_paths().iter())
             .map(|p| p.canonicalize().unwrap())
             .collect();
         // Sorting makes testing easier by ensuring the output is ordered.
@@ -180,12 +242,8 @@ impl Bindings {
         // compliant slice, without knowing the encoding, so we lossy convert such cases,
         // to avoid panics.
         let mut depfile = File::create(depfile_path).unwrap();
-        write!(
-            &mut depfile,
-            "{}:",
-            canon_header_path.to_string_lossy().replace(' ', "\\ ")
-        )
-        .expect("Writing header name to depfile failed");
+        write!(&mut depfile, "{}:", canon_header_paths.join(" "))
+            .expect("Writing header name to depfile failed");
         canon_source_files.into_iter().for_each(|source_file| {
             // Add line-continue and line-break and then indent with 4 spaces.
             // This makes the output more human-readable.
@@ -204,31 +262,15 @@ impl Bindings {
             return false;
         }
 
-        // Don't compare files if we've never written this file before
-        if !path.as_ref().is_file() {
-            if let Some(parent) = path::Path::new(path.as_ref()).parent() {
-                fs::create_dir_all(parent).unwrap();
-            }
-            self.write(File::create(path).unwrap());
-            return true;
-        }
+        let mut contents = Vec::new();
+        self.write(&mut contents);
+        let mut changed = write_if_changed(path.as_ref(), &contents);
 
-        let mut new_file_contents = Vec::new();
-        self.write(&mut new_file_contents);
-
-        let mut old_file_contents = Vec::new();
-        {
-            let mut old_file = File::open(&path).unwrap();
-            old_file.read_to_end(&mut old_file_contents).unwrap();
-        }
-
-        if old_file_contents != new_file_contents {
-            let mut new_file = File::create(&path).unwrap();
-            new_file.write_all(&new_file_contents).unwrap();
-            true
-        } else {
-            false
+        let dir = path.as_ref().parent().unwrap_or(path::Path::new(""));
+        for (file, contents) in &self.plugin_files {
+            changed |= write_if_changed(&dir.join(file), contents.as_bytes());
         }
+        changed
     }
 
     pub fn write<F: Write>(&self, file: F) {
@@ -242,6 +284,20 @@ impl Bindings {
         }
     }
 
+    /// The problems that writing the bindings finds, like traits that can't
+    /// be derived, so that they can be reported before the bindings are
+    /// written anywhere.
+    pub(crate) fn write_diagnostics(&self) -> Vec<Diagnostic> {
+        match self.config.language {
+            Language::Cxx | Language::C => {
+                let mut backend = CLikeLanguageBackend::new(&self.config);
+                self.write_with_backend(io::sink(), &mut backend);
+                backend.into_diagnostics()
+            }
+            Language::Cython => Vec::new(),
+        }
+    }
+
     fn write_with_backend<F: Write, LB: LanguageBackend>(
         &self,
         file: F,
@@ -256,3 +312,30 @@ impl Bindings {
         language_backend.write_bindings(&mut out, self);
     }
 }
+
+/// Writes `contents` to `path` unless it already has them, creating the
+/// parent directories if needed. Returns whether the file was written.
+fn write_if_changed(path: &path::Path, contents: &[u8]) -> bool {
+    // Don't compare files if we've never written this file before
+    if !path.is_file() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        File::create(path).unwrap().write_all(contents).unwrap();
+        return true;
+    }
+
+    let mut old_file_contents = Vec::new();
+    {
+        let mut old_file = File::open(path).unwrap();
+        old_file.read_to_end(&mut old_file_contents).unwrap();
+    }
+
+    if old_file_contents != contents {
+        let mut new_file = File::create(path).unwrap();
+        new_file.write_all(contents).unwrap();
+        true
+    } else {
+        false
+    }
+}
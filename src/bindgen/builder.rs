@@ -7,9 +7,11 @@ use std::path;
 use crate::bindgen::bindings::Bindings;
 use crate::bindgen::cargo::Cargo;
 use crate::bindgen::config::{Braces, Config, Language, Profile, Style};
+use crate::bindgen::diagnostic::Diagnostic;
 use crate::bindgen::error::Error;
 use crate::bindgen::library::Library;
 use crate::bindgen::parser::{self, Parse};
+use crate::bindgen::transform::{IrView, Transform};
 
 /// A builder for generating a bindings header.
 #[derive(Debug, Clone)]
@@ -20,6 +22,9 @@ pub struct Builder {
     lib_cargo: Option<Cargo>,
     std_types: bool,
     lockfile: Option<path::PathBuf>,
+    /// Problems found while configuring the builder, reported by `generate`.
+    config_errors: Vec<String>,
+    transforms: Vec<Transform>,
 }
 
 impl Builder {
@@ -32,9 +37,21 @@ impl Builder {
             lib_cargo: None,
             std_types: true,
             lockfile: None,
+            config_errors: Vec::new(),
+            transforms: Vec::new(),
         }
     }
 
+    /// Applies the `[overlay.<name>]` table of the config file on top of the
+    /// config, including the settings changed by earlier builder methods.
+    #[allow(unused)]
+    pub fn with_overlay<S: AsRef<str>>(mut self, name: S) -> Builder {
+        if let Err(e) = self.config.apply_overlay(name.as_ref()) {
+            self.config_errors.push(e);
+        }
+        self
+    }
+
     #[allow(unused)]
     pub fn with_header<S: AsRef<str>>(mut self, header: S) -> Builder {
         self.config.header = Some(String::from(header.as_ref()));
@@ -161,6 +178,7 @@ impl Builder {
         self
     }
 
+    /// Includes the items selected by a pattern, see `export.include`.
     #[allow(unused)]
     pub fn include_item<S: AsRef<str>>(mut self, item_name: S) -> Builder {
         self.config
@@ -170,6 +188,7 @@ impl Builder {
         self
     }
 
+    /// Excludes the items selected by a pattern, see `export.exclude`.
     #[allow(unused)]
     pub fn exclude_item<S: AsRef<str>>(mut self, item_name: S) -> Builder {
         self.config
@@ -343,27 +362,91 @@ impl Builder {
         self
     }
 
-    pub fn generate(self) -> Result<Bindings, Error> {
+    /// Registers a function that can rename, remove, document, annotate or
+    /// reorder the items of the bindings after they have been renamed and
+    /// monomorphized, right before they are written. Transforms run in the
+    /// order they were registered, once per generated header.
+    #[allow(unused)]
+    pub fn with_transform<F>(mut self, transform: F) -> Builder
+    where
+        F: FnMut(&mut IrView) + Send + 'static,
+    {
+        self.transforms.push(Transform::new(transform));
+        self
+    }
+
+    pub fn generate(mut self) -> Result<Bindings, Error> {
+        let warnings = self.check_config()?;
+
         // If macro expansion is enabled, then cbindgen will attempt to build the crate
         // and will run its build script which may run cbindgen again. That second run may start
         // infinite recursion, or overwrite previously written files with bindings.
         // So if we are called recursively, we are skipping the whole generation
         // and produce "noop" bindings that won't be able to overwrite anything.
         if std::env::var("_CBINDGEN_IS_RUNNING").is_ok() {
-            return Ok(Bindings::new(
-                self.config,
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                Default::default(),
-                true,
-                String::new(),
-            ));
+            return Ok(noop_bindings(self.config));
+        }
+
+        let mut result = self.parse()?;
+        result.diagnostics.splice(0..0, warnings);
+        let mut logged = result.diagnostics.clone();
+        let mut bindings = library(self.config, result).generate()?;
+        apply_transforms(&self.transforms, &mut bindings);
+        report_diagnostics(&mut bindings, &mut logged)?;
+        Ok(bindings)
+    }
+
+    /// Generates the bindings of every `[[output]]` table of the config, along
+    /// with the path each of them should be written to. The crate is only
+    /// parsed once for all of them.
+    pub fn generate_many(mut self) -> Result<Vec<(path::PathBuf, Bindings)>, Error> {
+        let warnings = self.check_config()?;
+
+        let outputs = self.config.outputs();
+        if std::env::var("_CBINDGEN_IS_RUNNING").is_ok() {
+            return Ok(outputs
+                .into_iter()
+                .map(|(path, config)| (path, noop_bindings(config)))
+                .collect());
         }
 
+        let mut result = self.parse()?;
+        result.diagnostics.splice(0..0, warnings);
+        let mut logged = result.diagnostics.clone();
+        outputs
+            .into_iter()
+            .map(|(path, config)| {
+                let mut bindings = library(config, result.clone()).generate()?;
+                apply_transforms(&self.transforms, &mut bindings);
+                report_diagnostics(&mut bindings, &mut logged)?;
+                Ok((path, bindings))
+            })
+            .collect()
+    }
+
+    /// Fails if the config can't be used, and else logs and returns the
+    /// warnings about it.
+    fn check_config(&mut self) -> Result<Vec<Diagnostic>, Error> {
+        let warnings = match self.config.validate() {
+            Ok(warnings) => warnings,
+            Err(Error::InvalidConfig(problems)) => {
+                self.config_errors.extend(problems);
+                vec![]
+            }
+            Err(e) => return Err(e),
+        };
+        if !self.config_errors.is_empty() {
+            return Err(Error::InvalidConfig(std::mem::take(
+                &mut self.config_errors,
+            )));
+        }
+        for warning in &warnings {
+            warning.log();
+        }
+        Ok(warnings)
+    }
+
+    fn parse(&self) -> Result<Parse, Error> {
         let mut result = Parse::new();
 
         if self.std_types {
@@ -394,26 +477,494 @@ impl Builder {
 
         result.source_files.extend_from_slice(self.srcs.as_slice());
 
-        Library::new(
-            self.config,
-            result.constants,
-            result.globals,
-            result.enums,
-            result.structs,
-            result.unions,
-            result.opaque_items,
-            result.typedefs,
-            result.functions,
-            result.source_files,
-            result.package_version,
-        )
-        .generate()
+        Ok(result)
+    }
+}
+
+fn library(config: Config, result: Parse) -> Library {
+    Library::new(
+        config,
+        result.constants,
+        result.globals,
+        result.enums,
+        result.structs,
+        result.unions,
+        result.opaque_items,
+        result.typedefs,
+        result.functions,
+        result.source_files,
+        result.package_version,
+        result.diagnostics,
+        result.provenance,
+    )
+}
+
+/// Adds the diagnostics found while writing to those of `bindings`, and logs
+/// the ones that aren't in `logged` yet, adding them to it so that the outputs
+/// of one parse don't log the same problems again. Fails on them like on the
+/// diagnostics found while parsing.
+fn report_diagnostics(bindings: &mut Bindings, logged: &mut Vec<Diagnostic>) -> Result<(), Error> {
+    let diagnostics = bindings.write_diagnostics();
+    bindings.diagnostics.extend(diagnostics);
+
+    let strict = bindings.config.parse.strict;
+    let new: Vec<_> = bindings
+        .diagnostics
+        .iter()
+        .filter(|x| !logged.contains(x))
+        .collect();
+    if new.iter().any(|x| strict || x.code.is_fatal()) {
+        return Err(Error::Diagnostics(bindings.diagnostics.clone()));
+    }
+    for diagnostic in new {
+        diagnostic.log();
+        logged.push(diagnostic.clone());
+    }
+    Ok(())
+}
+
+fn apply_transforms(transforms: &[Transform], bindings: &mut Bindings) {
+    for transform in transforms {
+        transform.apply(bindings);
     }
 }
 
+fn noop_bindings(config: Config) -> Bindings {
+    Bindings::new(
+        config,
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        Default::default(),
+        true,
+        String::new(),
+        Default::default(),
+        Default::default(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::bindgen::config::{CStandard, ItemType};
+    use crate::bindgen::diagnostic::DiagnosticCode;
+    use crate::bindgen::provenance::DependencyGraphFormat;
+
+    #[test]
+    fn with_overlay() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"language = \"C\"\nnamespace = \"base\"\n[overlay.cpp]\nlanguage = \"C++\"\n",
+        )
+        .unwrap();
+        let config = Config::from_file(file.path()).unwrap();
+
+        let builder = Builder::new().with_config(config).with_overlay("cpp");
+        assert_eq!(builder.config.language, Language::Cxx);
+        assert_eq!(builder.config.namespace.as_deref(), Some("base"));
+        assert!(builder.config_errors.is_empty());
+
+        let builder = builder.with_overlay("missing");
+        assert_eq!(builder.config_errors.len(), 1);
+    }
+
+    #[test]
+    fn with_overlay_keeps_builder_settings() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"[overlay.guarded]\ninclude_guard = \"GUARD_H\"\n",
+        )
+        .unwrap();
+        let config = Config::from_file(file.path()).unwrap();
+
+        let builder = Builder::new()
+            .with_config(config)
+            .with_language(Language::C)
+            .with_namespace("ffi")
+            .with_include("extra.h")
+            .with_overlay("guarded");
+        assert!(builder.config_errors.is_empty());
+        assert_eq!(builder.config.include_guard.as_deref(), Some("GUARD_H"));
+        assert_eq!(builder.config.language, Language::C);
+        assert_eq!(builder.config.namespace.as_deref(), Some("ffi"));
+        assert_eq!(builder.config.includes, ["extra.h"]);
+        assert_eq!(builder.config.config_path.as_deref(), Some(file.path()));
+    }
+
+    #[test]
+    fn config_warnings() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"#[no_mangle] pub extern \"C\" fn f() {}\n").unwrap();
+
+        let bindings = Builder::new()
+            .with_namespace("ffi")
+            .with_language(Language::C)
+            .with_src(file.path())
+            .generate()
+            .unwrap();
+        let codes: Vec<_> = bindings.diagnostics.iter().map(|d| d.code).collect();
+        assert_eq!(codes, [DiagnosticCode::IgnoredConfigOption]);
+    }
+
+    #[test]
+    fn diagnostic_locations() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("lib.rs");
+        std::fs::write(&src, "mod a;\nmod missing;\n").unwrap();
+        std::fs::write(dir.path().join("a.rs"), "pub struct A;\n").unwrap();
+
+        let bindings = Builder::new().with_src(&src).generate().unwrap();
+        let diagnostic = bindings
+            .diagnostics
+            .iter()
+            .find(|d| d.code == DiagnosticCode::MissingModule)
+            .unwrap();
+        let location = diagnostic.location.as_ref().unwrap();
+        assert_eq!(location.file, src);
+        assert_eq!((location.line, location.column), (2, 5));
+
+        let mut config = Config::default();
+        config.parse.strict = true;
+        let result = Builder::new().with_config(config).with_src(&src).generate();
+        assert!(matches!(result, Err(Error::Diagnostics(ref d)) if d.len() == 1));
+    }
+
+    #[test]
+    fn generate_many() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("lib.rs");
+        std::fs::write(&src, "#[no_mangle] pub extern \"C\" fn f() {}").unwrap();
+        let config = dir.path().join("cbindgen.toml");
+        std::fs::write(
+            &config,
+            "include_guard = \"BASE_H\"\n\
+             [[output]]\npath = \"foo.h\"\nlanguage = \"C\"\n\
+             [[output]]\npath = \"foo.hpp\"\ninclude_guard = \"FOO_HPP\"\n",
+        )
+        .unwrap();
+
+        let outputs = Builder::new()
+            .with_config(Config::from_file(&config).unwrap())
+            .with_src(&src)
+            .generate_many()
+            .unwrap();
+        let outputs: Vec<_> = outputs
+            .iter()
+            .map(|(path, bindings)| {
+                (
+                    path,
+                    bindings.config.language,
+                    &bindings.config.include_guard,
+                )
+            })
+            .collect();
+        assert_eq!(
+            outputs,
+            [
+                (
+                    &dir.path().join("foo.h"),
+                    Language::C,
+                    &Some("BASE_H".to_owned())
+                ),
+                (
+                    &dir.path().join("foo.hpp"),
+                    Language::Cxx,
+                    &Some("FOO_HPP".to_owned())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn with_transform() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"#[repr(C)] pub struct Foo { x: i32 }\n\
+              #[no_mangle] pub extern \"C\" fn make() -> Foo { todo!() }\n\
+              #[no_mangle] pub extern \"C\" fn check(foo: *const Foo) {}\n\
+              #[no_mangle] pub extern \"C\" fn hidden() {}\n",
+        )
+        .unwrap();
+
+        Builder::new()
+            .with_language(Language::C)
+            .with_src(file.path())
+            .with_transform(|ir| {
+                assert!(ir.rename("Foo", "Bar").unwrap());
+                assert!(ir.rename("make", "create").is_err());
+                assert!(ir.remove("hidden"));
+                assert!(!ir.remove("missing"));
+                assert!(ir.set_documentation("check", vec![" Checks a bar.".to_owned()]));
+                assert_eq!(ir.annotate("check", "must-use"), Ok(true));
+                ir.sort_by(|a, b| b.name.cmp(&a.name));
+            })
+            .with_transform(|ir| {
+                let items = ir.items();
+                let names: Vec<_> = items.iter().map(|item| &item.name[..]).collect();
+                assert_eq!(names, ["Bar", "make", "check"]);
+                assert_eq!(items[0].rust_name, "Foo");
+                assert_eq!(items[0].kind, ItemType::Structs);
+                assert_eq!(
+                    ir.documentation("check"),
+                    Some(&[" Checks a bar.".to_owned()][..])
+                );
+            })
+            .generate()
+            .unwrap();
+    }
+
+    #[test]
+    fn instantiated_dependencies() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"#[repr(C)] pub struct Foo<T> { x: T }\n\
+              #[repr(C)] pub struct Bar { x: i32 }\n",
+        )
+        .unwrap();
+
+        let mut config = Config {
+            language: Language::C,
+            ..Default::default()
+        };
+        config.export.include.push("Bar".to_owned());
+        config
+            .export
+            .instantiate
+            .insert("Foo".to_owned(), vec!["u32".to_owned()]);
+        let bindings = Builder::new()
+            .with_config(config)
+            .with_src(file.path())
+            .generate()
+            .unwrap();
+        let mut graph = Vec::new();
+        bindings
+            .write_dependency_graph(DependencyGraphFormat::Json, &mut graph)
+            .unwrap();
+        let graph: serde_json::Value = serde_json::from_slice(&graph).unwrap();
+        let edges = graph["edges"].as_array().unwrap();
+        for (from, to) in [("export.instantiate", "Foo_u32"), ("export.include", "Bar")] {
+            let edge = serde_json::json!({ "from": from, "to": to });
+            assert!(edges.contains(&edge), "{graph}");
+        }
+        let explanation = bindings.explain("Bar");
+        assert!(
+            explanation.contains("It is included via `export.include` -> `Bar`."),
+            "{explanation}"
+        );
+    }
+
+    #[test]
+    fn invalid_instantiation() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"#[repr(C)] pub struct Foo<T> { x: T }\n").unwrap();
+
+        let mut config = Config::default();
+        config
+            .export
+            .instantiate
+            .insert("Foo".to_owned(), vec!["()".to_owned()]);
+        let bindings = Builder::new()
+            .with_config(config.clone())
+            .with_src(file.path())
+            .generate()
+            .unwrap();
+        let diagnostic = &bindings.diagnostics[0];
+        assert_eq!(diagnostic.code, DiagnosticCode::InvalidInstantiation);
+        assert!(
+            diagnostic
+                .message
+                .starts_with("Can't instantiate `Foo<()>`"),
+            "{}",
+            diagnostic.message
+        );
+
+        config.parse.strict = true;
+        let result = Builder::new()
+            .with_config(config)
+            .with_src(file.path())
+            .generate();
+        assert!(matches!(result, Err(Error::Diagnostics(..))));
+    }
+
+    #[test]
+    fn cannot_derive_hash() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"#[repr(C)] pub struct Named { name: *const u8 }\n\
+              #[no_mangle] pub extern \"C\" fn root(named: Named) {}\n",
+        )
+        .unwrap();
+
+        let mut config = Config::default();
+        config.structure.derive_hash = true;
+        let bindings = Builder::new()
+            .with_config(config.clone())
+            .with_src(file.path())
+            .generate()
+            .unwrap();
+        assert_eq!(bindings.diagnostics.len(), 1, "{:?}", bindings.diagnostics);
+        let diagnostic = &bindings.diagnostics[0];
+        assert_eq!(diagnostic.code, DiagnosticCode::CannotDerive);
+        assert_eq!(diagnostic.item.as_deref(), Some("Named"));
+        assert_eq!(
+            diagnostic.message,
+            "Can't derive `std::hash` for `Named`: field `name` is a pointer."
+        );
+
+        config.parse.strict = true;
+        let result = Builder::new()
+            .with_config(config)
+            .with_src(file.path())
+            .generate();
+        assert!(matches!(result, Err(Error::Diagnostics(..))));
+    }
+
+    #[test]
+    fn cannot_derive_c_comparisons() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"#[repr(C)] pub struct Callback { f: extern \"C\" fn() }\n\
+              #[no_mangle] pub extern \"C\" fn root(callback: Callback) {}\n\
+              #[no_mangle] pub extern \"C\" fn Callback_eq() {}\n",
+        )
+        .unwrap();
+
+        let mut config = Config {
+            language: Language::C,
+            ..Default::default()
+        };
+        config.structure.derive_c_comparisons = true;
+        config.structure.derive_eq = true;
+        config.structure.derive_lt = true;
+        let bindings = Builder::new()
+            .with_config(config)
+            .with_src(file.path())
+            .generate()
+            .unwrap();
+        let messages: Vec<_> = bindings
+            .diagnostics
+            .iter()
+            .map(|x| {
+                assert_eq!(x.code, DiagnosticCode::CannotDerive);
+                assert_eq!(x.item.as_deref(), Some("Callback"));
+                &x.message[..]
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                "Can't derive `Callback_eq` for `Callback`: an exported item has that name.",
+                "Can't derive `Callback_cmp` for `Callback`: field `f` is a function pointer.",
+            ]
+        );
+    }
+
+    #[test]
+    fn enum_with_data_in_c89() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(
+            &mut file,
+            b"#[repr(C)] pub enum Shape { Circle(f32), Empty }\n\
+              #[no_mangle] pub extern \"C\" fn root(shape: Shape) {}\n",
+        )
+        .unwrap();
+
+        let config = Config {
+            language: Language::C,
+            c_standard: CStandard::C89,
+            ..Default::default()
+        };
+        let result = Builder::new()
+            .with_config(config)
+            .with_src(file.path())
+            .generate();
+        let Err(Error::Diagnostics(diagnostics)) = result else {
+            panic!("enums with data shouldn't be written for C89");
+        };
+        assert_eq!(diagnostics.len(), 1, "{diagnostics:?}");
+        assert_eq!(diagnostics[0].code, DiagnosticCode::UnsupportedByStandard);
+        assert_eq!(diagnostics[0].item.as_deref(), Some("Shape"));
+    }
+
+    // What plugins do to the bindings is covered by the `plugin` fixture.
+    #[cfg(unix)]
+    #[test]
+    fn plugins() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("lib.rs");
+        std::fs::write(
+            &src,
+            "#[repr(C)] pub struct Foo { x: i32 }\n\
+             #[no_mangle] pub extern \"C\" fn f(foo: Foo) {}\n",
+        )
+        .unwrap();
+        let plugin = |name: &str, response: &str| {
+            let path = dir.path().join(name);
+            std::fs::write(
+                &path,
+                format!(
+                    "#!/bin/sh\ncat > {}.json\necho '{response}'\n",
+                    path.display()
+                ),
+            )
+            .unwrap();
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        };
+        plugin(
+            "extra",
+            r#"{"files": [{"path": "extra.txt", "content": "extra"}]}"#,
+        );
+        plugin("fail", r#"{"error": "nope"}"#);
+
+        let config = dir.path().join("cbindgen.toml");
+        std::fs::write(&config, "language = \"C\"\nplugins = [\"./extra\"]\n").unwrap();
+        let bindings = Builder::new()
+            .with_config(Config::from_file(&config).unwrap())
+            .with_src(&src)
+            .generate()
+            .unwrap();
+        assert!(bindings.write_to_file(dir.path().join("out").join("lib.h")));
+        let extra = dir.path().join("out").join("extra.txt");
+        assert_eq!(std::fs::read_to_string(extra).unwrap(), "extra");
+        let request = std::fs::read_to_string(dir.path().join("extra.json")).unwrap();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        assert_eq!(request["language"], "C");
+        assert_eq!(request["items"][0]["rust_name"], "Foo");
+        assert_eq!(request["items"][0]["kind"], "structs");
+        assert_eq!(
+            request["items"][0]["fields"][0],
+            serde_json::json!({
+                "id": 0,
+                "name": "x",
+                "type": {"primitive": {"integer": {"kind": "b32", "signed": true, "zeroable": true}}},
+                "documentation": [],
+            })
+        );
+        assert_eq!(
+            request["items"][1]["args"][0]["type"]["path"]["path"],
+            "Foo"
+        );
+        assert_eq!(
+            request["items"][1]["ret"],
+            serde_json::json!({"primitive": "void"})
+        );
+
+        let mut config = Config::from_file(&config).unwrap();
+        config.plugins.push("./fail".to_owned());
+        let result = Builder::new().with_config(config).with_src(&src).generate();
+        assert!(matches!(result, Err(Error::Plugin { reason, .. }) if reason == "nope"));
+    }
 
     #[test]
     fn with_style() {
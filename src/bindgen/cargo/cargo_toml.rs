@@ -9,6 +9,8 @@ use std::io;
 use std::io::Read;
 use std::path::Path;
 
+use serde::de::DeserializeOwned;
+
 #[derive(Debug)]
 /// Possible errors that can occur during Cargo.toml parsing.
 pub enum Error {
@@ -55,13 +57,39 @@ pub struct Manifest {
 #[derive(Clone, Deserialize, Debug)]
 pub struct Package {
     pub name: String,
+    /// Left as any value, as only the config reads it.
+    pub metadata: Option<toml::Value>,
 }
 
-/// Parse the Cargo.toml for a given path
-pub fn manifest(manifest_path: &Path) -> Result<Manifest, Error> {
+/// A manifest that may be a virtual manifest, without a `[package]`.
+#[derive(Clone, Deserialize, Debug)]
+pub struct WorkspaceManifest {
+    pub package: Option<Package>,
+    pub workspace: Option<Workspace>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct Workspace {
+    pub metadata: Option<toml::Value>,
+    /// Paths of directories that aren't part of the workspace.
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+fn load<T: DeserializeOwned>(manifest_path: &Path) -> Result<T, Error> {
     let mut s = String::new();
     let mut f = File::open(manifest_path)?;
     f.read_to_string(&mut s)?;
 
-    toml::from_str::<Manifest>(&s).map_err(|x| x.into())
+    toml::from_str::<T>(&s).map_err(|x| x.into())
+}
+
+/// Parse the Cargo.toml for a given path
+pub fn manifest(manifest_path: &Path) -> Result<Manifest, Error> {
+    load(manifest_path)
+}
+
+/// Parse the Cargo.toml for a given path, which may be a virtual manifest
+pub fn workspace_manifest(manifest_path: &Path) -> Result<WorkspaceManifest, Error> {
+    load(manifest_path)
 }
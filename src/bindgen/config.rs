@@ -2,15 +2,19 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::default::Default;
-use std::str::FromStr;
 use std::{fmt, fs, path::Path as StdPath, path::PathBuf as StdPathBuf};
 
+use schemars::JsonSchema;
 use serde::de::value::{MapAccessDeserializer, SeqAccessDeserializer};
 use serde::de::{Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
 
+use crate::bindgen::cargo::cargo_toml;
+use crate::bindgen::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::bindgen::error::Error;
 use crate::bindgen::ir::annotation::AnnotationSet;
+use crate::bindgen::ir::cfg::check_define_key;
 use crate::bindgen::ir::path::Path;
 use crate::bindgen::ir::repr::ReprAlign;
 pub use crate::bindgen::rename::RenameRule;
@@ -25,29 +29,11 @@ pub enum Language {
     Cython,
 }
 
-impl FromStr for Language {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Language, Self::Err> {
-        match s {
-            "cxx" => Ok(Language::Cxx),
-            "Cxx" => Ok(Language::Cxx),
-            "CXX" => Ok(Language::Cxx),
-            "cpp" => Ok(Language::Cxx),
-            "Cpp" => Ok(Language::Cxx),
-            "CPP" => Ok(Language::Cxx),
-            "c++" => Ok(Language::Cxx),
-            "C++" => Ok(Language::Cxx),
-            "c" => Ok(Language::C),
-            "C" => Ok(Language::C),
-            "cython" => Ok(Language::Cython),
-            "Cython" => Ok(Language::Cython),
-            _ => Err(format!("Unrecognized Language: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(Language);
+config_enum_str!(Language, "Language" {
+    Cxx => ["C++", "c++", "cxx", "Cxx", "CXX", "cpp", "Cpp", "CPP"],
+    C => ["C", "c"],
+    Cython => ["Cython", "cython"],
+});
 
 impl Language {
     pub(crate) fn typedef(self) -> &'static str {
@@ -94,21 +80,12 @@ impl LineEndingStyle {
     }
 }
 
-impl FromStr for LineEndingStyle {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_lowercase().as_ref() {
-            "native" => Ok(Self::Native),
-            "lf" => Ok(Self::LF),
-            "crlf" => Ok(Self::CRLF),
-            "cr" => Ok(Self::CR),
-            _ => Err(format!("Unrecognized line ending style: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(LineEndingStyle);
+config_enum_str!(LineEndingStyle, "line ending style", ignore_case {
+    LF => ["LF", "lf"],
+    CR => ["CR", "cr"],
+    CRLF => ["CRLF", "crlf"],
+    Native => ["Native", "native"],
+});
 
 /// A style of braces to use for generating code.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -117,21 +94,10 @@ pub enum Braces {
     NextLine,
 }
 
-impl FromStr for Braces {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Braces, Self::Err> {
-        match s {
-            "SameLine" => Ok(Braces::SameLine),
-            "same_line" => Ok(Braces::SameLine),
-            "NextLine" => Ok(Braces::NextLine),
-            "next_line" => Ok(Braces::NextLine),
-            _ => Err(format!("Unrecognized Braces: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(Braces);
+config_enum_str!(Braces, "Braces" {
+    SameLine => ["SameLine", "same_line"],
+    NextLine => ["NextLine", "next_line"],
+});
 
 /// A type of layout to use when generating long lines of code.
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -141,23 +107,75 @@ pub enum Layout {
     Auto,
 }
 
-impl FromStr for Layout {
-    type Err = String;
+config_enum_str!(Layout, "Layout" {
+    Horizontal => ["Horizontal", "horizontal"],
+    Vertical => ["Vertical", "vertical"],
+    Auto => ["Auto", "auto"],
+});
+
+/// A revision of the C standard to write C bindings for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CStandard {
+    C89,
+    C99,
+    C11,
+    C17,
+    C23,
+}
+
+config_enum_str!(CStandard, "C standard", ignore_case {
+    C89 => ["c89", "C89", "c90", "C90"],
+    C99 => ["c99", "C99"],
+    C11 => ["c11", "C11"],
+    C17 => ["c17", "C17", "c18", "C18"],
+    C23 => ["c23", "C23"],
+});
+
+/// A revision of the C++ standard to write C++ bindings for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CppStandard {
+    Cpp11,
+    Cpp14,
+    Cpp17,
+    Cpp20,
+    Cpp23,
+}
 
-    fn from_str(s: &str) -> Result<Layout, Self::Err> {
-        match s {
-            "Horizontal" => Ok(Layout::Horizontal),
-            "horizontal" => Ok(Layout::Horizontal),
-            "Vertical" => Ok(Layout::Vertical),
-            "vertical" => Ok(Layout::Vertical),
-            "Auto" => Ok(Layout::Auto),
-            "auto" => Ok(Layout::Auto),
-            _ => Err(format!("Unrecognized Layout: '{s}'.")),
+config_enum_str!(CppStandard, "C++ standard", ignore_case {
+    Cpp11 => ["c++11", "C++11"],
+    Cpp14 => ["c++14", "C++14"],
+    Cpp17 => ["c++17", "C++17"],
+    Cpp20 => ["c++20", "C++20"],
+    Cpp23 => ["c++23", "C++23"],
+});
+
+/// A formatting library to generate `formatter` specializations for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Formatter {
+    #[default]
+    None,
+    /// `std::format`, from C++20.
+    Std,
+    /// The {fmt} library.
+    Fmt,
+}
+
+impl Formatter {
+    /// The namespace of the library, if any.
+    pub(crate) fn namespace(self) -> Option<&'static str> {
+        match self {
+            Formatter::None => None,
+            Formatter::Std => Some("std"),
+            Formatter::Fmt => Some("fmt"),
         }
     }
 }
 
-deserialize_enum_str!(Layout);
+config_enum_str!(Formatter, "formatter", ignore_case {
+    None => ["none", "None"],
+    Std => ["std"],
+    Fmt => ["fmt"],
+});
 
 /// How the comments containing documentation should be styled.
 #[derive(Debug, Clone, PartialEq, Eq, Copy)]
@@ -169,23 +187,13 @@ pub enum DocumentationStyle {
     Auto,
 }
 
-impl FromStr for DocumentationStyle {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<DocumentationStyle, Self::Err> {
-        match s.to_lowercase().as_ref() {
-            "c" => Ok(DocumentationStyle::C),
-            "c99" => Ok(DocumentationStyle::C99),
-            "cxx" => Ok(DocumentationStyle::Cxx),
-            "c++" => Ok(DocumentationStyle::Cxx),
-            "doxy" => Ok(DocumentationStyle::Doxy),
-            "auto" => Ok(DocumentationStyle::Auto),
-            _ => Err(format!("Unrecognized documentation style: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(DocumentationStyle);
+config_enum_str!(DocumentationStyle, "documentation style", ignore_case {
+    C => ["c", "C"],
+    C99 => ["c99", "C99"],
+    Doxy => ["doxy", "Doxy"],
+    Cxx => ["c++", "C++", "cxx", "Cxx"],
+    Auto => ["auto", "Auto"],
+});
 
 /// How much of the documentation to include in the header file.
 #[derive(Debug, Clone, Copy)]
@@ -194,19 +202,10 @@ pub enum DocumentationLength {
     Full,
 }
 
-impl FromStr for DocumentationLength {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<DocumentationLength, Self::Err> {
-        match s.to_lowercase().as_ref() {
-            "short" => Ok(DocumentationLength::Short),
-            "full" => Ok(DocumentationLength::Full),
-            _ => Err(format!("Unrecognized documentation style: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(DocumentationLength);
+config_enum_str!(DocumentationLength, "documentation length", ignore_case {
+    Short => ["short", "Short"],
+    Full => ["full", "Full"],
+});
 
 /// A style of Style to use when generating structs and enums.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
@@ -242,23 +241,11 @@ impl Style {
     }
 }
 
-impl FromStr for Style {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Style, Self::Err> {
-        match s {
-            "Both" => Ok(Style::Both),
-            "both" => Ok(Style::Both),
-            "Tag" => Ok(Style::Tag),
-            "tag" => Ok(Style::Tag),
-            "Type" => Ok(Style::Type),
-            "type" => Ok(Style::Type),
-            _ => Err(format!("Unrecognized Style: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(Style);
+config_enum_str!(Style, "Style" {
+    Both => ["both", "Both"],
+    Tag => ["tag", "Tag"],
+    Type => ["type", "Type"],
+});
 
 /// Different item types that we can generate and filter.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -273,26 +260,16 @@ pub enum ItemType {
     Functions,
 }
 
-impl FromStr for ItemType {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use self::ItemType::*;
-        Ok(match &*s.to_lowercase() {
-            "constants" => Constants,
-            "globals" => Globals,
-            "enums" => Enums,
-            "structs" => Structs,
-            "unions" => Unions,
-            "typedefs" => Typedefs,
-            "opaque" => OpaqueItems,
-            "functions" => Functions,
-            _ => return Err(format!("Unrecognized Style: '{s}'.")),
-        })
-    }
-}
-
-deserialize_enum_str!(ItemType);
+config_enum_str!(ItemType, "item type", ignore_case {
+    Constants => ["constants"],
+    Globals => ["globals"],
+    Enums => ["enums"],
+    Structs => ["structs"],
+    Unions => ["unions"],
+    Typedefs => ["typedefs"],
+    OpaqueItems => ["opaque"],
+    Functions => ["functions"],
+});
 
 /// Type which specifies the sort order of functions
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -301,32 +278,27 @@ pub enum SortKey {
     None,
 }
 
-impl FromStr for SortKey {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        use self::SortKey::*;
-        Ok(match &*s.to_lowercase() {
-            "name" => Name,
-            "none" => None,
-            _ => return Err(format!("Unrecognized sort option: '{s}'.")),
-        })
-    }
-}
-
-deserialize_enum_str!(SortKey);
+config_enum_str!(SortKey, "sort option", ignore_case {
+    Name => ["Name", "name"],
+    None => ["None", "none"],
+});
 
 /// Settings to apply when exporting items.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct ExportConfig {
     /// A list of additional items not used by exported functions to include in
-    /// the generated bindings
+    /// the generated bindings. Entries are `NamePattern`s: exact names, globs,
+    /// `re:` regular expressions or module-qualified paths.
     pub include: Vec<String>,
-    /// A list of items to not include in the generated bindings
+    /// A list of items to not include in the generated bindings, as
+    /// `NamePattern`s
     pub exclude: Vec<String>,
+    /// A list of structs, unions and enums to only forward declare, as
+    /// `NamePattern`s
+    pub opaque: Vec<String>,
     /// Table of name conversions to apply to item names
     pub rename: HashMap<String, String>,
     /// Table of raw strings to prepend to the body of items.
@@ -341,10 +313,22 @@ pub struct ExportConfig {
     pub renaming_overrides_prefixing: bool,
     /// Mangling configuration.
     pub mangle: MangleConfig,
+    /// Table of foreign types to write in place of Rust types, keyed by type
+    /// name. A key may be a path, but only its last segment is matched, as
+    /// types are referred to by name. Mapped types are written verbatim and
+    /// never defined.
+    pub type_map: HashMap<String, TypeMapping>,
+    /// Instantiations of generic types to generate even if they aren't used,
+    /// keyed by generic type, each entry being the generic arguments of one
+    /// instantiation, like `Pair = ["u32, f32"]`.
+    pub instantiate: BTreeMap<String, Vec<String>>,
+    /// Table of names to give to instantiations of generic types instead of
+    /// their mangled names, like `"Foo<u32>" = "FooU32"`.
+    pub monomorph_names: HashMap<String, String>,
 }
 
 /// Mangling-specific configuration.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -355,7 +339,67 @@ pub struct MangleConfig {
     pub remove_underscores: bool,
 }
 
+/// A foreign type to write in place of a Rust type.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum TypeMapping {
+    /// The same type for every language.
+    Name(String),
+    /// A type per language, and the headers it needs.
+    Table(TypeMappingTable),
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+#[serde(default)]
+pub struct TypeMappingTable {
+    /// The type for the languages without their own entry.
+    pub name: Option<String>,
+    pub c: Option<String>,
+    pub cpp: Option<String>,
+    pub cython: Option<String>,
+    /// Headers to `#include "..."` if the type is used.
+    pub includes: Vec<String>,
+    /// Headers to `#include <...>` if the type is used.
+    pub sys_includes: Vec<String>,
+}
+
+impl TypeMapping {
+    /// The type to write for `language`, if the mapping has one.
+    pub(crate) fn name(&self, language: Language) -> Option<&str> {
+        match *self {
+            TypeMapping::Name(ref name) => Some(name),
+            TypeMapping::Table(ref table) => match language {
+                Language::C => table.c.as_ref(),
+                Language::Cxx => table.cpp.as_ref(),
+                Language::Cython => table.cython.as_ref(),
+            }
+            .or(table.name.as_ref())
+            .map(String::as_str),
+        }
+    }
+}
+
 impl ExportConfig {
+    /// The Rust name and foreign type of each `type_map` entry that applies
+    /// to `language`.
+    pub(crate) fn type_mappings(
+        &self,
+        language: Language,
+    ) -> impl Iterator<Item = (&str, &str, &TypeMapping)> {
+        self.type_map
+            .iter()
+            .filter_map(move |(name, mapping)| Some((&name[..], mapping.name(language)?, mapping)))
+    }
+
+    /// The foreign type to write in place of the Rust type named `name`.
+    pub(crate) fn mapped_type(&self, name: &str, language: Language) -> Option<&str> {
+        self.type_mappings(language)
+            .find(|&(rust_name, ..)| rust_name == name)
+            .map(|(_, foreign_name, _)| foreign_name)
+    }
+
     pub(crate) fn should_generate(&self, item_type: ItemType) -> bool {
         self.item_types.is_empty() || self.item_types.contains(&item_type)
     }
@@ -382,7 +426,7 @@ impl ExportConfig {
 }
 
 /// Settings to apply to generated types with layout modifiers.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -405,7 +449,7 @@ impl LayoutConfig {
 }
 
 /// Settings to apply to generated functions.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -466,7 +510,7 @@ impl FunctionConfig {
 }
 
 /// Settings to apply to generated structs.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -488,8 +532,19 @@ pub struct StructConfig {
     pub derive_gt: bool,
     /// Whether to generate a greater than or equal to operator on structs with one field
     pub derive_gte: bool,
+    /// Whether the comparison options also generate `static inline` `Foo_eq`
+    /// and `Foo_cmp` functions in C.
+    pub derive_c_comparisons: bool,
     /// Whether to generate a ostream serializer for the struct
     pub derive_ostream: bool,
+    /// Whether to generate a `std::hash` specialization for the struct
+    pub derive_hash: bool,
+    /// The library to generate a `formatter` specialization for the struct
+    /// for, in the format of `derive_ostream`
+    pub derive_formatter: Formatter,
+    /// Whether `derive_hash` hashes pointers by address, rather than skipping
+    /// the types containing them
+    pub hash_pointers: bool,
     /// Whether associated constants should be in the body. Only applicable to
     /// non-transparent structs, and in C++-only.
     pub associated_constants_in_body: bool,
@@ -547,16 +602,34 @@ impl StructConfig {
         }
         self.derive_gte
     }
+    pub(crate) fn derive_c_comparisons(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-c-comparisons") {
+            return x;
+        }
+        self.derive_c_comparisons
+    }
     pub(crate) fn derive_ostream(&self, annotations: &AnnotationSet) -> bool {
         if let Some(x) = annotations.bool("derive-ostream") {
             return x;
         }
         self.derive_ostream
     }
+    pub(crate) fn derive_hash(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-hash") {
+            return x;
+        }
+        self.derive_hash
+    }
+    pub(crate) fn derive_formatter(&self, annotations: &AnnotationSet) -> Formatter {
+        if let Some(x) = annotations.parse_atom::<Formatter>("derive-formatter") {
+            return x;
+        }
+        self.derive_formatter
+    }
 }
 
 /// Settings to apply to generated enums.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -583,6 +656,17 @@ pub struct EnumConfig {
     /// responsible to provide the necessary header, otherwise `assert` will be
     /// used, and `<cassert>` will be included.
     pub cast_assert_name: Option<String>,
+    /// Whether `derive_helper_methods`, `derive_const_casts` and
+    /// `derive_mut_casts` also generate `static inline` functions in C.
+    pub derive_c_helpers: bool,
+    /// Whether to generate `visit(F&& f)`, `index()` and `holds<Tag>()`
+    /// methods for tagged enums.
+    pub derive_visit: bool,
+    /// Whether to generate a `std::hash` specialization for tagged enums.
+    pub derive_hash: bool,
+    /// The library to generate a `formatter` specialization for the enum
+    /// for, in the format of `derive_ostream`
+    pub derive_formatter: Formatter,
     /// The way to annotation this enum as #[must_use].
     pub must_use: Option<String>,
     /// The way to annotation this function as #[deprecated] without notes
@@ -610,6 +694,21 @@ pub struct EnumConfig {
     /// Whether to generate empty, private default-constructors for tagged
     /// enums.
     pub private_default_tagged_enum_constructor: bool,
+    /// Whether to write `#[non_exhaustive]` enums without data as an integer
+    /// typedef and a constant for each variant, since values that aren't
+    /// variants may be passed across the FFI boundary.
+    pub non_exhaustive_as_constants: bool,
+    /// Whether to add a `_UNKNOWN` constant with the largest value of the type
+    /// to the enums written as constants.
+    pub add_unknown_sentinel: bool,
+    /// Whether to generate a `Foo_to_string` function for enums without data.
+    pub derive_to_string: bool,
+    /// Whether to generate a `Foo_from_string` function for enums without
+    /// data.
+    pub derive_from_string: bool,
+    /// Whether `Foo_to_string` and `Foo_from_string` use the names of the
+    /// variants in Rust rather than in the bindings.
+    pub to_string_rust_names: bool,
 }
 
 impl Default for EnumConfig {
@@ -623,6 +722,10 @@ impl Default for EnumConfig {
             derive_const_casts: false,
             derive_mut_casts: false,
             cast_assert_name: None,
+            derive_c_helpers: false,
+            derive_visit: false,
+            derive_hash: false,
+            derive_formatter: Formatter::None,
             must_use: None,
             deprecated: None,
             deprecated_with_note: None,
@@ -634,6 +737,11 @@ impl Default for EnumConfig {
             derive_ostream: false,
             enum_class: true,
             private_default_tagged_enum_constructor: false,
+            non_exhaustive_as_constants: false,
+            add_unknown_sentinel: false,
+            derive_to_string: false,
+            derive_from_string: false,
+            to_string_rust_names: false,
         }
     }
 }
@@ -663,6 +771,30 @@ impl EnumConfig {
         }
         self.derive_mut_casts
     }
+    pub(crate) fn derive_c_helpers(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-c-helpers") {
+            return x;
+        }
+        self.derive_c_helpers
+    }
+    pub(crate) fn derive_visit(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-visit") {
+            return x;
+        }
+        self.derive_visit
+    }
+    pub(crate) fn derive_hash(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-hash") {
+            return x;
+        }
+        self.derive_hash
+    }
+    pub(crate) fn derive_formatter(&self, annotations: &AnnotationSet) -> Formatter {
+        if let Some(x) = annotations.parse_atom::<Formatter>("derive-formatter") {
+            return x;
+        }
+        self.derive_formatter
+    }
     pub(crate) fn derive_tagged_enum_destructor(&self, annotations: &AnnotationSet) -> bool {
         if let Some(x) = annotations.bool("derive-tagged-enum-destructor") {
             return x;
@@ -702,17 +834,48 @@ impl EnumConfig {
         }
         self.private_default_tagged_enum_constructor
     }
+    pub(crate) fn non_exhaustive_as_constants(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("non-exhaustive-as-constants") {
+            return x;
+        }
+        self.non_exhaustive_as_constants
+    }
+    pub(crate) fn add_unknown_sentinel(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("add-unknown-sentinel") {
+            return x;
+        }
+        self.add_unknown_sentinel
+    }
+    pub(crate) fn derive_to_string(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-to-string") {
+            return x;
+        }
+        self.derive_to_string
+    }
+    pub(crate) fn derive_from_string(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("derive-from-string") {
+            return x;
+        }
+        self.derive_from_string
+    }
+    pub(crate) fn to_string_rust_names(&self, annotations: &AnnotationSet) -> bool {
+        if let Some(x) = annotations.bool("to-string-rust-names") {
+            return x;
+        }
+        self.to_string_rust_names
+    }
 }
 
 /// Settings to apply to generated constants.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct ConstantConfig {
     /// Whether a generated constant can be a static const in C++ mode.
     pub allow_static_const: bool,
-    /// Whether a generated constant should be constexpr in C++ mode.
+    /// Whether a generated constant should be constexpr in C++ mode, or in C23
+    /// mode for constants of primitive types.
     pub allow_constexpr: bool,
     /// Sort key for constants
     pub sort_by: Option<SortKey>,
@@ -729,7 +892,7 @@ impl Default for ConstantConfig {
 }
 
 /// Settings for custom macro expansion.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -745,22 +908,13 @@ pub enum Profile {
     Release,
 }
 
-impl FromStr for Profile {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Profile, Self::Err> {
-        match s {
-            "debug" | "Debug" => Ok(Profile::Debug),
-            "release" | "Release" => Ok(Profile::Release),
-            _ => Err(format!("Unrecognized Profile: '{s}'.")),
-        }
-    }
-}
-
-deserialize_enum_str!(Profile);
+config_enum_str!(Profile, "Profile" {
+    Debug => ["debug", "Debug"],
+    Release => ["release", "Release"],
+});
 
 /// Settings to apply when running `rustc -Zunpretty=expanded`
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -829,8 +983,20 @@ fn retrocomp_parse_expand_config_deserialize<'de, D: Deserializer<'de>>(
     deserializer.deserialize_any(ParseExpandVisitor)
 }
 
+// The schema of the two forms accepted by `retrocomp_parse_expand_config_deserialize`.
+fn retrocomp_parse_expand_config_schema(
+    generator: &mut schemars::SchemaGenerator,
+) -> schemars::Schema {
+    schemars::json_schema!({
+        "anyOf": [
+            generator.subschema_for::<Vec<String>>(),
+            generator.subschema_for::<ParseExpandConfig>(),
+        ],
+    })
+}
+
 /// Settings to apply when parsing.
-#[derive(Debug, Default, Clone, Deserialize)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -844,12 +1010,13 @@ pub struct ParseConfig {
     /// before parsing. A crate marked in `expand` doesn't need to be added to any
     /// whitelist.
     pub parse_deps: bool,
-    /// An optional whitelist of names of crates to parse
+    /// An optional whitelist of names of crates to parse, as `NamePattern`s
     pub include: Option<Vec<String>>,
-    /// The names of crates to not parse
+    /// The names of crates to not parse, as `NamePattern`s
     pub exclude: Vec<String>,
     /// The configuration options for `rustc -Zunpretty=expanded`
     #[serde(deserialize_with = "retrocomp_parse_expand_config_deserialize")]
+    #[schemars(schema_with = "retrocomp_parse_expand_config_schema")]
     pub expand: ParseExpandConfig,
     /// Whether to use a new temporary target directory when running `rustc -Zunpretty=expanded`.
     /// This may be required for some build processes.
@@ -857,6 +1024,9 @@ pub struct ParseConfig {
     /// List of crate names which generate consts, statics, and fns. By default
     /// no dependent crates generate them.
     pub extra_bindings: Vec<String>,
+    /// Whether to fail instead of only warning when an item is skipped or an
+    /// annotation is unknown or misplaced.
+    pub strict: bool,
 }
 
 impl ParseConfig {
@@ -875,7 +1045,7 @@ impl ParseConfig {
 }
 
 /// Settings to apply to pointers
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -886,8 +1056,28 @@ pub struct PtrConfig {
     pub nullable_attribute: Option<String>,
 }
 
+/// A header generated by `Builder::generate_many`. Every setting that isn't
+/// set here is taken from the rest of the config.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+#[serde(deny_unknown_fields)]
+pub struct OutputConfig {
+    /// The file to write the header to, relative to the config file.
+    pub path: StdPathBuf,
+    /// The language to output the header in.
+    pub language: Option<Language>,
+    /// The style to use when generating structs and enums.
+    pub style: Option<Style>,
+    /// The include guard of the header.
+    pub include_guard: Option<String>,
+    /// The namespace to output around the header.
+    pub namespace: Option<String>,
+    /// The list of namespaces to output around the header.
+    pub namespaces: Option<Vec<String>>,
+}
+
 /// Settings specific to Cython bindings.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
@@ -900,11 +1090,20 @@ pub struct CythonConfig {
 }
 
 /// A collection of settings to customize the generated bindings.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 #[serde(deny_unknown_fields)]
 #[serde(default)]
 pub struct Config {
+    /// Other config files to inherit settings from, relative to the file that
+    /// lists them. Later files override earlier ones, and the settings of the
+    /// file itself override all of them. Tables are merged key by key, any
+    /// other value is replaced.
+    pub extends: Vec<String>,
+    /// Named sets of settings that can be merged on top of this config with
+    /// `--overlay` or `Builder::with_overlay`, for example `[overlay.cpp]`.
+    #[schemars(with = "HashMap<String, Config>")]
+    pub overlay: HashMap<String, toml::Table>,
     /// Optional text to output at the beginning of the file
     pub header: Option<String>,
     /// A list of additional includes to put at the beginning of the generated header
@@ -948,6 +1147,10 @@ pub struct Config {
     pub language: Language,
     /// Include preprocessor defines in C bindings to ensure C++ compatibility
     pub cpp_compat: bool,
+    /// The revision of the C standard the C bindings are written for
+    pub c_standard: CStandard,
+    /// The revision of the C++ standard the C++ bindings are written for
+    pub cpp_standard: CppStandard,
     /// The style to declare structs, enums and unions in for C
     pub style: Style,
     /// Default sort key for functions and constants.
@@ -1023,6 +1226,14 @@ pub struct Config {
     pub only_target_dependencies: bool,
     /// Configuration options specific to Cython.
     pub cython: CythonConfig,
+    /// Additional headers to generate from the same parse with
+    /// `Builder::generate_many`, for example a C and a C++ header.
+    pub output: Vec<OutputConfig>,
+    /// Programs to run on the items before the bindings are written. Each one
+    /// reads a JSON description of the items on stdin and prints the changes
+    /// to make and any additional files to write on stdout. Paths with more
+    /// than one component are relative to the config file.
+    pub plugins: Vec<String>,
     #[doc(hidden)]
     #[serde(skip)]
     /// Internal field for tracking from which file the config was loaded.
@@ -1037,6 +1248,8 @@ pub struct Config {
 impl Default for Config {
     fn default() -> Config {
         Config {
+            extends: Vec::new(),
+            overlay: HashMap::new(),
             header: None,
             includes: Vec::new(),
             sys_includes: Vec::new(),
@@ -1057,6 +1270,8 @@ impl Default for Config {
             line_endings: LineEndingStyle::default(),
             language: Language::Cxx,
             cpp_compat: false,
+            c_standard: CStandard::C99,
+            cpp_standard: CppStandard::Cpp11,
             style: Style::default(),
             usize_is_size_t: false,
             sort_by: SortKey::None,
@@ -1075,16 +1290,63 @@ impl Default for Config {
             pointer: PtrConfig::default(),
             only_target_dependencies: false,
             cython: CythonConfig::default(),
+            output: Vec::new(),
+            plugins: Vec::new(),
             config_path: None,
         }
     }
 }
 
 impl Config {
+    /// The JSON schema of config files, for editors to complete and validate
+    /// them with.
+    pub fn schema() -> serde_json::Value {
+        let mut schema = schemars::generate::SchemaSettings::draft07()
+            .into_generator()
+            .into_root_schema_for::<Config>();
+        schema.insert("title".to_owned(), "cbindgen.toml".into());
+        schema.to_value()
+    }
+
     pub(crate) fn cpp_compatible_c(&self) -> bool {
         self.language == Language::C && self.cpp_compat
     }
 
+    /// Whether the bindings are C, written for `standard` or a later one.
+    pub(crate) fn c_standard_at_least(&self, standard: CStandard) -> bool {
+        self.language == Language::C && self.c_standard >= standard
+    }
+
+    /// Whether the bindings are C++, written for `standard` or a later one.
+    pub(crate) fn cpp_standard_at_least(&self, standard: CppStandard) -> bool {
+        self.language == Language::Cxx && self.cpp_standard >= standard
+    }
+
+    /// The standard `attribute` to use in C23 when the option for it isn't set.
+    pub(crate) fn c23_attribute(&self, attribute: &'static str) -> Option<&'static str> {
+        self.c_standard_at_least(CStandard::C23)
+            .then_some(attribute)
+    }
+
+    /// The storage class of the functions defined in headers, as C89 doesn't
+    /// have `inline`.
+    pub(crate) fn static_inline(&self) -> &'static str {
+        if self.c_standard_at_least(CStandard::C99) {
+            "static inline"
+        } else {
+            "static"
+        }
+    }
+
+    /// A comment to end a line with, as C89 doesn't have `//` comments.
+    pub(crate) fn line_comment(&self, text: &str) -> String {
+        if self.language == Language::C && self.c_standard == CStandard::C89 {
+            format!("/* {text} */")
+        } else {
+            format!("// {text}")
+        }
+    }
+
     pub(crate) fn include_guard(&self) -> Option<&str> {
         if self.language == Language::Cython {
             None
@@ -1110,26 +1372,991 @@ impl Config {
     }
 
     pub fn from_file<P: AsRef<StdPath>>(file_name: P) -> Result<Config, String> {
-        let config_text = fs::read_to_string(file_name.as_ref()).map_err(|_| {
+        let table = load_toml(file_name.as_ref(), &mut vec![], &mut vec![])?;
+        let mut config = toml::Value::Table(table)
+            .try_into::<Config>()
+            .map_err(|e| {
+                format!(
+                    "Couldn't parse config file {}: {e}.",
+                    file_name.as_ref().display()
+                )
+            })?;
+        config.config_path = Some(StdPathBuf::from(file_name.as_ref()));
+        Ok(config)
+    }
+
+    /// The config files that `extends` and the workspace metadata pulled into
+    /// the config loaded from `config_path`, found by loading it again.
+    pub(crate) fn extended_config_paths(&self) -> Vec<StdPathBuf> {
+        let mut extended = vec![];
+        if let Some(ref path) = self.config_path {
+            // The config was loaded from these files before, so there can only
+            // be errors if they changed since, and then they are stale anyway.
+            let _ = if path.file_name() == Some("Cargo.toml".as_ref()) {
+                let crate_dir = path.parent().unwrap_or_else(|| StdPath::new(""));
+                load_cargo_metadata(crate_dir, &mut extended).map(drop)
+            } else {
+                load_toml(path, &mut vec![], &mut extended).map(drop)
+            };
+        }
+        extended
+    }
+
+    /// Sets config options from `key.path=value` overrides, as if they had
+    /// been written at the end of the config file. Values are parsed as TOML
+    /// values (`true`, `42`, `["a", "b"]`), and are taken as strings if that
+    /// fails.
+    pub fn apply_overrides<I, S>(&mut self, overrides: I) -> Result<(), String>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut table = toml::Table::new();
+        let mut applied = vec![];
+        for assignment in overrides {
+            let assignment = assignment.as_ref();
+            let (key, value) = split_assignment(assignment).ok_or_else(|| {
+                format!("Invalid config override `{assignment}`, expected `key.path=value`.")
+            })?;
+            set_toml(&mut table, &key, parse_toml_value(value.trim()))
+                .map_err(|e| format!("Invalid config override `{assignment}`: {e}."))?;
+            applied.push(assignment.to_owned());
+        }
+        if applied.is_empty() {
+            return Ok(());
+        }
+
+        self.merge(table).map_err(|e| {
             format!(
-                "Couldn't open config file: {}.",
-                file_name.as_ref().display()
+                "Couldn't apply config overrides `{}`: {}.",
+                applied.join("`, `"),
+                e.trim_end().replace('\n', " ")
             )
-        })?;
+        })
+    }
 
-        let mut config = toml::from_str::<Config>(&config_text)
-            .map_err(|e| format!("Couldn't parse config file: {e}."))?;
-        config.config_path = Some(StdPathBuf::from(file_name.as_ref()));
-        Ok(config)
+    /// Merges `table` over the current settings, as if it had been written at
+    /// the end of a config file holding them.
+    fn merge(&mut self, table: toml::Table) -> Result<(), String> {
+        let mut merged = toml::Table::try_from(&*self).map_err(|e| e.to_string())?;
+        merge_toml(&mut merged, table);
+        let mut config = toml::Value::Table(merged)
+            .try_into::<Config>()
+            .map_err(|e| e.to_string())?;
+        config.config_path = self.config_path.take();
+        *self = config;
+        Ok(())
+    }
+
+    /// Merges the settings of the overlay named `name` on top of the current
+    /// settings.
+    pub fn apply_overlay(&mut self, name: &str) -> Result<(), String> {
+        let overlay = self
+            .overlay
+            .get(name)
+            .ok_or_else(|| format!("Unknown config overlay `{name}`."))?;
+        if overlay.contains_key("extends") || overlay.contains_key("overlay") {
+            return Err(format!(
+                "Config overlay `{name}` can't set `extends` or `overlay`."
+            ));
+        }
+        self.merge(overlay.clone()).map_err(|e| {
+            format!(
+                "Couldn't apply config overlay `{name}`: {}.",
+                e.trim_end().replace('\n', " ")
+            )
+        })
+    }
+
+    /// The config of each `[[output]]` table, along with the path to write its
+    /// header to.
+    pub(crate) fn outputs(&self) -> Vec<(StdPathBuf, Config)> {
+        let dir = self.config_path.as_deref().and_then(StdPath::parent);
+        self.output
+            .iter()
+            .map(|output| {
+                let mut config = self.clone();
+                config.output.clear();
+                if let Some(language) = output.language {
+                    config.language = language;
+                }
+                if let Some(style) = output.style {
+                    config.style = style;
+                }
+                if output.include_guard.is_some() {
+                    config.include_guard.clone_from(&output.include_guard);
+                }
+                if output.namespace.is_some() {
+                    config.namespace.clone_from(&output.namespace);
+                }
+                if output.namespaces.is_some() {
+                    config.namespaces.clone_from(&output.namespaces);
+                }
+                let path = match dir {
+                    Some(dir) => dir.join(&output.path),
+                    None => output.path.clone(),
+                };
+                (path, config)
+            })
+            .collect()
+    }
+
+    /// The command to run for each of the `plugins`.
+    pub(crate) fn plugin_commands(&self) -> Vec<StdPathBuf> {
+        let dir = self.config_path.as_deref().and_then(StdPath::parent);
+        self.plugins
+            .iter()
+            .map(|plugin| {
+                let path = StdPath::new(plugin);
+                match dir {
+                    Some(dir) if path.components().count() > 1 => dir.join(path),
+                    _ => path.to_owned(),
+                }
+            })
+            .collect()
+    }
+
+    /// Checks settings that are valid on their own but contradict each other
+    /// or can't be used in the generated code. Returns a warning for each
+    /// setting that has no effect, or an error listing every problem that
+    /// makes the config unusable.
+    pub fn validate(&self) -> Result<Vec<Diagnostic>, Error> {
+        let mut warnings = vec![];
+        let mut problems = vec![];
+        let mut ignored = |message: String| {
+            warnings.push(Diagnostic::warning(
+                DiagnosticCode::IgnoredConfigOption,
+                message,
+            ))
+        };
+
+        if self.language == Language::C {
+            let options = [
+                ("namespace", self.namespace.is_some()),
+                ("namespaces", self.namespaces.is_some()),
+                ("using_namespaces", self.using_namespaces.is_some()),
+                ("cython.header", self.cython.header.is_some()),
+            ];
+            for (option, set) in options {
+                if set {
+                    ignored(format!("`{option}` has no effect with `language = \"C\"`."));
+                }
+            }
+        }
+
+        if self.language == Language::C
+            && self.c_standard == CStandard::C89
+            && matches!(
+                self.documentation_style,
+                DocumentationStyle::C99 | DocumentationStyle::Cxx
+            )
+        {
+            ignored(
+                "`documentation_style` writes `//` comments, which C89 doesn't have.".to_owned(),
+            );
+        }
+
+        if self.enumeration.derive_tagged_enum_copy_assignment
+            && !self.enumeration.derive_tagged_enum_copy_constructor
+        {
+            ignored(
+                "`enum.derive_tagged_enum_copy_assignment` has no effect without \
+                 `enum.derive_tagged_enum_copy_constructor`."
+                    .to_owned(),
+            );
+        }
+
+        if self.export.renaming_overrides_prefixing && self.export.rename.is_empty() {
+            ignored(
+                "`export.renaming_overrides_prefixing` has no effect without `export.rename` \
+                 entries."
+                    .to_owned(),
+            );
+        }
+
+        for output in &self.output {
+            if output.language == Some(Language::C) {
+                let namespaces = [
+                    ("namespace", output.namespace.is_some()),
+                    ("namespaces", output.namespaces.is_some()),
+                ];
+                for (option, set) in namespaces {
+                    if set {
+                        ignored(format!(
+                            "`{option}` of the output `{}` has no effect with \
+                             `language = \"C\"`.",
+                            output.path.display()
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut defines: Vec<_> = self.defines.keys().collect();
+        defines.sort();
+        for key in defines {
+            if let Err(reason) = check_define_key(key) {
+                problems.push(format!("Invalid `defines` key `{key}`: {reason}."));
+            }
+        }
+
+        let mut paths = HashSet::new();
+        for output in &self.output {
+            if !paths.insert(&output.path) {
+                problems.push(format!(
+                    "Several `[[output]]` tables write `{}`.",
+                    output.path.display()
+                ));
+            }
+        }
+
+        // C++ can refer to types in other namespaces, like `ns::Foo`.
+        let c_names = std::iter::once(self.language)
+            .chain(self.output.iter().filter_map(|output| output.language))
+            .any(|language| language != Language::Cxx);
+        let mut renames: Vec<_> = self.export.rename.iter().collect();
+        renames.sort();
+        for (name, target) in renames {
+            if c_names && !is_c_identifier(target) {
+                problems.push(format!(
+                    "`export.rename` renames `{name}` to `{target}`, which isn't a valid C \
+                     identifier."
+                ));
+            }
+        }
+
+        let mut instantiations: Vec<_> = self
+            .export
+            .instantiate
+            .iter()
+            .flat_map(|(name, arguments)| {
+                arguments
+                    .iter()
+                    .map(move |arguments| ("export.instantiate", format!("{name}<{arguments}>")))
+            })
+            .collect();
+        let mut monomorph_names: Vec<_> = self.export.monomorph_names.iter().collect();
+        monomorph_names.sort();
+        for (ty, name) in monomorph_names {
+            instantiations.push(("export.monomorph_names", ty.clone()));
+            if !is_c_identifier(name) {
+                problems.push(format!(
+                    "`export.monomorph_names` names `{ty}` `{name}`, which isn't a valid C \
+                     identifier."
+                ));
+            }
+        }
+        for (option, ty) in instantiations {
+            if syn::parse_str::<syn::Type>(&ty).is_err() {
+                problems.push(format!("`{option}` has an invalid type `{ty}`."));
+            }
+        }
+
+        // The references to a type don't say which module it comes from, so
+        // types can only be mapped by name.
+        let mut qualified: Vec<&String> = self
+            .export
+            .type_map
+            .keys()
+            .filter(|name| name.contains("::"))
+            .collect();
+        qualified.sort();
+        for path in qualified {
+            let name = path.rsplit("::").next().unwrap_or(path).trim();
+            problems.push(format!(
+                "`export.type_map` maps `{path}`, but types can only be mapped by name, \
+                 like `{name}`."
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(warnings)
+        } else {
+            Err(Error::InvalidConfig(problems))
+        }
     }
 
+    /// Loads the config from the `[package.metadata.cbindgen]` table of the
+    /// `Cargo.toml` in `crate_dir`, on top of the `[workspace.metadata.cbindgen]`
+    /// table of its workspace. Returns `None` if neither table exists. Manifests
+    /// that can't be loaded are skipped with a warning, as loading the crate
+    /// reports them.
+    pub fn from_cargo_metadata<P: AsRef<StdPath>>(crate_dir: P) -> Result<Option<Config>, String> {
+        let manifest_path = crate_dir.as_ref().join("Cargo.toml");
+        let table = match load_cargo_metadata(crate_dir.as_ref(), &mut vec![])? {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+        let mut config = toml::Value::Table(table)
+            .try_into::<Config>()
+            .map_err(|e| {
+                format!(
+                    "Couldn't parse the cbindgen metadata of {}: {e}.",
+                    manifest_path.display()
+                )
+            })?;
+        config.config_path = Some(manifest_path);
+        Ok(Some(config))
+    }
+
+    /// Loads `cbindgen.toml` in `root` if it exists, or else the config in the
+    /// package metadata of `Cargo.toml` (see `from_cargo_metadata`), or else the
+    /// default config if there is no manifest, or if it is a virtual manifest.
     pub fn from_root_or_default<P: AsRef<StdPath>>(root: P) -> Config {
         let c = root.as_ref().join("cbindgen.toml");
 
         if c.exists() {
             Config::from_file(c).unwrap()
         } else {
-            Config::default()
+            Config::from_cargo_metadata(root)
+                .unwrap()
+                .unwrap_or_default()
+        }
+    }
+}
+
+/// Loads the TOML of the `[package.metadata.cbindgen]` table of the
+/// `Cargo.toml` in `crate_dir`, merged over the `[workspace.metadata.cbindgen]`
+/// table of its workspace. `extended` collects the files pulled in by `extends`
+/// and the workspace manifest. See `Config::from_cargo_metadata`.
+fn load_cargo_metadata(
+    crate_dir: &StdPath,
+    extended: &mut Vec<StdPathBuf>,
+) -> Result<Option<toml::Table>, String> {
+    let manifest_path = crate_dir.join("Cargo.toml");
+    if !manifest_path.exists() {
+        return Ok(None);
+    }
+    // Problems with the manifest itself are left for loading the crate to
+    // report, the config doesn't depend on it.
+    let manifest = match cargo_toml::workspace_manifest(&manifest_path) {
+        Ok(manifest) => manifest,
+        Err(e) => {
+            warn!(
+                "Couldn't load manifest file {}: {e}. Ignoring its cbindgen metadata.",
+                manifest_path.display()
+            );
+            return Ok(None);
+        }
+    };
+    // Virtual manifests don't describe the crate the bindings are for.
+    let package = match manifest.package {
+        Some(package) => package,
+        None => return Ok(None),
+    };
+
+    let workspace = match manifest.workspace {
+        Some(workspace) => Some((manifest_path.clone(), workspace)),
+        None => find_workspace(crate_dir),
+    };
+    let tables = [
+        match workspace {
+            Some((path, workspace)) => {
+                let key = "workspace.metadata.cbindgen";
+                metadata_table(workspace.metadata, &path, key)?.map(|table| (path, key, table))
+            }
+            None => None,
+        },
+        metadata_table(
+            package.metadata,
+            &manifest_path,
+            "package.metadata.cbindgen",
+        )?
+        .map(|table| (manifest_path.clone(), "package.metadata.cbindgen", table)),
+    ];
+    if tables.iter().all(Option::is_none) {
+        return Ok(None);
+    }
+
+    let mut merged = toml::Table::new();
+    for (path, key, table) in tables.into_iter().flatten() {
+        let config = toml::Value::Table(table.clone())
+            .try_into::<Config>()
+            .map_err(|e| format!("Couldn't parse `[{key}]` in {}: {e}.", path.display()))?;
+        let table = resolve_extends(table, config.extends, &path, &mut vec![], extended)?;
+        merge_toml(&mut merged, table);
+        if path != manifest_path {
+            extended.push(path);
+        }
+    }
+
+    Ok(Some(merged))
+}
+
+/// Loads the TOML of the config file at `path`, with the files it `extends`
+/// merged in. `chain` holds the files currently being loaded to detect cycles,
+/// and `extended` collects every file that was pulled in by `extends`.
+fn load_toml(
+    path: &StdPath,
+    chain: &mut Vec<StdPathBuf>,
+    extended: &mut Vec<StdPathBuf>,
+) -> Result<toml::Table, String> {
+    let config_text = fs::read_to_string(path)
+        .map_err(|_| format!("Couldn't open config file: {}.", path.display()))?;
+
+    // Deserialize the file on its own first, for errors that point at the
+    // right line of the right file.
+    let parse_error = |e| format!("Couldn't parse config file {}: {e}.", path.display());
+    let config = toml::from_str::<Config>(&config_text).map_err(parse_error)?;
+    let table = toml::from_str::<toml::Table>(&config_text).map_err(parse_error)?;
+    resolve_extends(table, config.extends, path, chain, extended)
+}
+
+/// Merges the files listed in `extends` under `table`, the config loaded from
+/// `path`. See `load_toml`.
+fn resolve_extends(
+    mut table: toml::Table,
+    extends: Vec<String>,
+    path: &StdPath,
+    chain: &mut Vec<StdPathBuf>,
+    extended: &mut Vec<StdPathBuf>,
+) -> Result<toml::Table, String> {
+    if extends.is_empty() {
+        return Ok(table);
+    }
+
+    let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_owned());
+    if chain.contains(&canonical_path) {
+        return Err(format!(
+            "Config file {} extends itself.",
+            canonical_path.display()
+        ));
+    }
+    chain.push(canonical_path);
+
+    let dir = path.parent().unwrap_or_else(|| StdPath::new(""));
+    let mut merged = toml::Table::new();
+    for base in &extends {
+        let base = dir.join(base);
+        let mut base_table = load_toml(&base, chain, extended)?;
+        base_table.remove("extends");
+        merge_toml(&mut merged, base_table);
+        extended.push(base);
+    }
+    chain.pop();
+
+    table.remove("extends");
+    merge_toml(&mut merged, table);
+    merged.insert(
+        "extends".to_owned(),
+        toml::Value::Array(extends.into_iter().map(toml::Value::String).collect()),
+    );
+    Ok(merged)
+}
+
+/// The `cbindgen` table of the `metadata` of a package or workspace, named
+/// `key` in the manifest at `path`.
+fn metadata_table(
+    metadata: Option<toml::Value>,
+    path: &StdPath,
+    key: &str,
+) -> Result<Option<toml::Table>, String> {
+    let cbindgen = match metadata {
+        Some(toml::Value::Table(mut metadata)) => metadata.remove("cbindgen"),
+        _ => None,
+    };
+    match cbindgen {
+        Some(toml::Value::Table(table)) => Ok(Some(table)),
+        Some(_) => Err(format!("`{key}` in {} isn't a table.", path.display())),
+        None => Ok(None),
+    }
+}
+
+/// Finds the manifest of the workspace the crate in `crate_dir` is part of, by
+/// looking for a `[workspace]` in the manifests of the parent directories.
+fn find_workspace(crate_dir: &StdPath) -> Option<(StdPathBuf, cargo_toml::Workspace)> {
+    for dir in crate_dir.ancestors().skip(1) {
+        let manifest_path = dir.join("Cargo.toml");
+        if !manifest_path.exists() {
+            continue;
+        }
+        let manifest = match cargo_toml::workspace_manifest(&manifest_path) {
+            Ok(manifest) => manifest,
+            Err(e) => {
+                warn!(
+                    "Couldn't load manifest file {}: {e}. Ignoring its cbindgen metadata.",
+                    manifest_path.display()
+                );
+                continue;
+            }
+        };
+        if let Some(workspace) = manifest.workspace {
+            let excluded = workspace
+                .exclude
+                .iter()
+                .any(|excluded| crate_dir.starts_with(dir.join(excluded)));
+            return (!excluded).then_some((manifest_path, workspace));
+        }
+    }
+    None
+}
+
+/// Parses a value given on the command line as a TOML value, or as a string if
+/// it isn't one, so that `MY_` doesn't need to be quoted.
+fn parse_toml_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_owned()))
+}
+
+/// Sets the value at the dotted `key` path of `table`, creating the tables on
+/// the way if needed.
+fn set_toml(table: &mut toml::Table, key: &[String], value: toml::Value) -> Result<(), String> {
+    let mut segments = key.iter().peekable();
+    let mut table = table;
+    while let Some(segment) = segments.next() {
+        if segment.is_empty() {
+            return Err(format!("`{}` isn't a valid key", key.join(".")));
+        }
+        if segments.peek().is_none() {
+            table.insert(segment.clone(), value);
+            return Ok(());
+        }
+        let entry = table
+            .entry(segment)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        table = match *entry {
+            toml::Value::Table(ref mut table) => table,
+            _ => return Err(format!("`{segment}` in `{}` isn't a table", key.join("."))),
+        };
+    }
+    Err(format!("`{}` isn't a valid key", key.join(".")))
+}
+
+/// Splits `key.path=value` into the parts of the key and the value. Parts of
+/// the key can be quoted like in TOML to contain `.` or `=`, for example
+/// `defines."feature = serde"=SERDE`.
+fn split_assignment(assignment: &str) -> Option<(Vec<String>, &str)> {
+    let mut key = vec![];
+    let mut segment = String::new();
+    let mut quote = None;
+    for (i, c) in assignment.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), c) => segment.push(c),
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '.') => key.push(std::mem::take(&mut segment).trim().to_owned()),
+            (None, '=') => {
+                key.push(segment.trim().to_owned());
+                return Some((key, &assignment[i + 1..]));
+            }
+            (None, c) => segment.push(c),
+        }
+    }
+    None
+}
+
+/// Merges `overlay` into `base`: tables are merged key by key, any other value
+/// replaces the one in `base`.
+fn merge_toml(base: &mut toml::Table, overlay: toml::Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base)), toml::Value::Table(overlay)) => {
+                merge_toml(base, overlay)
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// Whether `name` can be used as an identifier in C.
+fn is_c_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn config_without_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(&manifest, "[workspace]\nmembers = [\"a\"]\n").unwrap();
+        assert!(Config::from_cargo_metadata(dir.path()).unwrap().is_none());
+        let config = Config::from_root_or_default(dir.path());
+        assert_eq!(config.language, Language::Cxx);
+        assert!(config.config_path.is_none());
+
+        // A manifest that can't be loaded doesn't prevent loading the config.
+        std::fs::write(&manifest, "[package\n").unwrap();
+        assert!(Config::from_cargo_metadata(dir.path()).unwrap().is_none());
+        let crate_dir = dir.path().join("a");
+        std::fs::create_dir(&crate_dir).unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"a\"\n[package.metadata.cbindgen]\nlanguage = \"C\"\n",
+        )
+        .unwrap();
+        let config = Config::from_root_or_default(&crate_dir);
+        assert_eq!(config.language, Language::C);
+        std::fs::write(crate_dir.join("Cargo.toml"), "[package\n").unwrap();
+        let config = Config::from_root_or_default(&crate_dir);
+        assert_eq!(config.language, Language::Cxx);
+    }
+
+    #[test]
+    #[should_panic(expected = "include_gaurd")]
+    fn invalid_package_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"a\"\n\
+             [package.metadata.cbindgen]\nlanguage = \"C\"\ninclude_gaurd = \"A_H\"\n",
+        )
+        .unwrap();
+        Config::from_root_or_default(dir.path());
+    }
+
+    #[test]
+    fn package_metadata_not_a_table() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = dir.path().join("Cargo.toml");
+        std::fs::write(
+            &manifest,
+            "[package]\nname = \"a\"\n[package.metadata]\ncbindgen = 1\n",
+        )
+        .unwrap();
+        // Loading the crate doesn't depend on the config.
+        assert_eq!(cargo_toml::manifest(&manifest).unwrap().package.name, "a");
+        assert!(Config::from_cargo_metadata(dir.path()).is_err());
+    }
+
+    #[test]
+    fn config_overrides() {
+        let mut config = Config::default();
+        config
+            .apply_overrides([
+                "export.prefix=MY_",
+                "enum.prefix_with_name=true",
+                "includes=[\"a.h\", \"b.h\"]",
+                "defines.'feature = serde'=DEFINE_SERDE",
+            ])
+            .unwrap();
+        assert_eq!(config.export.prefix.as_deref(), Some("MY_"));
+        assert!(config.enumeration.prefix_with_name);
+        assert_eq!(config.includes, ["a.h", "b.h"]);
+        assert_eq!(config.defines["feature = serde"], "DEFINE_SERDE");
+
+        assert!(config.apply_overrides(["export.bogus=1"]).is_err());
+        assert!(config.apply_overrides(["export.prefix.x=1"]).is_err());
+        assert!(config.apply_overrides(["export"]).is_err());
+    }
+
+    #[test]
+    fn config_overrides_keep_settings() {
+        let mut config = Config {
+            language: Language::C,
+            ..Default::default()
+        };
+        config.includes.push("a.h".to_owned());
+        config.apply_overrides(["export.prefix=MY_"]).unwrap();
+        assert_eq!(config.language, Language::C);
+        assert_eq!(config.includes, ["a.h"]);
+        assert_eq!(config.export.prefix.as_deref(), Some("MY_"));
+    }
+
+    /// Every option, set to a value other than its default and written the way
+    /// `Config` serializes it.
+    const FULL_CONFIG: &str = r##"
+extends = ["base.toml"]
+header = "/* header */"
+includes = ["a.h"]
+sys_includes = ["b.h"]
+after_includes = "#define A"
+trailer = "/* trailer */"
+include_guard = "A_H"
+pragma_once = true
+autogen_warning = "/* generated */"
+no_includes = true
+package_version = true
+include_version = true
+namespace = "ffi"
+namespaces = ["a", "b"]
+using_namespaces = ["std"]
+braces = "NextLine"
+line_length = 80
+tab_width = 4
+line_endings = "CRLF"
+language = "C"
+cpp_compat = true
+c_standard = "c89"
+cpp_standard = "c++20"
+style = "tag"
+sort_by = "Name"
+usize_is_size_t = true
+documentation = false
+documentation_style = "doxy"
+documentation_length = "short"
+only_target_dependencies = true
+plugins = ["plugin"]
+
+[[output]]
+path = "a.hpp"
+language = "C++"
+style = "type"
+include_guard = "A_HPP"
+namespace = "a"
+namespaces = ["a", "b"]
+
+[overlay.cxx]
+language = "C++"
+
+[parse]
+parse_deps = true
+include = ["dep"]
+exclude = ["other"]
+clean = true
+extra_bindings = ["dep"]
+strict = true
+
+[parse.expand]
+crates = ["a"]
+all_features = true
+default_features = false
+features = ["f"]
+profile = "release"
+
+[export]
+include = ["A"]
+exclude = ["B"]
+opaque = ["C"]
+item_types = ["structs", "functions"]
+prefix = "MY_"
+renaming_overrides_prefixing = true
+
+[export.rename]
+A = "B"
+
+[export.pre_body]
+A = "int pre;"
+
+[export.body]
+A = "int post;"
+
+[export.mangle]
+rename_types = "SnakeCase"
+remove_underscores = true
+
+[export.type_map]
+Fd = "int"
+
+[export.type_map.Handle]
+name = "handle_t"
+c = "c_handle_t"
+cpp = "cpp::Handle"
+cython = "handle"
+includes = ["handle.h"]
+sys_includes = ["sys/handle.h"]
+
+[export.instantiate]
+Foo = ["u32"]
+
+[export.monomorph_names]
+"Foo<u32>" = "FooU32"
+
+[macro_expansion]
+bitflags = true
+
+[layout]
+packed = "PACKED"
+aligned_n = "ALIGNED"
+
+[fn]
+prefix = "PRE"
+postfix = "POST"
+must_use = "MUST_USE"
+deprecated = "DEPRECATED"
+deprecated_with_note = "DEPRECATED_WITH_NOTE"
+no_return = "NO_RETURN"
+args = "Vertical"
+rename_args = "SnakeCase"
+swift_name_macro = "SWIFT"
+sort_by = "None"
+
+[struct]
+rename_fields = "CamelCase"
+must_use = "MUST_USE"
+deprecated = "DEPRECATED"
+deprecated_with_note = "DEPRECATED_WITH_NOTE"
+derive_constructor = true
+derive_eq = true
+derive_neq = true
+derive_lt = true
+derive_lte = true
+derive_gt = true
+derive_gte = true
+derive_c_comparisons = true
+derive_ostream = true
+derive_hash = true
+derive_formatter = "fmt"
+hash_pointers = true
+associated_constants_in_body = true
+rename_associated_constant = "UpperCase"
+
+[enum]
+rename_variants = "ScreamingSnakeCase"
+rename_variant_name_fields = "None"
+add_sentinel = true
+prefix_with_name = true
+derive_helper_methods = true
+derive_const_casts = true
+derive_mut_casts = true
+derive_c_helpers = true
+derive_visit = true
+derive_hash = true
+derive_formatter = "std"
+cast_assert_name = "ASSERT"
+must_use = "MUST_USE"
+deprecated = "DEPRECATED"
+deprecated_with_note = "DEPRECATED_WITH_NOTE"
+deprecated_variant = "DEPRECATED_VARIANT"
+deprecated_variant_with_note = "DEPRECATED_VARIANT_WITH_NOTE"
+derive_tagged_enum_destructor = true
+derive_tagged_enum_copy_constructor = true
+derive_tagged_enum_copy_assignment = true
+derive_ostream = true
+enum_class = false
+private_default_tagged_enum_constructor = true
+non_exhaustive_as_constants = true
+add_unknown_sentinel = true
+derive_to_string = true
+derive_from_string = true
+to_string_rust_names = true
+
+[const]
+allow_static_const = false
+allow_constexpr = false
+sort_by = "Name"
+
+[defines]
+"feature = serde" = "DEFINE_SERDE"
+
+[ptr]
+non_null_attribute = "NON_NULL"
+nullable_attribute = "NULLABLE"
+
+[cython]
+header = '"a.h"'
+
+[cython.cimports]
+"libc.stdint" = ["uint8_t"]
+"##;
+
+    #[test]
+    fn config_round_trip() {
+        // `merge` writes the config as TOML and reads it back, so that has to
+        // give the same config, whatever the options are set to.
+        let table = toml::from_str::<toml::Table>(FULL_CONFIG).unwrap();
+        let mut config = toml::Value::Table(table.clone())
+            .try_into::<Config>()
+            .unwrap();
+        assert_eq!(toml::Table::try_from(&config).unwrap(), table);
+        config.merge(toml::Table::new()).unwrap();
+        assert_eq!(toml::Table::try_from(&config).unwrap(), table);
+    }
+
+    #[test]
+    fn extended_config_paths() {
+        let dir = tempfile::tempdir().unwrap();
+        let base = dir.path().join("base.toml");
+        std::fs::write(&base, "language = \"C\"\n").unwrap();
+        let path = dir.path().join("cbindgen.toml");
+        std::fs::write(&path, "extends = [\"base.toml\"]\n").unwrap();
+        let config = Config::from_file(&path).unwrap();
+        assert_eq!(config.language, Language::C);
+        assert_eq!(config.extended_config_paths(), std::slice::from_ref(&base));
+
+        let crate_dir = dir.path().join("a");
+        std::fs::create_dir(&crate_dir).unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"a\"]\n\
+             [workspace.metadata.cbindgen]\nextends = [\"base.toml\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"a\"\n[package.metadata.cbindgen]\nnamespace = \"a\"\n",
+        )
+        .unwrap();
+        let config = Config::from_root_or_default(&crate_dir);
+        assert_eq!(config.language, Language::C);
+        assert_eq!(config.namespace.as_deref(), Some("a"));
+        assert_eq!(
+            config.extended_config_paths(),
+            [base, dir.path().join("Cargo.toml")]
+        );
+    }
+
+    #[test]
+    fn validate() {
+        assert!(Config::default().validate().unwrap().is_empty());
+
+        let mut config = Config {
+            language: Language::C,
+            namespace: Some("ffi".to_owned()),
+            ..Default::default()
+        };
+        config.cython.header = Some("\"header.h\"".to_owned());
+        config.enumeration.derive_tagged_enum_copy_assignment = true;
+        config.export.renaming_overrides_prefixing = true;
+        let warnings = config.validate().unwrap();
+        assert_eq!(warnings.len(), 4, "{warnings:#?}");
+        assert!(warnings
+            .iter()
+            .all(|w| w.code == DiagnosticCode::IgnoredConfigOption));
+
+        for key in [
+            "unix",
+            "feature = serde",
+            "any(unix, windows)",
+            "target_os = \"linux\"",
+        ] {
+            config.defines.insert(key.to_owned(), "X".to_owned());
         }
+        let problems = match config.validate() {
+            Err(Error::InvalidConfig(problems)) => problems,
+            result => panic!("unexpected {result:?}"),
+        };
+        assert_eq!(problems.len(), 2, "{problems:#?}");
+
+        // C++ can rename items to qualified names, but C can't.
+        let mut config = Config::default();
+        config
+            .export
+            .rename
+            .insert("Foo".to_owned(), "Bar".to_owned());
+        config
+            .export
+            .rename
+            .insert("Baz".to_owned(), "my::Baz".to_owned());
+        assert!(config.validate().unwrap().is_empty());
+        config.language = Language::C;
+        assert!(matches!(config.validate(), Err(Error::InvalidConfig(p)) if p.len() == 1));
+
+        let mut config = Config::default();
+        config
+            .apply_overrides(["export.type_map.Fd=int", "export.type_map.'libc::Fd'=int"])
+            .unwrap();
+        assert!(matches!(config.validate(), Err(Error::InvalidConfig(p)) if p.len() == 1));
+
+        let mut config = Config::default();
+        config
+            .export
+            .monomorph_names
+            .insert("Foo<u32>".to_owned(), "Foo<u32>".to_owned());
+        config
+            .export
+            .instantiate
+            .insert("Bar".to_owned(), vec!["u32 u8".to_owned()]);
+        assert!(matches!(config.validate(), Err(Error::InvalidConfig(p)) if p.len() == 2));
     }
 }
@@ -5,13 +5,54 @@
 use std::cmp::Ordering;
 use std::collections::HashSet;
 
+use indexmap::IndexSet;
+
 use crate::bindgen::ir::{ItemContainer, Path};
 
+/// Something that can pull an item into the bindings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum DependencyNode {
+    Function(Path),
+    Global(Path),
+    Constant(Path),
+    /// The `export.include` list of the config.
+    ExportInclude,
+    /// The instantiations requested by `export.instantiate` or
+    /// `cbindgen:instantiate`.
+    ExportInstantiate,
+    Item(Path),
+}
+
+impl DependencyNode {
+    pub fn name(&self) -> &str {
+        match *self {
+            DependencyNode::Function(ref path)
+            | DependencyNode::Global(ref path)
+            | DependencyNode::Constant(ref path)
+            | DependencyNode::Item(ref path) => path.name(),
+            DependencyNode::ExportInclude => "export.include",
+            DependencyNode::ExportInstantiate => "export.instantiate",
+        }
+    }
+
+    /// Whether this is where a chain of dependencies starts, as opposed to
+    /// an item that was itself pulled in.
+    pub fn is_root(&self) -> bool {
+        !matches!(*self, DependencyNode::Item(..))
+    }
+}
+
 /// A dependency list is used for gathering what order to output the types.
 #[derive(Default)]
 pub struct Dependencies {
     pub order: Vec<ItemContainer>,
     pub items: HashSet<Path>,
+    /// The types of `export.type_map` used by the items, by Rust name.
+    pub mapped_types: HashSet<Path>,
+    /// Every `(dependent, dependency)` pair found, in the order they were found.
+    pub edges: IndexSet<(DependencyNode, Path)>,
+    /// The chain of nodes whose dependencies are being collected.
+    stack: Vec<DependencyNode>,
 }
 
 impl Dependencies {
@@ -19,6 +60,25 @@ impl Dependencies {
         Dependencies {
             order: Vec::new(),
             items: HashSet::new(),
+            mapped_types: HashSet::new(),
+            edges: IndexSet::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Attributes the dependencies found until the matching `exit` to `node`.
+    pub fn enter(&mut self, node: DependencyNode) {
+        self.stack.push(node);
+    }
+
+    pub fn exit(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Records that the node being processed depends on `path`.
+    pub fn add_edge(&mut self, path: &Path) {
+        if let Some(node) = self.stack.last() {
+            self.edges.insert((node.clone(), path.clone()));
         }
     }
 
@@ -0,0 +1,206 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::fmt;
+use std::path::PathBuf;
+
+/// The `log` target diagnostics are logged with, so that loggers can tell them
+/// apart from other messages (for example to report them in a structured way
+/// from `Bindings::diagnostics` instead).
+pub const DIAGNOSTIC_LOG_TARGET: &str = "cbindgen::diagnostic";
+
+/// How severe a diagnostic is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticLevel {
+    Warning,
+    Error,
+}
+
+impl DiagnosticLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticLevel::Warning => "warning",
+            DiagnosticLevel::Error => "error",
+        }
+    }
+}
+
+/// Why a diagnostic was emitted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticCode {
+    /// The item is not `pub`.
+    NotPublic,
+    /// The function is not `extern "C"`.
+    NotExternC,
+    /// The item is neither `no_mangle` nor has an `export_name`.
+    NotNoMangle,
+    /// The item uses a type or expression that can't be represented.
+    Unsupported,
+    /// An item with the same name was already loaded.
+    ConflictingName,
+    /// A `bitflags!` invocation couldn't be parsed.
+    BitflagsParse,
+    /// The source of a module couldn't be found.
+    MissingModule,
+    /// The source of a crate couldn't be found.
+    MissingCrateSource,
+    /// A `cbindgen:` annotation isn't known.
+    UnknownAnnotation,
+    /// A `cbindgen:` annotation has no effect on the item it's attached to.
+    MisplacedAnnotation,
+    /// A `cbindgen:` annotation has an invalid value.
+    InvalidAnnotation,
+    /// A config option has no effect with the rest of the config.
+    IgnoredConfigOption,
+    /// A type of `export.instantiate` or of a `cbindgen:instantiate`
+    /// annotation can't be instantiated.
+    InvalidInstantiation,
+    /// A trait or helper requested for an item, like `std::hash`, can't be
+    /// written for it.
+    CannotDerive,
+    /// The item can't be written for the configured `c_standard`.
+    UnsupportedByStandard,
+}
+
+impl DiagnosticCode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DiagnosticCode::NotPublic => "not-pub",
+            DiagnosticCode::NotExternC => "not-extern-c",
+            DiagnosticCode::NotNoMangle => "not-no-mangle",
+            DiagnosticCode::Unsupported => "unsupported",
+            DiagnosticCode::ConflictingName => "conflicting-name",
+            DiagnosticCode::BitflagsParse => "bitflags-parse",
+            DiagnosticCode::MissingModule => "missing-module",
+            DiagnosticCode::MissingCrateSource => "missing-crate-source",
+            DiagnosticCode::UnknownAnnotation => "unknown-annotation",
+            DiagnosticCode::MisplacedAnnotation => "misplaced-annotation",
+            DiagnosticCode::InvalidAnnotation => "invalid-annotation",
+            DiagnosticCode::IgnoredConfigOption => "ignored-config-option",
+            DiagnosticCode::InvalidInstantiation => "invalid-instantiation",
+            DiagnosticCode::CannotDerive => "cannot-derive",
+            DiagnosticCode::UnsupportedByStandard => "unsupported-by-standard",
+        }
+    }
+
+    /// Whether a diagnostic with this code fails the run even outside of
+    /// strict mode.
+    pub fn is_fatal(self) -> bool {
+        matches!(
+            self,
+            DiagnosticCode::InvalidAnnotation | DiagnosticCode::UnsupportedByStandard
+        )
+    }
+}
+
+/// A range in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file: PathBuf,
+    /// 1-based line number of the start of the range.
+    pub line: usize,
+    /// 1-based column number of the start of the range.
+    pub column: usize,
+    /// 1-based line number of the end of the range.
+    pub end_line: usize,
+    /// 1-based column number one past the end of the range.
+    pub end_column: usize,
+}
+
+impl SourceLocation {
+    pub(crate) fn from_span(file: PathBuf, span: proc_macro2::Span) -> SourceLocation {
+        let start = span.start();
+        let end = span.end();
+        SourceLocation {
+            file,
+            line: start.line,
+            column: start.column + 1,
+            end_line: end.line,
+            end_column: end.column + 1,
+        }
+    }
+}
+
+/// A problem found while generating bindings, such as an item that was
+/// skipped because it can't be represented.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: DiagnosticLevel,
+    pub code: DiagnosticCode,
+    pub message: String,
+    /// The path of the Rust item the diagnostic is about, if any.
+    pub item: Option<String>,
+    pub location: Option<SourceLocation>,
+    /// A suggestion on how to fix the problem, if any.
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    pub(crate) fn new(level: DiagnosticLevel, code: DiagnosticCode, message: String) -> Self {
+        Diagnostic {
+            level,
+            code,
+            message,
+            item: None,
+            location: None,
+            help: None,
+        }
+    }
+
+    pub(crate) fn warning(code: DiagnosticCode, message: String) -> Self {
+        Self::new(DiagnosticLevel::Warning, code, message)
+    }
+
+    pub(crate) fn error(code: DiagnosticCode, message: String) -> Self {
+        Self::new(DiagnosticLevel::Error, code, message)
+    }
+
+    pub(crate) fn with_item(mut self, item: impl Into<String>) -> Self {
+        self.item = Some(item.into());
+        self
+    }
+
+    pub(crate) fn with_location(mut self, location: Option<SourceLocation>) -> Self {
+        self.location = location;
+        self
+    }
+
+    pub(crate) fn with_help(mut self, help: impl Into<String>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Logs the diagnostic through the `log` crate.
+    pub(crate) fn log(&self) {
+        match self.level {
+            DiagnosticLevel::Warning => {
+                warn!(target: DIAGNOSTIC_LOG_TARGET, "{}", self.message)
+            }
+            DiagnosticLevel::Error => {
+                error!(target: DIAGNOSTIC_LOG_TARGET, "{}", self.message)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(ref location) = self.location {
+            write!(
+                f,
+                "{}:{}:{}: ",
+                location.file.display(),
+                location.line,
+                location.column
+            )?;
+        }
+        write!(
+            f,
+            "{}[{}]: {}",
+            self.level.as_str(),
+            self.code.as_str(),
+            self.message
+        )
+    }
+}
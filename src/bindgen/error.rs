@@ -8,6 +8,7 @@ use std::fmt;
 pub use crate::bindgen::cargo::cargo_expand::Error as CargoExpandError;
 pub use crate::bindgen::cargo::cargo_metadata::Error as CargoMetadataError;
 pub use crate::bindgen::cargo::cargo_toml::Error as CargoTomlError;
+use crate::bindgen::diagnostic::Diagnostic;
 pub use syn::parse::Error as ParseError;
 
 #[derive(Debug)]
@@ -25,6 +26,22 @@ pub enum Error {
         crate_name: String,
         src_path: String,
     },
+    /// A pattern in the config couldn't be parsed.
+    InvalidPattern {
+        option: String,
+        pattern: String,
+        reason: String,
+    },
+    /// The config is invalid, for the contained reasons.
+    InvalidConfig(Vec<String>),
+    /// A plugin couldn't be run or its response couldn't be applied.
+    Plugin {
+        plugin: String,
+        reason: String,
+    },
+    /// Generation failed because of the contained diagnostics, either because
+    /// some of them are fatal or because `parse.strict` is set.
+    Diagnostics(Vec<Diagnostic>),
 }
 
 impl fmt::Display for Error {
@@ -66,6 +83,29 @@ impl fmt::Display for Error {
                 f,
                 "Parsing crate `{crate_name}`: cannot open file `{src_path}`."
             ),
+            Error::InvalidPattern {
+                ref option,
+                ref pattern,
+                ref reason,
+            } => write!(f, "Invalid pattern `{pattern}` in `{option}`: {reason}"),
+            Error::InvalidConfig(ref problems) => {
+                write!(f, "Invalid config:")?;
+                for problem in problems {
+                    write!(f, "\n  {problem}")?;
+                }
+                Ok(())
+            }
+            Error::Plugin {
+                ref plugin,
+                ref reason,
+            } => write!(f, "Plugin `{plugin}` failed: {reason}"),
+            Error::Diagnostics(ref diagnostics) => {
+                write!(f, "Couldn't generate bindings:")?;
+                for diagnostic in diagnostics {
+                    write!(f, "\n  {diagnostic}")?;
+                }
+                Ok(())
+            }
         }
     }
 }
@@ -78,6 +118,10 @@ impl error::Error for Error {
             Error::CargoExpand(_, ref error) => Some(error),
             Error::ParseSyntaxError { ref error, .. } => Some(error),
             Error::ParseCannotOpenFile { .. } => None,
+            Error::InvalidPattern { .. } => None,
+            Error::InvalidConfig(..) => None,
+            Error::Plugin { .. } => None,
+            Error::Diagnostics(..) => None,
         }
     }
 }
@@ -8,6 +8,8 @@ use std::collections::HashMap;
 use std::str::FromStr;
 
 use crate::bindgen::config::{Config, Language};
+use crate::bindgen::diagnostic::{Diagnostic, DiagnosticCode};
+use crate::bindgen::rename::RenameRule;
 use crate::bindgen::utilities::SynAttributeHelpers;
 
 // A system for specifying properties on items. Annotations are
@@ -36,6 +38,7 @@ pub enum AnnotationValue {
 pub struct AnnotationSet {
     annotations: HashMap<String, AnnotationValue>,
     pub must_use: bool,
+    pub non_exhaustive: bool,
     pub deprecated: Option<String>,
 }
 
@@ -51,6 +54,7 @@ impl AnnotationSet {
         AnnotationSet {
             annotations: HashMap::new(),
             must_use: false,
+            non_exhaustive: false,
             deprecated: None,
         }
     }
@@ -79,14 +83,16 @@ impl AnnotationSet {
         }
 
         if note.is_empty() {
-            return Some(Cow::Borrowed(match kind {
-                DeprecatedNoteKind::Enum => config.enumeration.deprecated.as_deref()?,
-                DeprecatedNoteKind::EnumVariant => {
-                    config.enumeration.deprecated_variant.as_deref()?
+            return Some(Cow::Borrowed(
+                match kind {
+                    DeprecatedNoteKind::Enum => &config.enumeration.deprecated,
+                    DeprecatedNoteKind::EnumVariant => &config.enumeration.deprecated_variant,
+                    DeprecatedNoteKind::Function => &config.function.deprecated,
+                    DeprecatedNoteKind::Struct => &config.structure.deprecated,
                 }
-                DeprecatedNoteKind::Function => config.function.deprecated.as_deref()?,
-                DeprecatedNoteKind::Struct => config.structure.deprecated.as_deref()?,
-            }));
+                .as_deref()
+                .or_else(|| config.c23_attribute("[[deprecated]]"))?,
+            ));
         }
 
         let format = match kind {
@@ -95,7 +101,8 @@ impl AnnotationSet {
             DeprecatedNoteKind::Function => &config.function.deprecated_with_note,
             DeprecatedNoteKind::Struct => &config.structure.deprecated_with_note,
         }
-        .as_ref()?;
+        .as_deref()
+        .or_else(|| config.c23_attribute("[[deprecated({})]]"))?;
         Some(Cow::Owned(format.replace("{}", &format!("{note:?}"))))
     }
 
@@ -114,6 +121,7 @@ impl AnnotationSet {
             .collect();
 
         let must_use = attrs.has_attr_word("must_use");
+        let non_exhaustive = attrs.has_attr_word("non_exhaustive");
         let deprecated = attrs.find_deprecated_note();
         let mut annotations = HashMap::new();
 
@@ -124,50 +132,30 @@ impl AnnotationSet {
             // Remove the "cbindgen:" prefix
             let annotation = &line[9..];
 
-            // Split the annotation in two
-            let parts: Vec<&str> = annotation.split('=').map(|x| x.trim()).collect();
-
-            if parts.len() > 2 {
-                return Err(format!("Couldn't parse {line}."));
-            }
-
-            // Grab the name that this annotation is modifying
-            let name = parts[0];
-
-            // If the annotation only has a name, assume it's setting a bool flag
-            if parts.len() == 1 {
-                annotations.insert(name.to_string(), AnnotationValue::Bool(true));
-                continue;
-            }
-
-            // Parse the value we're setting the name to
-            let value = parts[1];
-
-            if let Some(x) = parse_list(value) {
-                annotations.insert(name.to_string(), AnnotationValue::List(x));
-                continue;
-            }
-            if let Ok(x) = value.parse::<bool>() {
-                annotations.insert(name.to_string(), AnnotationValue::Bool(x));
-                continue;
-            }
-            annotations.insert(
-                name.to_string(),
-                if value.is_empty() {
-                    AnnotationValue::Atom(None)
-                } else {
-                    AnnotationValue::Atom(Some(value.to_string()))
-                },
-            );
+            let (name, value) =
+                parse_annotation(annotation).ok_or_else(|| format!("Couldn't parse {line}."))?;
+            annotations.insert(name, value);
         }
 
         Ok(AnnotationSet {
             annotations,
             must_use,
+            non_exhaustive,
             deprecated,
         })
     }
 
+    /// Adds an annotation written like the part of a `cbindgen:` doc comment
+    /// line after the prefix, e.g. `derive-eq` or `prefix=FOO_`, replacing any
+    /// previous value.
+    #[allow(unused)]
+    pub fn add(&mut self, annotation: &str) -> Result<(), String> {
+        let (name, value) =
+            parse_annotation(annotation).ok_or_else(|| format!("Couldn't parse {annotation}."))?;
+        self.annotations.insert(name, value);
+        Ok(())
+    }
+
     /// Adds an annotation value if none is specified.
     pub fn add_default(&mut self, name: &str, value: AnnotationValue) {
         if let Entry::Vacant(e) = self.annotations.entry(name.to_string()) {
@@ -199,13 +187,344 @@ impl AnnotationSet {
         T: Default + FromStr,
     {
         match self.annotations.get(name) {
-            Some(AnnotationValue::Atom(x)) => Some(
-                x.as_ref()
-                    .map_or(T::default(), |y| y.parse::<T>().ok().unwrap()),
-            ),
+            Some(AnnotationValue::Atom(x)) => {
+                x.as_ref().map_or(Some(T::default()), |y| y.parse().ok())
+            }
             _ => None,
         }
     }
+
+    /// Checks the annotations against the set of known annotations for the
+    /// given kind of item, reporting unknown or misplaced annotations as
+    /// warnings and annotations with unexpected values as errors.
+    pub(crate) fn validate(
+        &self,
+        target: AnnotationTarget,
+        item: &str,
+        diagnostics: &mut Vec<Diagnostic>,
+    ) {
+        // Sort the names so that the messages are deterministic.
+        let mut names: Vec<&String> = self.annotations.keys().collect();
+        names.sort();
+
+        for name in names {
+            let known = match KNOWN_ANNOTATIONS.iter().find(|x| x.name == name) {
+                Some(known) => known,
+                None => {
+                    let mut message = format!("{item}: unknown annotation `cbindgen:{name}`.");
+                    let suggestion = suggest_annotation(name);
+                    if let Some(suggestion) = suggestion {
+                        message.push_str(&format!(" Did you mean `cbindgen:{suggestion}`?"));
+                    }
+                    let mut diagnostic =
+                        Diagnostic::warning(DiagnosticCode::UnknownAnnotation, message)
+                            .with_item(item);
+                    if let Some(suggestion) = suggestion {
+                        diagnostic = diagnostic.with_help(format!(
+                            "replace `cbindgen:{name}` with `cbindgen:{suggestion}`"
+                        ));
+                    }
+                    diagnostics.push(diagnostic);
+                    continue;
+                }
+            };
+
+            if !known.applies_to(target) {
+                let targets: Vec<&str> = known.targets.iter().map(|x| x.as_str()).collect();
+                let message = format!(
+                    "{item}: annotation `cbindgen:{name}` has no effect on {} {}, \
+                     it only applies to: {}.",
+                    target.article(),
+                    target.as_str(),
+                    targets.join(", ")
+                );
+                diagnostics.push(
+                    Diagnostic::warning(DiagnosticCode::MisplacedAnnotation, message)
+                        .with_item(item)
+                        .with_help(format!("remove `cbindgen:{name}`")),
+                );
+                continue;
+            }
+
+            if let Err(reason) = known.kind.check(&self.annotations[name]) {
+                let message = format!("{item}: invalid value for `cbindgen:{name}`: {reason}.");
+                diagnostics.push(
+                    Diagnostic::error(DiagnosticCode::InvalidAnnotation, message).with_item(item),
+                );
+            }
+        }
+    }
+}
+
+/// The kind of item an annotation is attached to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnnotationTarget {
+    Struct,
+    Union,
+    Enum,
+    Variant,
+    Field,
+    Function,
+    Static,
+    Constant,
+    Typedef,
+}
+
+impl AnnotationTarget {
+    fn as_str(self) -> &'static str {
+        match self {
+            AnnotationTarget::Struct => "struct",
+            AnnotationTarget::Union => "union",
+            AnnotationTarget::Enum => "enum",
+            AnnotationTarget::Variant => "enum variant",
+            AnnotationTarget::Field => "field",
+            AnnotationTarget::Function => "function",
+            AnnotationTarget::Static => "static",
+            AnnotationTarget::Constant => "constant",
+            AnnotationTarget::Typedef => "type alias",
+        }
+    }
+
+    fn article(self) -> &'static str {
+        match self {
+            AnnotationTarget::Enum | AnnotationTarget::Variant => "an",
+            _ => "a",
+        }
+    }
+}
+
+/// The type of value an annotation expects.
+#[derive(Debug, Clone, Copy)]
+enum AnnotationKind {
+    Bool,
+    Atom,
+    List,
+    RenameRule,
+}
+
+impl AnnotationKind {
+    fn check(self, value: &AnnotationValue) -> Result<(), String> {
+        match (self, value) {
+            (AnnotationKind::Bool, AnnotationValue::Bool(_))
+            | (AnnotationKind::Atom, AnnotationValue::Atom(_))
+            | (AnnotationKind::List, AnnotationValue::List(_))
+            | (AnnotationKind::RenameRule, AnnotationValue::Atom(None)) => Ok(()),
+            (AnnotationKind::RenameRule, AnnotationValue::Atom(Some(rule))) => rule
+                .parse::<RenameRule>()
+                .map(|_| ())
+                .map_err(|_| format!("`{rule}` is not a valid rename rule")),
+            (AnnotationKind::Bool, _) => Err("expected `true` or `false`".to_owned()),
+            (AnnotationKind::Atom, _) => Err("expected a single value".to_owned()),
+            (AnnotationKind::List, _) => Err("expected a list like `[a, b]`".to_owned()),
+            (AnnotationKind::RenameRule, _) => Err("expected a rename rule".to_owned()),
+        }
+    }
+}
+
+struct KnownAnnotation {
+    name: &'static str,
+    kind: AnnotationKind,
+    targets: &'static [AnnotationTarget],
+}
+
+impl KnownAnnotation {
+    fn applies_to(&self, target: AnnotationTarget) -> bool {
+        // Annotations on a type alias are transferred to the aliased item, so
+        // accept anything that is valid for a type definition.
+        self.targets.iter().any(|x| {
+            *x == target
+                || (target == AnnotationTarget::Typedef
+                    && matches!(
+                        x,
+                        AnnotationTarget::Struct | AnnotationTarget::Union | AnnotationTarget::Enum
+                    ))
+        })
+    }
+}
+
+const ALL_ITEMS: &[AnnotationTarget] = &[
+    AnnotationTarget::Struct,
+    AnnotationTarget::Union,
+    AnnotationTarget::Enum,
+    AnnotationTarget::Function,
+    AnnotationTarget::Static,
+    AnnotationTarget::Constant,
+    AnnotationTarget::Typedef,
+];
+const STRUCT: &[AnnotationTarget] = &[AnnotationTarget::Struct];
+const ENUM: &[AnnotationTarget] = &[AnnotationTarget::Enum];
+const VARIANT: &[AnnotationTarget] = &[AnnotationTarget::Variant];
+const FIELD: &[AnnotationTarget] = &[AnnotationTarget::Field];
+const FUNCTION: &[AnnotationTarget] = &[AnnotationTarget::Function];
+const STRUCT_OR_UNION: &[AnnotationTarget] = &[AnnotationTarget::Struct, AnnotationTarget::Union];
+const STRUCT_OR_ENUM: &[AnnotationTarget] = &[AnnotationTarget::Struct, AnnotationTarget::Enum];
+const STRUCT_UNION_OR_ENUM: &[AnnotationTarget] = &[
+    AnnotationTarget::Struct,
+    AnnotationTarget::Union,
+    AnnotationTarget::Enum,
+];
+const GENERIC_TYPE: &[AnnotationTarget] = &[
+    AnnotationTarget::Struct,
+    AnnotationTarget::Union,
+    AnnotationTarget::Enum,
+    AnnotationTarget::Typedef,
+];
+const RENAMEABLE: &[AnnotationTarget] = &[
+    AnnotationTarget::Struct,
+    AnnotationTarget::Union,
+    AnnotationTarget::Enum,
+    AnnotationTarget::Function,
+];
+const OSTREAM: &[AnnotationTarget] = &[
+    AnnotationTarget::Struct,
+    AnnotationTarget::Enum,
+    AnnotationTarget::Variant,
+];
+
+macro_rules! known_annotations {
+    ($($name:expr => $kind:ident, $targets:expr;)*) => {
+        &[$(KnownAnnotation {
+            name: $name,
+            kind: AnnotationKind::$kind,
+            targets: $targets,
+        },)*]
+    };
+}
+
+/// Every annotation understood by cbindgen, along with the value it expects and
+/// the items it can be attached to.
+static KNOWN_ANNOTATIONS: &[KnownAnnotation] = known_annotations! {
+    "ignore" => Bool, ALL_ITEMS;
+    "no-export" => Bool, ALL_ITEMS;
+    "opaque" => Bool, STRUCT_UNION_OR_ENUM;
+    "instantiate" => List, GENERIC_TYPE;
+    "non-exhaustive-as-constants" => Bool, ENUM;
+    "add-unknown-sentinel" => Bool, ENUM;
+    "rename-all" => RenameRule, RENAMEABLE;
+    "field-names" => List, STRUCT_OR_UNION;
+    "rename-associated-constant" => RenameRule, STRUCT;
+    "internal-derive-bitflags" => Bool, STRUCT;
+    "derive-constructor" => Bool, STRUCT;
+    "derive-eq" => Bool, STRUCT_OR_ENUM;
+    "derive-neq" => Bool, STRUCT_OR_ENUM;
+    "derive-lt" => Bool, STRUCT_OR_ENUM;
+    "derive-lte" => Bool, STRUCT_OR_ENUM;
+    "derive-gt" => Bool, STRUCT_OR_ENUM;
+    "derive-gte" => Bool, STRUCT_OR_ENUM;
+    "derive-c-comparisons" => Bool, STRUCT_OR_ENUM;
+    "derive-ostream" => Bool, OSTREAM;
+    "derive-hash" => Bool, STRUCT_OR_ENUM;
+    "derive-formatter" => Atom, STRUCT_OR_ENUM;
+    "eq-attributes" => Atom, STRUCT_OR_ENUM;
+    "neq-attributes" => Atom, STRUCT_OR_ENUM;
+    "lt-attributes" => Atom, STRUCT;
+    "lte-attributes" => Atom, STRUCT;
+    "gt-attributes" => Atom, STRUCT;
+    "gte-attributes" => Atom, STRUCT;
+    "enum-trailing-values" => List, ENUM;
+    "rename-variant-name-fields" => RenameRule, ENUM;
+    "add-sentinel" => Bool, ENUM;
+    "derive-helper-methods" => Bool, ENUM;
+    "derive-const-casts" => Bool, ENUM;
+    "derive-mut-casts" => Bool, ENUM;
+    "derive-c-helpers" => Bool, ENUM;
+    "derive-visit" => Bool, ENUM;
+    "derive-tagged-enum-destructor" => Bool, ENUM;
+    "derive-tagged-enum-copy-constructor" => Bool, ENUM;
+    "derive-tagged-enum-copy-assignment" => Bool, ENUM;
+    "enum-class" => Bool, ENUM;
+    "prefix-with-name" => Bool, ENUM;
+    "private-default-tagged-enum-constructor" => Bool, ENUM;
+    "derive-to-string" => Bool, ENUM;
+    "derive-from-string" => Bool, ENUM;
+    "to-string-rust-names" => Bool, ENUM;
+    "destructor-attributes" => Atom, ENUM;
+    "copy-constructor-attributes" => Atom, ENUM;
+    "copy-assignment-attributes" => Atom, ENUM;
+    "variant-constructor-attributes" => Atom, VARIANT;
+    "variant-const-cast-attributes" => Atom, VARIANT;
+    "variant-mut-cast-attributes" => Atom, VARIANT;
+    "variant-is-attributes" => Atom, VARIANT;
+    "bitfield" => Atom, FIELD;
+    "prefix" => Atom, FUNCTION;
+    "postfix" => Atom, FUNCTION;
+    "ptrs-as-arrays" => List, FUNCTION;
+};
+
+/// Finds the known annotation closest to `name`, if any is close enough to
+/// plausibly be what was meant.
+fn suggest_annotation(name: &str) -> Option<&'static str> {
+    KNOWN_ANNOTATIONS
+        .iter()
+        .filter(|x| !x.name.starts_with("internal-"))
+        .map(|x| {
+            let distance = if x.name.ends_with(name) || x.name.starts_with(name) {
+                x.name.len() - name.len()
+            } else {
+                edit_distance(name, x.name)
+            };
+            (distance, x.name)
+        })
+        .filter(|(distance, candidate)| *distance <= candidate.len().max(name.len()) / 3)
+        .min()
+        .map(|(_, candidate)| candidate)
+}
+
+/// The Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let current = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                previous
+            } else {
+                1 + previous.min(current).min(row[j])
+            };
+            previous = current;
+        }
+    }
+    row[b.len()]
+}
+
+/// Parses an annotation like "name" or "name=value", returning `None` if it
+/// has more than one `=`.
+fn parse_annotation(annotation: &str) -> Option<(String, AnnotationValue)> {
+    // Split the annotation in two
+    let parts: Vec<&str> = annotation.split('=').map(|x| x.trim()).collect();
+
+    if parts.len() > 2 {
+        return None;
+    }
+
+    // Grab the name that this annotation is modifying
+    let name = parts[0].to_string();
+
+    // If the annotation only has a name, assume it's setting a bool flag
+    if parts.len() == 1 {
+        return Some((name, AnnotationValue::Bool(true)));
+    }
+
+    // Parse the value we're setting the name to
+    let value = parts[1];
+
+    if let Some(x) = parse_list(value) {
+        return Some((name, AnnotationValue::List(x)));
+    }
+    if let Ok(x) = value.parse::<bool>() {
+        return Some((name, AnnotationValue::Bool(x)));
+    }
+    Some((
+        name,
+        if value.is_empty() {
+            AnnotationValue::Atom(None)
+        } else {
+            AnnotationValue::Atom(Some(value.to_string()))
+        },
+    ))
 }
 
 /// Parse lists like "[x, y, z]". This is not implemented efficiently or well.
@@ -224,3 +543,53 @@ fn parse_list(list: &str) -> Option<Vec<String>> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bindgen::diagnostic::DiagnosticLevel;
+
+    fn annotations(line: &str) -> AnnotationSet {
+        let attr: syn::Attribute = syn::parse_quote!(#[doc = #line]);
+        AnnotationSet::load(&[attr]).unwrap()
+    }
+
+    #[test]
+    fn unknown_annotation_suggestion() {
+        let mut diagnostics = vec![];
+        annotations("cbindgen:derive-eqq").validate(
+            AnnotationTarget::Struct,
+            "Foo",
+            &mut diagnostics,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DiagnosticCode::UnknownAnnotation);
+        assert_eq!(
+            diagnostics[0].message,
+            "Foo: unknown annotation `cbindgen:derive-eqq`. Did you mean `cbindgen:derive-eq`?"
+        );
+    }
+
+    #[test]
+    fn misplaced_annotation() {
+        let mut diagnostics = vec![];
+        annotations("cbindgen:prefix-with-name").validate(
+            AnnotationTarget::Struct,
+            "Foo",
+            &mut diagnostics,
+        );
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].code, DiagnosticCode::MisplacedAnnotation);
+        assert!(diagnostics[0].message.contains("has no effect on a struct"));
+    }
+
+    #[test]
+    fn invalid_annotation_value() {
+        let set = annotations("cbindgen:rename-all=NotARule");
+        let mut diagnostics = vec![];
+        set.validate(AnnotationTarget::Enum, "Foo", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].level, DiagnosticLevel::Error);
+        assert!(set.parse_atom::<RenameRule>("rename-all").is_none());
+    }
+}
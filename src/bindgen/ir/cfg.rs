@@ -42,6 +42,31 @@ impl<'a> DefineKey<'a> {
     }
 }
 
+/// Checks that a key of the `[defines]` table is either a cfg name, such as
+/// `unix`, or a cfg name and an unquoted value, such as `target_os = freebsd`.
+pub(crate) fn check_define_key(key: &str) -> Result<(), String> {
+    let is_name = |s: &str| syn::parse_str::<syn::Ident>(s).is_ok();
+    match DefineKey::load(key) {
+        DefineKey::Boolean(_) if key.contains('=') => {
+            Err("expected a cfg name, or a name and a value separated by one `=`".to_owned())
+        }
+        DefineKey::Boolean(name) if !is_name(name) => Err(if name.contains('(') {
+            "`any()`, `all()` and `not()` aren't supported, list each cfg separately".to_owned()
+        } else {
+            format!("`{name}` isn't a valid cfg name")
+        }),
+        DefineKey::Named(name, _) if !is_name(name) => {
+            Err(format!("`{name}` isn't a valid cfg name"))
+        }
+        DefineKey::Named(_, "") => Err("the value is empty".to_owned()),
+        DefineKey::Named(_, value) if value.starts_with('"') => Err(format!(
+            "the value must not be quoted, use `{}` instead",
+            key.replace('"', "")
+        )),
+        _ => Ok(()),
+    }
+}
+
 #[derive(Debug, Clone)]
 pub enum Cfg {
     Boolean(String),
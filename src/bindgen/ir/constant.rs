@@ -9,7 +9,7 @@ use std::io::Write;
 use syn::ext::IdentExt;
 use syn::UnOp;
 
-use crate::bindgen::config::{Config, Language};
+use crate::bindgen::config::{CStandard, Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
@@ -166,6 +166,56 @@ impl Literal {
         }
     }
 
+    /// Changes the name every path to `path` in this literal is written as.
+    pub(crate) fn rename_references(&mut self, path: &Path, export_name: &str) {
+        match *self {
+            Literal::PostfixUnaryOp { ref mut value, .. } => {
+                value.rename_references(path, export_name);
+            }
+            Literal::BinOp {
+                ref mut left,
+                ref mut right,
+                ..
+            } => {
+                left.rename_references(path, export_name);
+                right.rename_references(path, export_name);
+            }
+            Literal::FieldAccess { ref mut base, .. } => {
+                base.rename_references(path, export_name);
+            }
+            Literal::Struct {
+                path: ref struct_path,
+                export_name: ref mut struct_export_name,
+                ref mut fields,
+            } => {
+                if struct_path == path {
+                    export_name.clone_into(struct_export_name);
+                }
+                for ref mut expr in fields.values_mut() {
+                    expr.value.rename_references(path, export_name);
+                }
+            }
+            Literal::Cast {
+                ref mut ty,
+                ref mut value,
+            } => {
+                ty.rename_references(path, export_name);
+                value.rename_references(path, export_name);
+            }
+            Literal::Path {
+                ref mut associated_to,
+                ..
+            } => {
+                if let Some((ref assoc_path, ref mut assoc_export_name)) = *associated_to {
+                    if assoc_path == path {
+                        export_name.clone_into(assoc_export_name);
+                    }
+                }
+            }
+            Literal::Expr(..) => {}
+        }
+    }
+
     fn is_valid(&self, bindings: &Bindings) -> bool {
         match *self {
             Literal::Expr(..) => true,
@@ -209,7 +259,7 @@ impl Literal {
             } => left.visit(visitor) && right.visit(visitor),
             Literal::FieldAccess { ref base, .. } => base.visit(visitor),
             Literal::Struct { ref fields, .. } => {
-                for (_name, field) in fields.iter() {
+                for field in fields.values() {
                     if !field.value.visit(visitor) {
                         return false;
                     }
@@ -503,6 +553,10 @@ pub struct Constant {
 }
 
 impl Constant {
+    pub fn simplify_standard_types(&mut self, config: &Config) {
+        self.ty.simplify_standard_types(config);
+    }
+
     pub fn load(
         path: Path,
         mod_cfg: Option<&Cfg>,
@@ -732,6 +786,18 @@ impl Constant {
                 language_backend.write_literal(out, value);
                 write!(out, ";");
             }
+            // C23 only has `constexpr` objects of scalar types.
+            Language::C
+                if allow_constexpr
+                    && config.c_standard_at_least(CStandard::C23)
+                    && matches!(self.ty, Type::Primitive(..)) =>
+            {
+                out.write("static constexpr ");
+                language_backend.write_type(out, &self.ty);
+                write!(out, " {name} = ");
+                language_backend.write_literal(out, value);
+                write!(out, ";");
+            }
             Language::Cxx | Language::C => {
                 write!(out, "#define {name} ");
                 language_backend.write_literal(out, value);
@@ -6,13 +6,14 @@ use std::io::Write;
 
 use syn::ext::IdentExt;
 
-use crate::bindgen::config::{Config, Language};
+use crate::bindgen::config::{CStandard, Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
 use crate::bindgen::dependencies::Dependencies;
 use crate::bindgen::ir::{
-    AnnotationSet, AnnotationValue, Cfg, ConditionWrite, DeprecatedNoteKind, Documentation, Field,
-    GenericArgument, GenericParams, GenericPath, Item, ItemContainer, Literal, Path, Repr,
-    ReprStyle, Struct, ToCondition, Type,
+    AnnotationSet, AnnotationValue, Cfg, ConditionWrite, Constant, DeprecatedNoteKind,
+    Documentation, Field, GenericArgument, GenericParams, GenericPath, IntKind, Item,
+    ItemContainer, Literal, Path, PrimitiveType, Repr, ReprStyle, Struct, ToCondition, Type,
+    Typedef,
 };
 use crate::bindgen::language_backend::LanguageBackend;
 use crate::bindgen::library::Library;
@@ -65,7 +66,7 @@ impl VariantBody {
         &self,
         generic_values: &[GenericArgument],
         mappings: &[(&Path, &GenericArgument)],
-        config: &Config,
+        library: &Library,
     ) -> Self {
         match *self {
             Self::Empty(ref annos) => Self::Empty(annos.clone()),
@@ -76,7 +77,7 @@ impl VariantBody {
                 inline_casts,
             } => Self::Body {
                 name: name.clone(),
-                body: body.specialize(generic_values, mappings, config),
+                body: body.specialize(generic_values, mappings, library),
                 inline,
                 inline_casts,
             },
@@ -270,12 +271,16 @@ impl EnumVariant {
         &self,
         generic_values: &[GenericArgument],
         mappings: &[(&Path, &GenericArgument)],
-        config: &Config,
+        library: &Library,
     ) -> Self {
         Self::new(
-            mangle::mangle_name(&self.name, generic_values, &config.export.mangle),
+            mangle::mangle_name(
+                &self.name,
+                generic_values,
+                &library.get_config().export.mangle,
+            ),
             self.discriminant.clone(),
-            self.body.specialize(generic_values, mappings, config),
+            self.body.specialize(generic_values, mappings, library),
             self.cfg.clone(),
             self.documentation.clone(),
         )
@@ -318,6 +323,16 @@ impl Enum {
         repr.style != ReprStyle::C
     }
 
+    /// Whether the enum is written as an integer typedef and a constant for
+    /// each variant, see `EnumConfig::non_exhaustive_as_constants`.
+    pub(crate) fn is_written_as_constants(&self, config: &Config) -> bool {
+        self.annotations.non_exhaustive
+            && self.tag.is_none()
+            && config
+                .enumeration
+                .non_exhaustive_as_constants(&self.annotations)
+    }
+
     pub fn add_monomorphs(&self, library: &Library, out: &mut Monomorphs) {
         if self.is_generic() {
             return;
@@ -562,38 +577,20 @@ impl Item for Enum {
             .unwrap_or(&config.enumeration.rename_variants);
 
         if let Some(r) = rules.not_none() {
-            self.variants = self
-                .variants
-                .iter()
-                .map(|variant| {
-                    EnumVariant::new(
-                        r.apply(
-                            &variant.export_name,
-                            IdentifierType::EnumVariant {
-                                prefix: &self.export_name,
-                            },
-                        )
-                        .into_owned(),
-                        variant.discriminant.clone(),
-                        match variant.body {
-                            VariantBody::Empty(..) => variant.body.clone(),
-                            VariantBody::Body {
-                                ref name,
-                                ref body,
-                                inline,
-                                inline_casts,
-                            } => VariantBody::Body {
-                                name: r.apply(name, IdentifierType::StructMember).into_owned(),
-                                body: body.clone(),
-                                inline,
-                                inline_casts,
-                            },
+            // The Rust names of the variants are kept for `to_string_rust_names`.
+            for variant in &mut self.variants {
+                variant.export_name = r
+                    .apply(
+                        &variant.export_name,
+                        IdentifierType::EnumVariant {
+                            prefix: &self.export_name,
                         },
-                        variant.cfg.clone(),
-                        variant.documentation.clone(),
                     )
-                })
-                .collect();
+                    .into_owned();
+                if let VariantBody::Body { ref mut name, .. } = variant.body {
+                    *name = r.apply(name, IdentifierType::StructMember).into_owned();
+                }
+            }
         }
     }
 
@@ -611,11 +608,7 @@ impl Item for Enum {
             }
         }
 
-        let mangled_path = mangle::mangle_path(
-            &self.path,
-            generic_values,
-            &library.get_config().export.mangle,
-        );
+        let mangled_path = library.monomorph_path(&self.path, generic_values);
 
         let monomorph = Enum::new(
             mangled_path,
@@ -623,7 +616,7 @@ impl Item for Enum {
             self.repr,
             self.variants
                 .iter()
-                .map(|v| v.specialize(generic_values, &mappings, library.get_config()))
+                .map(|v| v.specialize(generic_values, &mappings, library))
                 .collect(),
             self.tag.clone(),
             self.cfg.clone(),
@@ -648,7 +641,7 @@ impl Enum {
     pub(crate) fn write_tag_enum<
         F: Write,
         LB: LanguageBackend,
-        WV: Fn(&mut LB, &mut SourceWriter<F>, &EnumVariant),
+        WV: Fn(&mut LB, &mut SourceWriter<F>, &EnumVariant, bool),
     >(
         &self,
         config: &Config,
@@ -658,10 +651,12 @@ impl Enum {
         write_variant: WV,
     ) {
         let tag_name = self.tag_name();
+        // C23 enums can have a fixed underlying type, like in C++.
+        let typed = size.is_some() && config.c_standard_at_least(CStandard::C23);
         // Open the tag enum.
         match config.language {
             Language::C => {
-                if let Some(prim) = size {
+                if let Some(prim) = size.filter(|_| !typed) {
                     // If we need to specify size, then we have no choice but to create a typedef,
                     // so `config.style` is not respected.
                     write!(out, "enum");
@@ -679,11 +674,13 @@ impl Enum {
                         out.new_line();
                         write!(out, "  : {prim}");
                         out.new_line();
-                        out.write("#endif // __cplusplus");
+                        write!(out, "#endif {}", config.line_comment("__cplusplus"));
                         out.new_line();
                     }
                 } else {
-                    if config.style.generate_typedef() {
+                    // A typed enum is always given a typedef, like an enum with a typedef
+                    // of its size before C23.
+                    if config.style.generate_typedef() || typed {
                         out.write("typedef ");
                     }
                     out.write("enum");
@@ -696,6 +693,9 @@ impl Enum {
                     if config.style.generate_tag() {
                         write!(out, " {tag_name}");
                     }
+                    if let Some(prim) = size {
+                        write!(out, " : {prim}");
+                    }
                 }
             }
             Language::Cxx => {
@@ -735,16 +735,20 @@ impl Enum {
         }
         out.open_brace();
 
-        // Emit enumerators for the tag enum.
+        // Emit enumerators for the tag enum. C89 doesn't allow a comma after the last one.
+        let last_comma = !(config.language == Language::C && config.c_standard == CStandard::C89);
         for (i, variant) in self.variants.iter().enumerate() {
             if i != 0 {
                 out.new_line()
             }
-            write_variant(language_backend, out, variant);
+            let comma = last_comma || i + 1 != self.variants.len();
+            write_variant(language_backend, out, variant, comma);
         }
 
         // Close the tag enum.
-        if config.language == Language::C && size.is_none() && config.style.generate_typedef() {
+        if config.language == Language::C
+            && ((size.is_none() && config.style.generate_typedef()) || typed)
+        {
             out.close_brace(false);
             write!(out, " {tag_name};");
         } else {
@@ -752,9 +756,9 @@ impl Enum {
         }
 
         // Emit typedef specifying the tag enum's size if necessary.
-        // In C++ enums can "inherit" from numeric types (`enum E: uint8_t { ... }`),
-        // but in C `typedef uint8_t E` is the only way to give a fixed size to `E`.
-        if let Some(prim) = size {
+        // In C++ and C23 enums can "inherit" from numeric types (`enum E: uint8_t { ... }`),
+        // but before C23 `typedef uint8_t E` is the only way to give a fixed size to `E`.
+        if let Some(prim) = size.filter(|_| !typed) {
             if config.cpp_compatible_c() {
                 out.new_line_if_not_start();
                 out.write("#ifndef __cplusplus");
@@ -767,12 +771,257 @@ impl Enum {
 
             if config.cpp_compatible_c() {
                 out.new_line_if_not_start();
-                out.write("#endif // __cplusplus");
+                write!(out, "#endif {}", config.line_comment("__cplusplus"));
             }
         }
 
         // Emit convenience methods for the tag enum.
         self.write_derived_functions_enum(config, language_backend, out);
+
+        // Emit string conversions, which need the type as it's written in C.
+        if config.language == Language::C && size.is_none() && !config.style.generate_typedef() {
+            self.write_string_functions(config, out, &format!("enum {tag_name}"), false);
+        } else {
+            let qualified = config.language == Language::Cxx;
+            self.write_string_functions(config, out, tag_name, qualified);
+        }
+    }
+
+    /// Whether the casts written for this enum check the tag with the standard
+    /// `assert`, and so need its header.
+    pub(crate) fn casts_use_assert(&self, config: &Config) -> bool {
+        let enumeration = &config.enumeration;
+        let casts = enumeration.derive_const_casts(&self.annotations)
+            || enumeration.derive_mut_casts(&self.annotations);
+        let written = match config.language {
+            Language::C => enumeration.derive_c_helpers(&self.annotations),
+            Language::Cxx => enumeration.derive_helper_methods(&self.annotations),
+            Language::Cython => false,
+        };
+        self.tag.is_some() && casts && written && enumeration.cast_assert_name.is_none()
+    }
+
+    /// Whether `Foo_to_string` is written for this enum.
+    pub(crate) fn derives_to_string(&self, config: &Config) -> bool {
+        self.tag.is_none() && config.enumeration.derive_to_string(&self.annotations)
+    }
+
+    /// Whether `Foo_from_string` is written for this enum.
+    pub(crate) fn derives_from_string(&self, config: &Config) -> bool {
+        self.tag.is_none() && config.enumeration.derive_from_string(&self.annotations)
+    }
+
+    /// Emit `Foo_to_string` and `Foo_from_string` functions for an enum without
+    /// data, `ty` being the enum type as it's written, and `qualified` whether
+    /// the variants are scoped to it.
+    pub(crate) fn write_string_functions<F: Write>(
+        &self,
+        config: &Config,
+        out: &mut SourceWriter<F>,
+        ty: &str,
+        qualified: bool,
+    ) {
+        if config.language == Language::Cython || self.tag.is_some() {
+            return;
+        }
+        let name = self.export_name();
+        let rust_names = config.enumeration.to_string_rust_names(&self.annotations);
+        let path = |variant: &EnumVariant| {
+            if qualified {
+                format!("{}::{}", name, variant.export_name)
+            } else {
+                variant.export_name.clone()
+            }
+        };
+        let string = |variant: &EnumVariant| {
+            if rust_names {
+                variant.name.clone()
+            } else {
+                variant.export_name.clone()
+            }
+        };
+        let value = config
+            .function
+            .rename_args
+            .apply("value", IdentifierType::FunctionArg);
+        let static_inline = config.static_inline();
+
+        if self.derives_to_string(config) {
+            out.new_line();
+            out.new_line();
+            if config.language == Language::Cxx {
+                write!(
+                    out,
+                    "constexpr std::string_view {name}_to_string({ty} {value})"
+                );
+            } else {
+                write!(
+                    out,
+                    "{static_inline} const char *{name}_to_string({ty} {value})"
+                );
+            }
+            out.open_brace();
+            write!(out, "switch ({value})");
+            out.open_brace();
+            for (i, variant) in self.variants.iter().enumerate() {
+                if i != 0 {
+                    out.new_line();
+                }
+                let condition = variant.cfg.to_condition(config);
+                condition.write_before(config, out);
+                write!(
+                    out,
+                    "case {}: return \"{}\";",
+                    path(variant),
+                    string(variant)
+                );
+                condition.write_after(config, out);
+            }
+            out.close_brace(false);
+            out.new_line();
+            // Values that aren't variants can come from C.
+            if config.language == Language::Cxx {
+                out.write("return {};");
+            } else if config.c_standard_at_least(CStandard::C23) {
+                out.write("return nullptr;");
+            } else {
+                out.write("return NULL;");
+            }
+            out.close_brace(false);
+        }
+
+        if self.derives_from_string(config) {
+            let string_arg = config
+                .function
+                .rename_args
+                .apply("string", IdentifierType::FunctionArg);
+            out.new_line();
+            out.new_line();
+            if config.language == Language::Cxx {
+                write!(
+                    out,
+                    "constexpr std::optional<{ty}> {name}_from_string(std::string_view {string_arg})"
+                );
+            } else {
+                write!(
+                    out,
+                    "{static_inline} bool {name}_from_string(const char *{string_arg}, {ty} *{value})"
+                );
+            }
+            out.open_brace();
+            for variant in &self.variants {
+                let condition = variant.cfg.to_condition(config);
+                condition.write_before(config, out);
+                if config.language == Language::Cxx {
+                    write!(out, "if ({string_arg} == \"{}\")", string(variant));
+                    out.open_brace();
+                    write!(out, "return {};", path(variant));
+                } else {
+                    write!(
+                        out,
+                        "if (strcmp({string_arg}, \"{}\") == 0)",
+                        string(variant)
+                    );
+                    out.open_brace();
+                    write!(out, "*{value} = {};", path(variant));
+                    out.new_line();
+                    out.write("return true;");
+                }
+                out.close_brace(false);
+                condition.write_after(config, out);
+                out.new_line();
+            }
+            if config.language == Language::Cxx {
+                out.write("return std::nullopt;");
+            } else {
+                out.write("return false;");
+            }
+            out.close_brace(false);
+        }
+    }
+
+    /// Emit an enum without data as a typedef of its representation and a
+    /// constant for each variant, so that values which aren't variants can be
+    /// stored in it.
+    pub(crate) fn write_as_constants<F: Write, LB: LanguageBackend>(
+        &self,
+        config: &Config,
+        language_backend: &mut LB,
+        out: &mut SourceWriter<F>,
+    ) {
+        // A `#[repr(C)]` enum has the size of a C enum, which is an `int`.
+        let prim = match self.repr.ty {
+            Some(ty) => ty.to_primitive(),
+            None => PrimitiveType::Integer {
+                kind: IntKind::B32,
+                signed: true,
+                zeroable: true,
+            },
+        };
+        let mut documentation = self.documentation.clone();
+        if !documentation.doc_comment.is_empty() {
+            documentation.doc_comment.push(String::new());
+        }
+        documentation.doc_comment.push(
+            " This enum is non-exhaustive, so values other than the ones below may appear."
+                .to_owned(),
+        );
+        let typedef = Typedef::new(
+            Path::new(self.export_name()),
+            GenericParams::default(),
+            Type::Primitive(prim.clone()),
+            self.cfg.clone(),
+            AnnotationSet::new(),
+            documentation,
+        );
+        language_backend.write_type_def(out, &typedef);
+
+        let ty = Type::Path(GenericPath::new(Path::new(self.export_name()), vec![]));
+        let mut implicit = Literal::Expr("0".to_owned());
+        for variant in &self.variants {
+            let value = variant.discriminant.clone().unwrap_or(implicit);
+            implicit = match int_value(&value) {
+                Some(n) if n >= i64::MAX as i128 => Literal::Expr(format!("{}ull", n + 1)),
+                Some(n) => Literal::Expr((n + 1).to_string()),
+                None => Literal::BinOp {
+                    left: Box::new(value.clone()),
+                    op: "+",
+                    right: Box::new(Literal::Expr("1".to_owned())),
+                },
+            };
+            let constant = Constant::new(
+                Path::new(variant.export_name.clone()),
+                ty.clone(),
+                value,
+                Cfg::append(self.cfg.as_ref(), variant.cfg.clone()),
+                AnnotationSet::new(),
+                variant.documentation.clone(),
+                None,
+            );
+            out.new_line();
+            constant.write(config, language_backend, out, None);
+        }
+
+        if config.enumeration.add_unknown_sentinel(&self.annotations) {
+            // e.g. `uint8_t` -> `UINT8_MAX`, from <stdint.h>.
+            let max = format!(
+                "{}_MAX",
+                prim.to_repr_c(config).trim_end_matches("_t").to_uppercase()
+            );
+            let constant = Constant::new(
+                Path::new(format!("{}_UNKNOWN", self.export_name())),
+                ty,
+                Literal::Expr(max),
+                self.cfg.clone(),
+                AnnotationSet::new(),
+                Documentation::simple(" Stands for values that aren't variants."),
+                None,
+            );
+            out.new_line();
+            constant.write(config, language_backend, out, None);
+        }
+
+        self.write_string_functions(config, out, self.export_name(), false);
     }
 
     /// The code here mirrors the beginning of `Struct::write` and `Union::write`.
@@ -791,7 +1040,9 @@ impl Enum {
         out.write(if inline_tag_field { "union" } else { "struct" });
 
         if self.annotations.must_use(config) {
-            if let Some(ref anno) = config.structure.must_use {
+            if let Some(anno) = (config.structure.must_use.as_deref())
+                .or_else(|| config.c23_attribute("[[nodiscard]]"))
+            {
                 write!(out, " {anno}");
             }
         }
@@ -1290,6 +1541,72 @@ impl Enum {
             }
         }
 
+        if config.enumeration.derive_visit(&self.annotations) {
+            // Every path of a function returning a value has to return, so the
+            // last variant without a condition also takes the tags that aren't
+            // variants, which are undefined behavior anyway.
+            let default = self.variants.iter().rposition(|v| v.cfg.is_none());
+            let write_switch =
+                |out: &mut SourceWriter<F>, case: &dyn Fn(usize, &EnumVariant) -> String| {
+                    write!(out, "switch (tag)");
+                    out.open_brace();
+                    for (i, variant) in self.variants.iter().enumerate() {
+                        if i != 0 {
+                            out.new_line();
+                        }
+                        let condition = variant.cfg.to_condition(config);
+                        condition.write_before(config, out);
+                        write!(out, "case {}::{}:", tag_name, variant.export_name);
+                        if default == Some(i) {
+                            out.new_line();
+                            out.write("default:");
+                        }
+                        write!(out, " return {};", case(i, variant));
+                        condition.write_after(config, out);
+                    }
+                    out.close_brace(false);
+                };
+
+            for const_visit in [true, false] {
+                out.new_line();
+                out.new_line();
+                out.write("template <class F>");
+                out.new_line();
+                write!(out, "decltype(auto) visit(F&& f)");
+                if const_visit {
+                    out.write(" const");
+                }
+                out.open_brace();
+                write_switch(out, &|_, variant| {
+                    let arg = match variant.body {
+                        VariantBody::Body { ref name, .. } => name.clone(),
+                        VariantBody::Empty(..) => format!(
+                            "std::integral_constant<{tag_name}, {tag_name}::{}>()",
+                            variant.export_name
+                        ),
+                    };
+                    format!("std::forward<F>(f)({arg})")
+                });
+                out.close_brace(false);
+            }
+
+            out.new_line();
+            out.new_line();
+            out.write("size_t index() const");
+            out.open_brace();
+            write_switch(out, &|i, _| i.to_string());
+            out.close_brace(false);
+
+            out.new_line();
+            out.new_line();
+            write!(out, "template <{tag_name} T>");
+            out.new_line();
+            out.write("bool holds() const");
+            out.open_brace();
+            out.write("return tag == T;");
+            out.close_brace(false);
+        }
+
         let other = config
             .function
             .rename_args
@@ -1497,9 +1814,259 @@ impl Enum {
         }
     }
 
+    /// Emit the C equivalents of the methods of `write_derived_functions_data`
+    /// as functions following the struct or union of an enum with data.
+    pub(crate) fn write_c_helper_functions<
+        F: Write,
+        LB: LanguageBackend,
+        WF: Fn(&mut LB, &mut SourceWriter<F>, &Field),
+    >(
+        &self,
+        config: &Config,
+        language_backend: &mut LB,
+        out: &mut SourceWriter<F>,
+        write_field: WF,
+    ) {
+        if config.language != Language::C || !config.enumeration.derive_c_helpers(&self.annotations)
+        {
+            return;
+        }
+        let helper_methods = config.enumeration.derive_helper_methods(&self.annotations);
+        let const_casts = config.enumeration.derive_const_casts(&self.annotations);
+        let mut_casts = config.enumeration.derive_mut_casts(&self.annotations);
+        if !helper_methods && !const_casts && !mut_casts {
+            return;
+        }
+
+        let name = self.export_name();
+        let keyword = |keyword: &'static str| {
+            if config.style.generate_typedef() {
+                ""
+            } else {
+                keyword
+            }
+        };
+        let ty = if Self::inline_tag_field(&self.repr) {
+            format!("{}{name}", keyword("union "))
+        } else {
+            format!("{}{name}", keyword("struct "))
+        };
+        let arg_renamer = |name: &str| {
+            config
+                .function
+                .rename_args
+                .apply(name, IdentifierType::FunctionArg)
+                .into_owned()
+        };
+        let value = arg_renamer("value");
+        let assert_name = match config.enumeration.cast_assert_name {
+            Some(ref n) => &**n,
+            None => "assert",
+        };
+        let static_inline = config.static_inline();
+
+        for variant in &self.variants {
+            let variant_name = &variant.export_name;
+
+            macro_rules! write_attrs {
+                ($op:expr) => {{
+                    if let Some(Some(attrs)) =
+                        variant
+                            .body
+                            .annotations()
+                            .atom(concat!("variant-", $op, "-attributes"))
+                    {
+                        write!(out, "{} ", attrs);
+                    }
+                }};
+            }
+
+            // The fields of inline bodies are members of an anonymous struct,
+            // so they're accessed directly rather than through the variant.
+            let (member, tag_field, fields, inline) = match variant.body {
+                VariantBody::Body {
+                    ref name,
+                    ref body,
+                    inline,
+                    ..
+                } => {
+                    let (tag_field, fields) = body.fields.split_at(body.has_tag_field as usize);
+                    (Some(name), tag_field.first(), fields, inline)
+                }
+                VariantBody::Empty(..) => (None, None, &[][..], false),
+            };
+            let access = |field: &Field| match member {
+                Some(member) if !inline => format!("{member}.{}", field.name),
+                _ => field.name.clone(),
+            };
+            // Only the constructor and the predicate apply to variants without
+            // fields.
+            if !helper_methods && fields.is_empty() {
+                continue;
+            }
+
+            out.new_line();
+            out.new_line();
+
+            let condition = variant.cfg.to_condition(config);
+            condition.write_before(config, out);
+
+            if helper_methods {
+                write_attrs!("constructor");
+                write!(out, "{static_inline} {ty} {name}_{variant_name}(");
+                if fields.is_empty() {
+                    out.write("void");
+                } else {
+                    let args: Vec<_> = fields
+                        .iter()
+                        .map(|field| {
+                            Field::from_name_and_type(arg_renamer(&field.name), field.ty.clone())
+                        })
+                        .collect();
+                    out.write_vertical_source_list(
+                        language_backend,
+                        &args[..],
+                        ListType::Join(","),
+                        &write_field,
+                    );
+                }
+                write!(out, ")");
+                out.open_brace();
+                write!(out, "{ty} result;");
+                if fields
+                    .iter()
+                    .any(|field| matches!(field.ty, Type::Array(..)))
+                {
+                    out.new_line();
+                    out.write("int i;");
+                }
+                for field in fields {
+                    out.new_line();
+                    match field.ty {
+                        Type::Array(_, ref length) => {
+                            // Arrays aren't assignable in C, so copy the elements.
+                            write!(out, "for (i = 0; i < {}; i++)", length.as_str());
+                            out.open_brace();
+                            write!(
+                                out,
+                                "result.{}[i] = {}[i];",
+                                access(field),
+                                arg_renamer(&field.name)
+                            );
+                            out.close_brace(false);
+                        }
+                        _ => {
+                            write!(
+                                out,
+                                "result.{} = {};",
+                                access(field),
+                                arg_renamer(&field.name)
+                            );
+                        }
+                    }
+                }
+                out.new_line();
+                // With an inline tag, the variant is written through its own
+                // member of the union, which holds a copy of the tag.
+                match tag_field {
+                    Some(tag_field) => {
+                        write!(out, "result.{} = {variant_name};", access(tag_field))
+                    }
+                    None => write!(out, "result.tag = {variant_name};"),
+                }
+                out.new_line();
+                out.write("return result;");
+                out.close_brace(false);
+
+                out.new_line();
+                out.new_line();
+
+                write_attrs!("is");
+                write!(
+                    out,
+                    "{static_inline} bool {name}_is_{variant_name}(const {ty} *{value})"
+                );
+                out.open_brace();
+                write!(out, "return {value}->tag == {variant_name};");
+                out.close_brace(false);
+            }
+
+            let mut derive_casts = |const_casts: bool, separate: bool| {
+                let member = match member {
+                    Some(member) if !fields.is_empty() => member,
+                    _ => return,
+                };
+
+                if separate {
+                    out.new_line();
+                    out.new_line();
+                }
+
+                let (qualifier, suffix) = if const_casts {
+                    write_attrs!("const-cast");
+                    ("const ", "")
+                } else {
+                    write_attrs!("mut-cast");
+                    ("", "_mut")
+                };
+                out.write(static_inline);
+                out.write(" ");
+                let function =
+                    format!("{name}_as_{variant_name}{suffix}({qualifier}{ty} *{value})");
+                let target = if inline {
+                    // Inline bodies have a single field, which is returned.
+                    let field = fields.last().unwrap();
+                    let return_type = Type::Ptr {
+                        ty: Box::new(field.ty.clone()),
+                        is_const: const_casts,
+                        is_ref: false,
+                        is_nullable: false,
+                    };
+                    write_field(
+                        language_backend,
+                        out,
+                        &Field::from_name_and_type(function, return_type),
+                    );
+                    field.name.clone()
+                } else {
+                    let body = match variant.body {
+                        VariantBody::Body { ref body, .. } => body.export_name(),
+                        VariantBody::Empty(..) => unreachable!(),
+                    };
+                    write!(out, "{qualifier}{}{body} *{function}", keyword("struct "));
+                    member.clone()
+                };
+                out.open_brace();
+                write!(out, "{assert_name}({value}->tag == {variant_name});");
+                out.new_line();
+                write!(out, "return &{value}->{target};");
+                out.close_brace(false);
+            };
+
+            if const_casts {
+                derive_casts(true, helper_methods);
+            }
+
+            if mut_casts {
+                derive_casts(false, helper_methods || const_casts);
+            }
+
+            condition.write_after(config, out);
+        }
+    }
+
     pub fn simplify_standard_types(&mut self, config: &Config) {
         for variant in &mut self.variants {
             variant.simplify_standard_types(config);
         }
     }
 }
+
+/// The value of an integer literal, as loaded by `Literal::load`.
+fn int_value(literal: &Literal) -> Option<i128> {
+    match literal {
+        Literal::Expr(digits) => digits.trim_end_matches(['u', 'l']).parse().ok(),
+        Literal::PostfixUnaryOp { op: "-", value } => int_value(value).map(|n| -n),
+        _ => None,
+    }
+}
@@ -199,7 +199,8 @@ impl Deref for GenericParams {
 /// Note: Both arguments in a type like `Array<T, N>` are represented as
 /// `GenericArgument::Type`s, even if `N` is actually the name of a const. This
 /// is a consequence of `syn::GenericArgument` doing the same thing.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GenericArgument {
     Type(Type),
     Const(ConstExpr),
@@ -234,7 +235,7 @@ impl GenericArgument {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct GenericPath {
     path: Path,
     export_name: String,
@@ -253,6 +254,31 @@ impl GenericPath {
         }
     }
 
+    /// A path to a type of `export.type_map`, written as `export_name`.
+    pub(crate) fn new_mapped(path: Path, export_name: &str) -> Self {
+        Self {
+            path,
+            export_name: export_name.to_owned(),
+            generics: Vec::new(),
+            ctype: None,
+        }
+    }
+
+    /// Parses a generic type written in the config or an annotation, like
+    /// `Foo<u32>`.
+    pub(crate) fn parse(ty: &str, config: &Config) -> Result<Self, String> {
+        let syn_ty = syn::parse_str::<syn::Type>(ty).map_err(|e| format!("{e}"))?;
+        let mut ty = match Type::load(&syn_ty)? {
+            Some(ty) => ty,
+            None => return Err("it is a zero sized type".to_owned()),
+        };
+        ty.simplify_standard_types(config);
+        match ty {
+            Type::Path(path) if !path.generics.is_empty() => Ok(path),
+            _ => Err("it isn't a generic type".to_owned()),
+        }
+    }
+
     pub fn self_path() -> Self {
         Self::new(Path::new("Self"), vec![])
     }
@@ -264,6 +290,14 @@ impl GenericPath {
         // Caller deals with generics.
     }
 
+    /// Changes the name this path is written as if it refers to `path`.
+    pub(crate) fn rename_references(&mut self, path: &Path, export_name: &str) {
+        if self.path == *path {
+            export_name.clone_into(&mut self.export_name);
+        }
+        // Caller deals with generics.
+    }
+
     pub fn path(&self) -> &Path {
         &self.path
     }
@@ -296,7 +330,12 @@ impl GenericPath {
         for generic in &mut self.generics {
             generic.rename_for_config(config, generic_params);
         }
-        if !generic_params.iter().any(|param| param.name == self.path) {
+        if !generic_params.iter().any(|param| param.name == self.path)
+            && config
+                .export
+                .mapped_type(self.path.name(), config.language)
+                .is_none()
+        {
             config.export.rename(&mut self.export_name);
         }
     }
@@ -9,7 +9,6 @@ use crate::bindgen::ir::{
     AnnotationSet, Cfg, Documentation, GenericArgument, GenericParams, Item, ItemContainer, Path,
 };
 use crate::bindgen::library::Library;
-use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
 
 #[derive(Debug, Clone)]
@@ -119,11 +118,7 @@ impl Item for OpaqueItem {
             generic_values.len(),
         );
 
-        let mangled_path = mangle::mangle_path(
-            &self.path,
-            generic_values,
-            &library.get_config().export.mangle,
-        );
+        let mangled_path = library.monomorph_path(&self.path, generic_values);
 
         let monomorph = OpaqueItem::new(
             mangled_path,
@@ -14,7 +14,6 @@ use crate::bindgen::ir::{
     ItemContainer, Path, Repr, ReprAlign, ReprStyle, Type, Typedef,
 };
 use crate::bindgen::library::Library;
-use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::rename::{IdentifierType, RenameRule};
 use crate::bindgen::reserved;
@@ -138,7 +137,7 @@ impl Struct {
         if fields.is_empty() {
             warn!(
                 "Passing zero-sized struct {} across the FFI boundary is undefined behavior",
-                &path
+                path
             );
             is_transparent = false;
         }
@@ -196,9 +195,9 @@ impl Struct {
         &self,
         generic_values: &[GenericArgument],
         mappings: &[(&Path, &GenericArgument)],
-        config: &Config,
+        library: &Library,
     ) -> Self {
-        let mangled_path = mangle::mangle_path(&self.path, generic_values, &config.export.mangle);
+        let mangled_path = library.monomorph_path(&self.path, generic_values);
         Struct::new(
             mangled_path,
             GenericParams::default(),
@@ -398,7 +397,7 @@ impl Item for Struct {
         out: &mut Monomorphs,
     ) {
         let mappings = self.generic_params.call(self.path.name(), generic_values);
-        let monomorph = self.specialize(generic_values, &mappings, library.get_config());
+        let monomorph = self.specialize(generic_values, &mappings, library);
         out.insert_struct(library, self, monomorph, generic_values.to_owned());
     }
 }
@@ -8,13 +8,14 @@ use syn::ext::IdentExt;
 
 use crate::bindgen::config::{Config, Language};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
-use crate::bindgen::dependencies::Dependencies;
+use crate::bindgen::dependencies::{Dependencies, DependencyNode};
 use crate::bindgen::ir::{GenericArgument, GenericParams, GenericPath, Path};
 use crate::bindgen::library::Library;
 use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::utilities::IterHelpers;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PrimitiveType {
     Void,
     Bool,
@@ -33,7 +34,8 @@ pub enum PrimitiveType {
     },
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum IntKind {
     Short,
     Int,
@@ -235,7 +237,8 @@ impl PrimitiveType {
 ///
 /// Used for the `U` part of `[T; U]` and const generics. We support a very
 /// limited vocabulary here: only identifiers and literals.
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ConstExpr {
     Name(String),
     Value(String),
@@ -304,7 +307,8 @@ impl ConstExpr {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Type {
     Ptr {
         ty: Box<Type>,
@@ -570,11 +574,35 @@ impl Type {
 
     pub fn simplify_standard_types(&mut self, config: &Config) {
         self.visit_types(|ty| ty.simplify_standard_types(config));
-        if let Some(ty) = self.simplified_type(config) {
+        if let Some(ty) = self.mapped_type(config) {
+            *self = ty;
+        } else if let Some(ty) = self.simplified_type(config) {
             *self = ty;
         }
     }
 
+    /// The foreign type `export.type_map` maps this type to, if any.
+    fn mapped_type(&self, config: &Config) -> Option<Self> {
+        let export = &config.export;
+        let (name, foreign_name) = match *self {
+            Type::Path(ref path) => (
+                path.name(),
+                export.mapped_type(path.name(), config.language)?,
+            ),
+            // Primitive types don't remember how they were spelled, so a
+            // mapping of any of their names applies to all of them.
+            Type::Primitive(ref primitive) => export
+                .type_mappings(config.language)
+                .find(|&(name, ..)| PrimitiveType::maybe(name).as_ref() == Some(primitive))
+                .map(|(name, foreign_name, _)| (name, foreign_name))?,
+            _ => return None,
+        };
+        Some(Type::Path(GenericPath::new_mapped(
+            Path::new(name),
+            foreign_name,
+        )))
+    }
+
     pub fn replace_self_with(&mut self, self_ty: &Path) {
         if let Type::Path(ref mut generic_path) = *self {
             generic_path.replace_self_with(self_ty);
@@ -582,6 +610,14 @@ impl Type {
         self.visit_types(|ty| ty.replace_self_with(self_ty))
     }
 
+    /// Changes the name every path to `path` in this type is written as.
+    pub(crate) fn rename_references(&mut self, path: &Path, export_name: &str) {
+        if let Type::Path(ref mut generic_path) = *self {
+            generic_path.rename_references(path, export_name);
+        }
+        self.visit_types(|ty| ty.rename_references(path, export_name))
+    }
+
     fn visit_types(&mut self, mut visitor: impl FnMut(&mut Type)) {
         match *self {
             Type::Array(ref mut ty, ..) | Type::Ptr { ref mut ty, .. } => visitor(ty),
@@ -702,16 +738,26 @@ impl Type {
                 let path = generic.path();
                 if !generic_params.iter().any(|param| param.name() == path) {
                     if let Some(items) = library.get_items(path) {
+                        out.add_edge(path);
                         if !out.items.contains(path) {
                             out.items.insert(path.clone());
 
+                            out.enter(DependencyNode::Item(path.clone()));
                             for item in &items {
                                 item.deref().add_dependencies(library, out);
                             }
+                            out.exit();
                             for item in items {
                                 out.order.push(item);
                             }
                         }
+                    } else if library
+                        .get_config()
+                        .export
+                        .mapped_type(path.name(), library.get_config().language)
+                        .is_some()
+                    {
+                        out.mapped_types.insert(path.clone());
                     } else {
                         warn!(
                             "Can't find {path}. This usually means that this type was incompatible or \
@@ -14,7 +14,6 @@ use crate::bindgen::ir::{
     Path, Struct, Type,
 };
 use crate::bindgen::library::Library;
-use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
 
 /// A type alias that is represented as a C typedef
@@ -174,11 +173,7 @@ impl Item for Typedef {
     ) {
         let mappings = self.generic_params.call(self.path.name(), generic_values);
 
-        let mangled_path = mangle::mangle_path(
-            &self.path,
-            generic_values,
-            &library.get_config().export.mangle,
-        );
+        let mangled_path = library.monomorph_path(&self.path, generic_values);
 
         let monomorph = Typedef::new(
             mangled_path,
@@ -12,7 +12,6 @@ use crate::bindgen::ir::{
     Path, Repr, ReprAlign, ReprStyle,
 };
 use crate::bindgen::library::Library;
-use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
 use crate::bindgen::rename::{IdentifierType, RenameRule};
 use crate::bindgen::utilities::IterHelpers;
@@ -228,11 +227,7 @@ impl Item for Union {
     ) {
         let mappings = self.generic_params.call(self.path.name(), generic_values);
 
-        let mangled_path = mangle::mangle_path(
-            &self.path,
-            generic_values,
-            &library.get_config().export.mangle,
-        );
+        let mangled_path = library.monomorph_path(&self.path, generic_values);
 
         let monomorph = Union::new(
             mangled_path,
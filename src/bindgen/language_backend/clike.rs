@@ -1,25 +1,144 @@
+use crate::bindgen::diagnostic::{Diagnostic, DiagnosticCode};
 use crate::bindgen::ir::{
     to_known_assoc_constant, ConditionWrite, DeprecatedNoteKind, Documentation, Enum, EnumVariant,
-    Field, GenericParams, Item, Literal, OpaqueItem, ReprAlign, Static, Struct, ToCondition, Type,
-    Typedef, Union,
+    Field, GenericParams, IntKind, Item, ItemContainer, Literal, OpaqueItem, PrimitiveType,
+    ReprAlign, Static, Struct, ToCondition, Type, Typedef, Union, VariantBody,
 };
 use crate::bindgen::language_backend::LanguageBackend;
 use crate::bindgen::rename::IdentifierType;
 use crate::bindgen::writer::{ListType, SourceWriter};
 use crate::bindgen::{cdecl, Bindings, Config, Language};
-use crate::bindgen::{DocumentationLength, DocumentationStyle};
+use crate::bindgen::{CStandard, CppStandard, DocumentationLength, DocumentationStyle, Formatter};
+use std::collections::{HashMap, HashSet};
 use std::io::Write;
 
 pub struct CLikeLanguageBackend<'a> {
     config: &'a Config,
+    /// The problems found while writing, like traits that can't be derived.
+    diagnostics: Vec<Diagnostic>,
+}
+
+/// How the `_eq` and `_cmp` helpers of C bindings compare two values.
+enum CComparison {
+    /// With the operators of the language.
+    Operator,
+    /// With the helpers of the named struct or enum.
+    Helper(String),
+    /// Element by element, with the given length.
+    Array(Box<CComparison>, String),
+}
+
+impl CComparison {
+    /// The number of nested loops needed to compare arrays.
+    fn array_depth(&self) -> usize {
+        match *self {
+            CComparison::Array(ref element, _) => element.array_depth() + 1,
+            _ => 0,
+        }
+    }
+
+    fn uses_helper(&self) -> bool {
+        match *self {
+            CComparison::Operator => false,
+            CComparison::Helper(..) => true,
+            CComparison::Array(ref element, _) => element.uses_helper(),
+        }
+    }
+
+    /// The name of the index of the loop at `depth`: `i`, `j`, `k`...
+    fn index(depth: usize) -> char {
+        (b'i' + depth as u8) as char
+    }
+}
+
+/// The comparisons of the fields of a `_eq` or `_cmp` helper, grouped by the
+/// variant they belong to for enums.
+struct CComparisons<'a> {
+    variants: Vec<(Option<&'a EnumVariant>, Vec<CComparedField<'a>>)>,
+    /// Whether some variants have no fields to compare.
+    default: bool,
+}
+
+/// A field compared by a `_eq` or `_cmp` helper, with the member to access
+/// it through.
+struct CComparedField<'a> {
+    field: &'a Field,
+    member: String,
+    comparison: CComparison,
+}
+
+/// Fields, with the member to access each of them through.
+type DataFields<'a> = Vec<(&'a Field, String)>;
+
+/// The fields of `s` that hold data, that is all of them but the tag field of
+/// the body of an enum variant, with the member to access each of them
+/// through: `field`, or `member.field` for a body stored in `member`.
+fn data_fields<'a>(s: &'a Struct, member: Option<&str>) -> DataFields<'a> {
+    s.fields[s.has_tag_field as usize..]
+        .iter()
+        .map(|field| match member {
+            Some(member) => (field, format!("{member}.{}", field.name)),
+            None => (field, field.name.clone()),
+        })
+        .collect()
+}
+
+/// The data fields of each variant of an enum, as `data_fields` gives them,
+/// or `None` for variants without a body.
+fn variant_data_fields(e: &Enum) -> Vec<(&EnumVariant, Option<DataFields<'_>>)> {
+    e.variants
+        .iter()
+        .map(|variant| match variant.body {
+            // Inline bodies are anonymous structs, whose fields are members
+            // of the enum.
+            VariantBody::Body {
+                ref name,
+                ref body,
+                inline,
+                ..
+            } => (variant, Some(data_fields(body, (!inline).then_some(name)))),
+            VariantBody::Empty(..) => (variant, None),
+        })
+        .collect()
+}
+
+/// The format spec and argument a formatter writes `expr`, of type `ty`,
+/// with, so that it prints like the `operator<<` of `derive_ostream`.
+fn formatter_arg(ty: &Type, expr: &str) -> (&'static str, String) {
+    match *ty {
+        // Formatters only take pointers to void, and arrays decay to
+        // pointers in `operator<<`.
+        Type::Ptr { .. } | Type::Array(..) => ("", format!("static_cast<const void*>({expr})")),
+        Type::FuncPtr { .. } => ("", format!("reinterpret_cast<const void*>({expr})")),
+        // Streams write bools as numbers and 8-bit integers as characters.
+        Type::Primitive(PrimitiveType::Bool) => (":d", expr.to_owned()),
+        Type::Primitive(PrimitiveType::SChar)
+        | Type::Primitive(PrimitiveType::UChar)
+        | Type::Primitive(PrimitiveType::Integer {
+            kind: IntKind::B8, ..
+        }) => ("", format!("static_cast<char>({expr})")),
+        _ => ("", expr.to_owned()),
+    }
 }
 
 impl<'a> CLikeLanguageBackend<'a> {
     pub fn new(config: &'a Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub(crate) fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics
     }
 
-    fn write_enum_variant<W: Write>(&mut self, out: &mut SourceWriter<W>, u: &EnumVariant) {
+    fn write_enum_variant<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        u: &EnumVariant,
+        comma: bool,
+    ) {
         let condition = u.cfg.to_condition(self.config);
 
         condition.write_before(self.config, out);
@@ -38,7 +157,9 @@ impl<'a> CLikeLanguageBackend<'a> {
 
             self.write_literal(out, discriminant);
         }
-        out.write(",");
+        if comma {
+            out.write(",");
+        }
         condition.write_after(self.config, out);
     }
 
@@ -107,7 +228,7 @@ impl<'a> CLikeLanguageBackend<'a> {
 
         out.new_line();
         if self.config.cpp_compatible_c() {
-            out.write("#endif  // __cplusplus");
+            write!(out, "#endif  {}", self.config.line_comment("__cplusplus"));
             out.new_line();
         }
     }
@@ -261,7 +382,57 @@ impl<'a> CLikeLanguageBackend<'a> {
             out.close_brace(false);
         }
 
-        let skip_fields = s.has_tag_field as usize;
+        // C++20 compilers can write the comparisons of all the fields
+        // themselves, and derive the other operators from `==` and `<=>`.
+        // The fields that `==` can't compare are left to the operators below.
+        if self.config.cpp_standard_at_least(CppStandard::Cpp20) && s.can_derive_eq() {
+            let structure = &self.config.structure;
+            // The attributes of the first derived operator apply to the
+            // defaulted operator it is derived from.
+            let derived = |ops: &[(&str, bool)]| {
+                ops.iter()
+                    .find(|(_, derive)| *derive)
+                    .map(|(name, _)| s.annotations.atom(&format!("{name}-attributes")).flatten())
+            };
+            let eq = derived(&[
+                ("eq", structure.derive_eq(&s.annotations)),
+                ("neq", structure.derive_neq(&s.annotations)),
+            ]);
+            let cmp = derived(&[
+                ("lt", structure.derive_lt(&s.annotations)),
+                ("lte", structure.derive_lte(&s.annotations)),
+                ("gt", structure.derive_gt(&s.annotations)),
+                ("gte", structure.derive_gte(&s.annotations)),
+            ]);
+            if (eq.is_some() || cmp.is_some()) && !wrote_start_newline {
+                out.new_line();
+            }
+            if let Some(attrs) = eq {
+                out.new_line();
+                if let Some(attrs) = attrs {
+                    write!(out, "{attrs} ");
+                }
+                write!(
+                    out,
+                    "friend bool operator==(const {0}&, const {0}&) = default;",
+                    s.export_name()
+                );
+            }
+            if let Some(attrs) = cmp {
+                out.new_line();
+                if let Some(attrs) = attrs {
+                    write!(out, "{attrs} ");
+                }
+                write!(
+                    out,
+                    "friend auto operator<=>(const {0}&, const {0}&) = default;",
+                    s.export_name()
+                );
+            }
+            return;
+        }
+
+        let fields = data_fields(s, None);
 
         macro_rules! emit_op {
             ($op_name:expr, $op:expr, $conjuc:expr) => {{
@@ -288,11 +459,9 @@ impl<'a> CLikeLanguageBackend<'a> {
                 );
                 out.open_brace();
                 out.write("return ");
-                let vec: Vec<_> = s
-                    .fields
+                let vec: Vec<_> = fields
                     .iter()
-                    .skip(skip_fields)
-                    .map(|field| format!("{} {} {}.{}", field.name, $op, other, field.name))
+                    .map(|(_, member)| format!("{} {} {}.{}", member, $op, other, member))
                     .collect();
                 out.write_vertical_source_list(
                     self,
@@ -336,12 +505,717 @@ impl<'a> CLikeLanguageBackend<'a> {
             emit_op!("gte", ">=", "&&");
         }
     }
+
+    /// The name of an item as written outside of the namespaces of the
+    /// bindings.
+    fn qualified_name(&self, name: &str) -> String {
+        let mut path: Vec<&str> = vec![];
+        if let Some(ref namespace) = self.config.namespace {
+            path.push(namespace);
+        }
+        if let Some(ref namespaces) = self.config.namespaces {
+            path.extend(namespaces.iter().map(|namespace| &**namespace));
+        }
+        path.push(name);
+        path.join("::")
+    }
+
+    /// Checks that a field of type `ty` can be hashed, `hashed` being the
+    /// items which already got a `std::hash` specialization.
+    fn can_hash(
+        &self,
+        ty: &Type,
+        items: &HashMap<&str, &ItemContainer>,
+        hashed: &HashSet<&str>,
+    ) -> Result<(), &'static str> {
+        match *ty {
+            Type::Ptr { .. } | Type::FuncPtr { .. } if !self.config.structure.hash_pointers => {
+                Err("is a pointer")
+            }
+            Type::Ptr { .. } | Type::FuncPtr { .. } | Type::Primitive(..) => Ok(()),
+            Type::Array(ref ty, _) => self.can_hash(ty, items, hashed),
+            Type::Path(ref path) => {
+                let name = path.export_name();
+                let hashable = match items.get(name) {
+                    Some(ItemContainer::Typedef(t)) => {
+                        return self.can_hash(&t.aliased, items, hashed)
+                    }
+                    Some(ItemContainer::Struct(s)) => match s.as_typedef() {
+                        Some(t) => return self.can_hash(&t.aliased, items, hashed),
+                        None => hashed.contains(name),
+                    },
+                    Some(ItemContainer::Enum(e)) => e.tag.is_none() || hashed.contains(name),
+                    Some(_) => false,
+                    // Types from `export.type_map` or the standard library.
+                    None => true,
+                };
+                if hashable {
+                    Ok(())
+                } else {
+                    Err("has a type without a `std::hash` specialization")
+                }
+            }
+        }
+    }
+
+    /// Finds how the `_eq` helpers, or the `_cmp` ones with `order`, compare
+    /// values of type `ty`, `compared` being the items which already got one.
+    fn c_comparison(
+        &self,
+        ty: &Type,
+        items: &HashMap<&str, &ItemContainer>,
+        compared: &HashSet<&str>,
+        order: bool,
+    ) -> Result<CComparison, &'static str> {
+        match *ty {
+            Type::FuncPtr { .. } if order => Err("is a function pointer"),
+            Type::Ptr { .. } | Type::FuncPtr { .. } | Type::Primitive(..) => {
+                Ok(CComparison::Operator)
+            }
+            Type::Array(ref ty, ref length) => Ok(CComparison::Array(
+                Box::new(self.c_comparison(ty, items, compared, order)?),
+                length.as_str().to_owned(),
+            )),
+            Type::Path(ref path) => {
+                let name = path.export_name();
+                match items.get(name) {
+                    Some(ItemContainer::Typedef(t)) => {
+                        self.c_comparison(&t.aliased, items, compared, order)
+                    }
+                    Some(ItemContainer::Struct(s)) if s.as_typedef().is_some() => {
+                        let t = s.as_typedef().unwrap();
+                        self.c_comparison(&t.aliased, items, compared, order)
+                    }
+                    Some(ItemContainer::Enum(e)) if e.tag.is_none() => Ok(CComparison::Operator),
+                    Some(ItemContainer::Struct(..)) | Some(ItemContainer::Enum(..))
+                        if compared.contains(name) =>
+                    {
+                        Ok(CComparison::Helper(name.to_owned()))
+                    }
+                    Some(ItemContainer::Struct(..)) | Some(ItemContainer::Enum(..)) => {
+                        Err("has no comparison helper")
+                    }
+                    _ => Err("can't be compared"),
+                }
+            }
+        }
+    }
+
+    /// Emit the comparison of the values `a` and `b` of a `_eq` helper, or of
+    /// a `_cmp` one with `order`, returning as soon as they differ.
+    fn write_c_comparison<W: Write>(
+        &self,
+        out: &mut SourceWriter<W>,
+        comparison: &CComparison,
+        (a, b): (&str, &str),
+        order: bool,
+        depth: usize,
+    ) {
+        match *comparison {
+            CComparison::Operator => {
+                write!(out, "if ({a} != {b})");
+                out.open_brace();
+                if order {
+                    write!(out, "return {a} < {b} ? -1 : 1;");
+                } else {
+                    out.write("return false;");
+                }
+                out.close_brace(false);
+            }
+            CComparison::Helper(ref name) if order => {
+                write!(out, "result = {name}_cmp(&{a}, &{b});");
+                out.new_line();
+                out.write("if (result != 0)");
+                out.open_brace();
+                out.write("return result;");
+                out.close_brace(false);
+            }
+            CComparison::Helper(ref name) => {
+                write!(out, "if (!{name}_eq(&{a}, &{b}))");
+                out.open_brace();
+                out.write("return false;");
+                out.close_brace(false);
+            }
+            CComparison::Array(ref element, ref length) => {
+                let i = CComparison::index(depth);
+                write!(out, "for ({i} = 0; {i} < {length}; {i}++)");
+                out.open_brace();
+                self.write_c_comparison(
+                    out,
+                    element,
+                    (&format!("{a}[{i}]"), &format!("{b}[{i}]")),
+                    order,
+                    depth + 1,
+                );
+                out.close_brace(false);
+            }
+        }
+    }
+
+    /// Finds the comparisons of the fields of the `_eq` helper of a struct or
+    /// of an enum with data, or of its `_cmp` one with `order`, `compared`
+    /// being the items which already got one. Fails with the field that can't
+    /// be compared and why.
+    fn c_comparisons<'i>(
+        &self,
+        item: &'i ItemContainer,
+        items: &HashMap<&str, &ItemContainer>,
+        compared: &HashSet<&str>,
+        order: bool,
+    ) -> Result<CComparisons<'i>, (&'i Field, &'static str)> {
+        let check = |fields: DataFields<'i>| {
+            let mut checked = vec![];
+            for (field, member) in fields {
+                match self.c_comparison(&field.ty, items, compared, order) {
+                    Ok(comparison) => checked.push(CComparedField {
+                        field,
+                        member,
+                        comparison,
+                    }),
+                    Err(reason) => return Err((field, reason)),
+                }
+            }
+            Ok(checked)
+        };
+
+        let mut checked = vec![];
+        let mut default = false;
+        match *item {
+            ItemContainer::Struct(ref s) => {
+                checked.push((None, check(data_fields(s, None))?));
+            }
+            ItemContainer::Enum(ref e) => {
+                for (variant, fields) in variant_data_fields(e) {
+                    match fields {
+                        Some(fields) if !fields.is_empty() => {
+                            checked.push((Some(variant), check(fields)?))
+                        }
+                        _ => default = true,
+                    }
+                }
+            }
+            _ => unreachable!(),
+        }
+        Ok(CComparisons {
+            variants: checked,
+            default,
+        })
+    }
+
+    /// Emit the `_eq` helper of a struct or of an enum with data, or its
+    /// `_cmp` one with `order`, comparing the fields one after the other.
+    /// Enums compare their tags first, then the fields of the current variant.
+    fn write_c_comparison_helper<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        item: &ItemContainer,
+        comparisons: &CComparisons,
+        order: bool,
+    ) {
+        let config = self.config;
+        let name = item.deref().export_name();
+        let suffix = if order { "cmp" } else { "eq" };
+        let a = config
+            .function
+            .rename_args
+            .apply("a", IdentifierType::FunctionArg);
+        let b = config
+            .function
+            .rename_args
+            .apply("b", IdentifierType::FunctionArg);
+        let keyword = match *item {
+            ItemContainer::Enum(ref e) if Enum::inline_tag_field(&e.repr) => "union ",
+            _ => "struct ",
+        };
+        let checked = &comparisons.variants;
+
+        let ty = if self.generate_typedef() {
+            name.to_owned()
+        } else {
+            format!("{keyword}{name}")
+        };
+        let static_inline = config.static_inline();
+        write!(
+            out,
+            "{static_inline} {} {name}_{suffix}(const {ty} *{a}, const {ty} *{b})",
+            if order { "int" } else { "bool" }
+        );
+        out.open_brace();
+        let all = || {
+            checked
+                .iter()
+                .flat_map(|(_, fields)| fields.iter().map(|compared| &compared.comparison))
+        };
+        let depth = all().map(|c| c.array_depth()).max().unwrap_or(0);
+        if depth > 0 {
+            let indices: Vec<_> = (0..depth)
+                .map(|depth| CComparison::index(depth).to_string())
+                .collect();
+            write!(out, "size_t {};", indices.join(", "));
+            out.new_line();
+        }
+        if order && all().any(|c| c.uses_helper()) {
+            out.write("int result;");
+            out.new_line();
+        }
+
+        let members = (&*a, &*b);
+        match *item {
+            ItemContainer::Struct(..) => {
+                self.write_c_field_comparisons(out, &checked[0].1, members, order);
+                out.new_line();
+            }
+            _ => {
+                self.write_c_comparison(
+                    out,
+                    &CComparison::Operator,
+                    (&format!("{a}->tag"), &format!("{b}->tag")),
+                    order,
+                    0,
+                );
+                out.new_line();
+                if !checked.is_empty() {
+                    write!(out, "switch ({a}->tag)");
+                    out.open_brace();
+                    for (i, (variant, fields)) in checked.iter().enumerate() {
+                        if i != 0 {
+                            out.new_line();
+                        }
+                        let variant = variant.unwrap();
+                        let condition = variant.cfg.to_condition(config);
+                        condition.write_before(config, out);
+                        write!(out, "case {}:", variant.export_name);
+                        out.push_tab();
+                        out.new_line();
+                        self.write_c_field_comparisons(out, fields, members, order);
+                        out.new_line();
+                        out.write("break;");
+                        out.pop_tab();
+                        condition.write_after(config, out);
+                    }
+                    if comparisons.default {
+                        out.new_line();
+                        out.write("default:");
+                        out.push_tab();
+                        out.new_line();
+                        out.write("break;");
+                        out.pop_tab();
+                    }
+                    out.close_brace(false);
+                    out.new_line();
+                }
+            }
+        }
+        write!(out, "return {};", if order { "0" } else { "true" });
+        out.close_brace(false);
+    }
+
+    /// Emit the comparisons of the fields of a `_eq` or `_cmp` helper, which
+    /// are accessed through their members on the values `a` and `b`.
+    fn write_c_field_comparisons<W: Write>(
+        &self,
+        out: &mut SourceWriter<W>,
+        fields: &[CComparedField],
+        (a, b): (&str, &str),
+        order: bool,
+    ) {
+        for (i, compared) in fields.iter().enumerate() {
+            if i != 0 {
+                out.new_line();
+            }
+            let member = &compared.member;
+            let condition = compared.field.cfg.to_condition(self.config);
+            condition.write_before(self.config, out);
+            self.write_c_comparison(
+                out,
+                &compared.comparison,
+                (&format!("{a}->{member}"), &format!("{b}->{member}")),
+                order,
+                0,
+            );
+            condition.write_after(self.config, out);
+        }
+    }
+
+    /// Emit the `_eq` and `_cmp` helpers of the structs and enums with data
+    /// of C bindings, which don't get comparison operators.
+    fn write_c_comparison_helpers<W: Write>(&mut self, out: &mut SourceWriter<W>, b: &Bindings) {
+        let items: HashMap<&str, &ItemContainer> = b
+            .items
+            .iter()
+            .map(|item| (item.deref().export_name(), item))
+            .collect();
+        // The helpers can't take the name of something the bindings declare.
+        let exported: HashSet<&str> = items
+            .keys()
+            .copied()
+            .chain(b.functions.iter().map(|f| f.path().name()))
+            .chain(b.globals.iter().map(|g| g.export_name()))
+            .chain(b.constants.iter().map(|c| c.export_name()))
+            .collect();
+        let mut diagnostics = vec![];
+        let available = |item: &ItemContainer, helper: &str, diagnostics: &mut Vec<_>| {
+            let name = item.deref().export_name();
+            let helper = format!("{name}_{helper}");
+            let available = !exported.contains(&*helper);
+            if !available {
+                let message = format!(
+                    "Can't derive `{helper}` for `{name}`: an exported item has that name."
+                );
+                diagnostics.push(
+                    Diagnostic::warning(DiagnosticCode::CannotDerive, message)
+                        .with_item(item.deref().name()),
+                );
+            }
+            available
+        };
+        let structure = &self.config.structure;
+        let mut equal = HashSet::new();
+        let mut ordered = HashSet::new();
+        for item in &b.items {
+            let annotations = match *item {
+                ItemContainer::Struct(ref s)
+                    if s.generic_params.is_empty() && s.as_typedef().is_none() =>
+                {
+                    &s.annotations
+                }
+                ItemContainer::Enum(ref e) if e.tag.is_some() && e.generic_params.is_empty() => {
+                    &e.annotations
+                }
+                _ => continue,
+            };
+            if !annotations.should_export() || !structure.derive_c_comparisons(annotations) {
+                continue;
+            }
+            let name = item.deref().export_name();
+            let eq = (structure.derive_eq(annotations) || structure.derive_neq(annotations))
+                && available(item, "eq", &mut diagnostics);
+            let cmp = (structure.derive_lt(annotations)
+                || structure.derive_lte(annotations)
+                || structure.derive_gt(annotations)
+                || structure.derive_gte(annotations))
+                && available(item, "cmp", &mut diagnostics);
+            if !eq && !cmp {
+                continue;
+            }
+
+            let mut comparisons = |compared: &HashSet<&str>, order: bool| {
+                let (field, reason) = match self.c_comparisons(item, &items, compared, order) {
+                    Ok(comparisons) => return Some(comparisons),
+                    Err(unsupported) => unsupported,
+                };
+                let message = format!(
+                    "Can't derive `{name}_{}` for `{name}`: field `{}` {reason}.",
+                    if order { "cmp" } else { "eq" },
+                    field.name
+                );
+                diagnostics.push(
+                    Diagnostic::warning(DiagnosticCode::CannotDerive, message)
+                        .with_item(item.deref().name()),
+                );
+                None
+            };
+            let eq = match eq {
+                true => comparisons(&equal, false),
+                false => None,
+            };
+            let cmp = match cmp {
+                true => comparisons(&ordered, true),
+                false => None,
+            };
+            if eq.is_none() && cmp.is_none() {
+                continue;
+            }
+
+            let condition = item
+                .deref()
+                .cfg()
+                .and_then(|cfg| cfg.to_condition(self.config));
+            out.new_line_if_not_start();
+            condition.write_before(self.config, out);
+            if let Some(ref comparisons) = eq {
+                self.write_c_comparison_helper(out, item, comparisons, false);
+                equal.insert(name);
+            }
+            if let Some(ref comparisons) = cmp {
+                if eq.is_some() {
+                    out.new_line();
+                    out.new_line();
+                }
+                self.write_c_comparison_helper(out, item, comparisons, true);
+                ordered.insert(name);
+            }
+            condition.write_after(self.config, out);
+            out.new_line();
+        }
+        self.diagnostics.extend(diagnostics);
+    }
+
+    /// Emit a `std::hash` specialization combining the hashes of the fields
+    /// of a struct, or of the tag and the fields of the current variant of an
+    /// enum with data.
+    fn write_hash<W: Write>(&mut self, out: &mut SourceWriter<W>, item: &ItemContainer) {
+        let item_ref = item.deref();
+        let name = self.qualified_name(item_ref.export_name());
+        let value = self
+            .config
+            .function
+            .rename_args
+            .apply("value", IdentifierType::FunctionArg);
+
+        let condition = item_ref.cfg().and_then(|cfg| cfg.to_condition(self.config));
+        condition.write_before(self.config, out);
+
+        out.write("template<>");
+        out.new_line();
+        write!(out, "struct std::hash<{name}>");
+        out.open_brace();
+        out.write("template <class T>");
+        out.new_line();
+        out.write("static void combine(size_t& seed, const T& value)");
+        out.open_brace();
+        out.write("seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);");
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+        out.write("template <class T, size_t N>");
+        out.new_line();
+        out.write("static void combine(size_t& seed, const T (&values)[N])");
+        out.open_brace();
+        out.write("for (const T& value : values)");
+        out.open_brace();
+        out.write("combine(seed, value);");
+        out.close_brace(false);
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+        write!(out, "size_t operator()(const {name}& {value}) const");
+        out.open_brace();
+        out.write("size_t seed = 0;");
+
+        let write_fields = |out: &mut SourceWriter<W>, fields: DataFields| {
+            for (field, member) in fields {
+                out.new_line();
+                let condition = field.cfg.to_condition(self.config);
+                condition.write_before(self.config, out);
+                write!(out, "combine(seed, {value}.{member});");
+                condition.write_after(self.config, out);
+            }
+        };
+        match *item {
+            ItemContainer::Struct(ref s) => write_fields(out, data_fields(s, None)),
+            ItemContainer::Enum(ref e) => {
+                out.new_line();
+                write!(out, "combine(seed, {value}.tag);");
+                out.new_line();
+                write!(out, "switch ({value}.tag)");
+                out.open_brace();
+                for (variant, fields) in variant_data_fields(e) {
+                    if let Some(fields) = fields {
+                        let condition = variant.cfg.to_condition(self.config);
+                        condition.write_before(self.config, out);
+                        write!(
+                            out,
+                            "case {name}::{}::{}:",
+                            e.tag_name(),
+                            variant.export_name
+                        );
+                        out.push_tab();
+                        write_fields(out, fields);
+                        out.new_line();
+                        out.write("break;");
+                        out.pop_tab();
+                        condition.write_after(self.config, out);
+                        out.new_line();
+                    }
+                }
+                out.write("default: break;");
+                out.close_brace(false);
+            }
+            _ => unreachable!(),
+        }
+
+        out.new_line();
+        out.write("return seed;");
+        out.close_brace(false);
+        out.close_brace(true);
+
+        condition.write_after(self.config, out);
+    }
+
+    /// Emit a `formatter` specialization of `library` for `ty`, with
+    /// `write_format` writing the body of its `format` function.
+    fn write_formatter<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        library: &str,
+        ty: &str,
+        write_format: impl FnOnce(&mut SourceWriter<W>, &str),
+    ) {
+        let value = self
+            .config
+            .function
+            .rename_args
+            .apply("value", IdentifierType::FunctionArg);
+
+        out.write("template<>");
+        out.new_line();
+        write!(out, "struct {library}::formatter<{ty}>");
+        out.open_brace();
+        write!(
+            out,
+            "constexpr auto parse({library}::format_parse_context& ctx)"
+        );
+        out.open_brace();
+        out.write("return ctx.begin();");
+        out.close_brace(false);
+        out.new_line();
+        out.new_line();
+        out.write("template <class FormatContext>");
+        out.new_line();
+        write!(
+            out,
+            "auto format(const {ty}& {value}, FormatContext& ctx) const"
+        );
+        out.open_brace();
+        write_format(out, &value);
+        out.close_brace(false);
+        out.close_brace(true);
+    }
+
+    /// Emit a formatter writing the name of the variant, like the
+    /// `operator<<` of `derive_ostream`.
+    fn write_enum_formatter<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        library: &str,
+        ty: &str,
+        variants: &[EnumVariant],
+    ) {
+        let config = self.config;
+        self.write_formatter(out, library, ty, |out, value| {
+            write!(out, "switch ({value})");
+            out.open_brace();
+            for (i, variant) in variants.iter().enumerate() {
+                if i != 0 {
+                    out.new_line();
+                }
+                let condition = variant.cfg.to_condition(config);
+                condition.write_before(config, out);
+                write!(
+                    out,
+                    "case {ty}::{0}: return {library}::format_to(ctx.out(), \"{0}\");",
+                    variant.export_name
+                );
+                condition.write_after(config, out);
+            }
+            out.close_brace(false);
+            out.new_line();
+            out.write("return ctx.out();");
+        });
+    }
+
+    /// Emit a formatter writing the fields of a struct, like the
+    /// `operator<<` of `derive_ostream`.
+    fn write_struct_formatter<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        library: &str,
+        ty: &str,
+        fields: &[Field],
+    ) {
+        let config = self.config;
+        self.write_formatter(out, library, ty, |out, value| {
+            out.write("auto out = ctx.out();");
+            out.new_line();
+            write!(out, "out = {library}::format_to(out, \"{{{{ \");");
+            for (i, field) in fields.iter().enumerate() {
+                out.new_line();
+                let condition = field.cfg.to_condition(config);
+                condition.write_before(config, out);
+                let (spec, arg) = formatter_arg(&field.ty, &format!("{value}.{}", field.name));
+                write!(
+                    out,
+                    "out = {library}::format_to(out, \"{}{}={{{spec}}}\", {arg});",
+                    if i == 0 { "" } else { ", " },
+                    field.name,
+                );
+                condition.write_after(config, out);
+            }
+            out.new_line();
+            write!(out, "return {library}::format_to(out, \" }}}}\");");
+        });
+    }
+
+    /// Emit the formatters of an enum with data, which need ones for its
+    /// tag and the bodies of its variants.
+    fn write_data_enum_formatter<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        library: &str,
+        ty: &str,
+        e: &Enum,
+    ) {
+        let tag = format!("{ty}::{}", e.tag_name());
+        self.write_enum_formatter(out, library, &tag, &e.variants);
+        for variant in &e.variants {
+            if let VariantBody::Body { ref body, .. } = variant.body {
+                out.new_line();
+                out.new_line();
+                let condition = variant.cfg.to_condition(self.config);
+                condition.write_before(self.config, out);
+                let body_ty = format!("{ty}::{}", body.export_name());
+                self.write_struct_formatter(out, library, &body_ty, &body.fields);
+                condition.write_after(self.config, out);
+            }
+        }
+
+        out.new_line();
+        out.new_line();
+        let config = self.config;
+        self.write_formatter(out, library, ty, |out, value| {
+            write!(out, "switch ({value}.tag)");
+            out.open_brace();
+            for (i, variant) in e.variants.iter().enumerate() {
+                if i != 0 {
+                    out.new_line();
+                }
+                let condition = variant.cfg.to_condition(config);
+                condition.write_before(config, out);
+                write!(out, "case {tag}::{}: ", variant.export_name);
+                match variant.body {
+                    // The bodies with a tag field write the name of the
+                    // variant themselves.
+                    VariantBody::Body {
+                        ref name, ref body, ..
+                    } => write!(
+                        out,
+                        "return {library}::format_to(ctx.out(), \"{}{{}}\", {value}.{name});",
+                        if body.has_tag_field {
+                            ""
+                        } else {
+                            &variant.export_name
+                        },
+                    ),
+                    VariantBody::Empty(..) => write!(
+                        out,
+                        "return {library}::format_to(ctx.out(), \"{}\");",
+                        variant.export_name
+                    ),
+                }
+                condition.write_after(config, out);
+            }
+            out.close_brace(false);
+            out.new_line();
+            out.write("return ctx.out();");
+        });
+    }
 }
 
 impl LanguageBackend for CLikeLanguageBackend<'_> {
-    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, package_version: &str) {
+    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, b: &Bindings) {
         if self.config.package_version {
-            write!(out, "/* Package version: {package_version} */");
+            write!(out, "/* Package version: {} */", b.package_version);
             out.new_line();
         }
         if let Some(ref f) = self.config.header {
@@ -386,13 +1260,43 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
 
         out.new_line_if_not_start();
 
+        // Conversions and casts can be derived by annotations alone, so look
+        // at the enums that are actually written.
+        let enums = || {
+            b.items.iter().filter_map(|item| match *item {
+                ItemContainer::Enum(ref e) => Some(e),
+                _ => None,
+            })
+        };
+        let to_string = enums().any(|e| e.derives_to_string(self.config));
+        let from_string = enums().any(|e| e.derives_from_string(self.config));
+        let assert = enums().any(|e| e.casts_use_assert(self.config));
+
         if !self.config.no_includes {
             match self.config.language {
                 Language::C => {
                     out.write("#include <stdarg.h>");
                     out.new_line();
-                    out.write("#include <stdbool.h>");
-                    out.new_line();
+                    // `bool` is a keyword since C23, and C89 doesn't have
+                    // `<stdbool.h>`, so fall back to a type of the same size
+                    // unless it's compiled as a later standard.
+                    if self.config.c_standard == CStandard::C89 {
+                        for line in [
+                            "#if defined(__STDC_VERSION__) && __STDC_VERSION__ >= 199901L",
+                            "#include <stdbool.h>",
+                            "#elif !defined(__cplusplus) && !defined(__bool_true_false_are_defined)",
+                            "typedef unsigned char bool;",
+                            "#define true 1",
+                            "#define false 0",
+                            "#endif",
+                        ] {
+                            out.write(line);
+                            out.new_line();
+                        }
+                    } else if !self.config.c_standard_at_least(CStandard::C23) {
+                        out.write("#include <stdbool.h>");
+                        out.new_line();
+                    }
                     if self.config.usize_is_size_t {
                         out.write("#include <stddef.h>");
                         out.new_line();
@@ -401,6 +1305,14 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
                     out.new_line();
                     out.write("#include <stdlib.h>");
                     out.new_line();
+                    if from_string {
+                        out.write("#include <string.h>");
+                        out.new_line();
+                    }
+                    if assert {
+                        out.write("#include <assert.h>");
+                        out.new_line();
+                    }
                 }
                 Language::Cxx => {
                     out.write("#include <cstdarg>");
@@ -417,13 +1329,52 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
                     out.new_line();
                     out.write("#include <new>");
                     out.new_line();
-                    if self.config.enumeration.cast_assert_name.is_none()
+                    if from_string {
+                        out.write("#include <optional>");
+                        out.new_line();
+                    }
+                    if to_string || from_string {
+                        out.write("#include <string_view>");
+                        out.new_line();
+                    }
+                    let config_casts = self.config.enumeration.cast_assert_name.is_none()
                         && (self.config.enumeration.derive_mut_casts
-                            || self.config.enumeration.derive_const_casts)
-                    {
+                            || self.config.enumeration.derive_const_casts);
+                    if assert || config_casts {
                         out.write("#include <cassert>");
                         out.new_line();
                     }
+                    if self.config.cpp_standard_at_least(CppStandard::Cpp20)
+                        && (self.config.structure.derive_lt
+                            || self.config.structure.derive_lte
+                            || self.config.structure.derive_gt
+                            || self.config.structure.derive_gte)
+                    {
+                        out.write("#include <compare>");
+                        out.new_line();
+                    }
+                    let formatters = [
+                        self.config.structure.derive_formatter,
+                        self.config.enumeration.derive_formatter,
+                    ];
+                    if formatters.contains(&Formatter::Std) {
+                        out.write("#include <format>");
+                        out.new_line();
+                    }
+                    if formatters.contains(&Formatter::Fmt) {
+                        out.write("#include <fmt/format.h>");
+                        out.new_line();
+                    }
+                    if self.config.structure.derive_hash || self.config.enumeration.derive_hash {
+                        out.write("#include <functional>");
+                        out.new_line();
+                    }
+                    if self.config.enumeration.derive_visit {
+                        out.write("#include <type_traits>");
+                        out.new_line();
+                        out.write("#include <utility>");
+                        out.new_line();
+                    }
                 }
                 _ => {}
             }
@@ -453,6 +1404,115 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
         self.open_close_namespaces(out, false)
     }
 
+    fn write_specializations<W: Write>(&mut self, out: &mut SourceWriter<W>, b: &Bindings) {
+        if self.config.language == Language::C {
+            self.write_c_comparison_helpers(out, b);
+            return;
+        }
+
+        let items: HashMap<&str, &ItemContainer> = b
+            .items
+            .iter()
+            .map(|item| (item.deref().export_name(), item))
+            .collect();
+        let mut hashed = HashSet::new();
+        for item in &b.items {
+            let fields: Vec<&Field> = match *item {
+                ItemContainer::Struct(ref s)
+                    if self.config.structure.derive_hash(&s.annotations)
+                        && s.generic_params.is_empty()
+                        && s.as_typedef().is_none() =>
+                {
+                    s.fields.iter().collect()
+                }
+                ItemContainer::Enum(ref e)
+                    if e.tag.is_some()
+                        && self.config.enumeration.derive_hash(&e.annotations)
+                        && e.generic_params.is_empty() =>
+                {
+                    e.variants
+                        .iter()
+                        .flat_map(|variant| match variant.body {
+                            VariantBody::Body { ref body, .. } => &body.fields[..],
+                            VariantBody::Empty(..) => &[],
+                        })
+                        .collect()
+                }
+                _ => continue,
+            };
+            let name = item.deref().export_name();
+            if !item.deref().annotations().should_export() {
+                continue;
+            }
+            let unhashable = fields.iter().find_map(|field| {
+                self.can_hash(&field.ty, &items, &hashed)
+                    .err()
+                    .map(|reason| (field, reason))
+            });
+            if let Some((field, reason)) = unhashable {
+                self.diagnostics.push(
+                    Diagnostic::warning(
+                        DiagnosticCode::CannotDerive,
+                        format!(
+                            "Can't derive `std::hash` for `{name}`: field `{}` {reason}.",
+                            field.name
+                        ),
+                    )
+                    .with_item(item.deref().name()),
+                );
+                continue;
+            }
+            hashed.insert(name);
+            out.new_line_if_not_start();
+            self.write_hash(out, item);
+            out.new_line();
+        }
+
+        for item in &b.items {
+            let formatter = match *item {
+                ItemContainer::Struct(ref s)
+                    if s.generic_params.is_empty() && s.as_typedef().is_none() =>
+                {
+                    self.config.structure.derive_formatter(&s.annotations)
+                }
+                ItemContainer::Enum(ref e)
+                    if e.generic_params.is_empty() && !e.is_written_as_constants(self.config) =>
+                {
+                    self.config.enumeration.derive_formatter(&e.annotations)
+                }
+                _ => continue,
+            };
+            let library = match formatter.namespace() {
+                Some(library) => library,
+                None => continue,
+            };
+            if !item.deref().annotations().should_export() {
+                continue;
+            }
+            let ty = self.qualified_name(item.deref().export_name());
+            let condition = item
+                .deref()
+                .cfg()
+                .and_then(|cfg| cfg.to_condition(self.config));
+            out.new_line_if_not_start();
+            condition.write_before(self.config, out);
+            match *item {
+                ItemContainer::Struct(ref s) => {
+                    self.write_struct_formatter(out, library, &ty, &s.fields)
+                }
+                ItemContainer::Enum(ref e) if e.tag.is_some() => {
+                    self.write_data_enum_formatter(out, library, &ty, e)
+                }
+                ItemContainer::Enum(ref e) => {
+                    self.write_enum_formatter(out, library, &ty, &e.variants)
+                }
+                _ => unreachable!(),
+            }
+            condition.write_after(self.config, out);
+            out.new_line();
+        }
+    }
+
     fn write_footers<W: Write>(&mut self, out: &mut SourceWriter<W>) {
         if let Some(f) = self.config.include_guard() {
             out.new_line_if_not_start();
@@ -466,11 +1526,36 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
     }
 
     fn write_enum<W: Write>(&mut self, out: &mut SourceWriter<W>, e: &Enum) {
+        if e.is_written_as_constants(self.config) {
+            e.write_as_constants(self.config, self, out);
+            return;
+        }
+
         let size = e.repr.ty.map(|ty| ty.to_primitive().to_repr_c(self.config));
         let has_data = e.tag.is_some();
         let inline_tag_field = Enum::inline_tag_field(&e.repr);
         let tag_name = e.tag_name();
 
+        // The data of enums with data is in anonymous structs and unions,
+        // which C89 doesn't have.
+        if has_data
+            && self.config.language == Language::C
+            && self.config.c_standard == CStandard::C89
+        {
+            self.diagnostics.push(
+                Diagnostic::error(
+                    DiagnosticCode::UnsupportedByStandard,
+                    format!(
+                        "Can't write enum `{}` with data for C89, which doesn't have anonymous \
+                         structs and unions.",
+                        e.export_name
+                    ),
+                )
+                .with_item(e.path.name())
+                .with_help("Use a `c_standard` of \"c99\" or later, or a C++ `language`."),
+            );
+        }
+
         let condition = e.cfg.to_condition(self.config);
         condition.write_before(self.config, out);
 
@@ -534,6 +1619,9 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
             } else {
                 out.close_brace(true);
             }
+
+            // Emit the C functions for the struct or union for the data.
+            e.write_c_helper_functions(self.config, self, out, Self::write_field);
         }
 
         condition.write_after(self.config, out);
@@ -578,7 +1666,9 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
         }
 
         if s.annotations.must_use(self.config) {
-            if let Some(ref anno) = self.config.structure.must_use {
+            if let Some(anno) = (self.config.structure.must_use.as_deref())
+                .or_else(|| self.config.c23_attribute("[[nodiscard]]"))
+            {
                 write!(out, " {anno}");
             }
         }
@@ -978,7 +2068,7 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
             }
 
             if b.config.cpp_compatible_c() {
-                out.write("#endif // __cplusplus");
+                write!(out, "#endif {}", b.config.line_comment("__cplusplus"));
                 out.new_line();
             }
 
@@ -1004,7 +2094,7 @@ impl LanguageBackend for CLikeLanguageBackend<'_> {
             }
 
             if b.config.cpp_compatible_c() {
-                out.write("#endif  // __cplusplus");
+                write!(out, "#endif  {}", b.config.line_comment("__cplusplus"));
                 out.new_line();
             }
         }
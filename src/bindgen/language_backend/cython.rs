@@ -17,7 +17,12 @@ impl<'a> CythonLanguageBackend<'a> {
         Self { config }
     }
 
-    fn write_enum_variant<W: Write>(&mut self, out: &mut SourceWriter<W>, u: &EnumVariant) {
+    fn write_enum_variant<W: Write>(
+        &mut self,
+        out: &mut SourceWriter<W>,
+        u: &EnumVariant,
+        comma: bool,
+    ) {
         self.write_documentation(out, &u.documentation);
         write!(out, "{}", u.export_name);
         if let Some(discriminant) = &u.discriminant {
@@ -26,7 +31,9 @@ impl<'a> CythonLanguageBackend<'a> {
             out.write(" # = ");
             self.write_literal(out, discriminant);
         }
-        out.write(",");
+        if comma {
+            out.write(",");
+        }
     }
 
     fn write_field<W: Write>(&mut self, out: &mut SourceWriter<W>, f: &Field) {
@@ -42,9 +49,9 @@ impl<'a> CythonLanguageBackend<'a> {
 }
 
 impl LanguageBackend for CythonLanguageBackend<'_> {
-    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, package_version: &str) {
+    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, b: &Bindings) {
         if self.config.package_version {
-            write!(out, "''' Package version: {package_version} '''");
+            write!(out, "''' Package version: {} '''", b.package_version);
             out.new_line();
         }
         if let Some(ref f) = self.config.header {
@@ -118,6 +125,11 @@ impl LanguageBackend for CythonLanguageBackend<'_> {
     fn write_footers<W: Write>(&mut self, _out: &mut SourceWriter<W>) {}
 
     fn write_enum<W: Write>(&mut self, out: &mut SourceWriter<W>, e: &Enum) {
+        if e.is_written_as_constants(self.config) {
+            e.write_as_constants(self.config, self, out);
+            return;
+        }
+
         let size = e.repr.ty.map(|ty| ty.to_primitive().to_repr_c(self.config));
         let has_data = e.tag.is_some();
         let inline_tag_field = Enum::inline_tag_field(&e.repr);
@@ -268,7 +280,7 @@ impl LanguageBackend for CythonLanguageBackend<'_> {
         write!(
             out,
             "{}struct {}",
-            &self.config.style.cython_def(),
+            self.config.style.cython_def(),
             o.export_name()
         );
         out.open_brace();
@@ -284,7 +296,7 @@ impl LanguageBackend for CythonLanguageBackend<'_> {
 
         self.write_documentation(out, &t.documentation);
 
-        write!(out, "{} ", &self.config.language.typedef());
+        write!(out, "{} ", self.config.language.typedef());
 
         self.write_field(
             out,
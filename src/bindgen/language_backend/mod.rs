@@ -17,7 +17,7 @@ pub use cython::CythonLanguageBackend;
 pub trait LanguageBackend: Sized {
     fn open_namespaces<W: Write>(&mut self, out: &mut SourceWriter<W>);
     fn close_namespaces<W: Write>(&mut self, out: &mut SourceWriter<W>);
-    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, package_version: &str);
+    fn write_headers<W: Write>(&self, out: &mut SourceWriter<W>, b: &Bindings);
     fn write_footers<W: Write>(&mut self, out: &mut SourceWriter<W>);
     fn write_enum<W: Write>(&mut self, out: &mut SourceWriter<W>, e: &Enum);
     fn write_struct<W: Write>(&mut self, out: &mut SourceWriter<W>, s: &Struct);
@@ -79,7 +79,9 @@ pub trait LanguageBackend: Sized {
                 write_space(layout, out);
             }
             if func.annotations.must_use(config) {
-                if let Some(ref anno) = config.function.must_use {
+                if let Some(anno) = (config.function.must_use.as_deref())
+                    .or_else(|| config.c23_attribute("[[nodiscard]]"))
+                {
                     write!(out, "{anno}");
                     write_space(layout, out);
                 }
@@ -91,6 +93,13 @@ pub trait LanguageBackend: Sized {
                 write!(out, "{note}");
                 write_space(layout, out);
             }
+            // Before C23 `no_return` is written after the declaration, see `cdecl`.
+            if func.never_return && config.function.no_return.is_none() {
+                if let Some(anno) = config.c23_attribute("[[noreturn]]") {
+                    write!(out, "{anno}");
+                    write_space(layout, out);
+                }
+            }
         }
         cdecl::write_func(self, out, func, layout, config);
 
@@ -117,7 +126,7 @@ pub trait LanguageBackend: Sized {
     fn write_literal<W: Write>(&mut self, out: &mut SourceWriter<W>, l: &Literal);
 
     fn write_bindings<W: Write>(&mut self, out: &mut SourceWriter<W>, b: &Bindings) {
-        self.write_headers(out, &b.package_version);
+        self.write_headers(out, b);
         self.open_namespaces(out);
         self.write_primitive_constants(out, b);
         self.write_items(out, b);
@@ -125,10 +134,16 @@ pub trait LanguageBackend: Sized {
         self.write_globals(out, b);
         self.write_functions(out, b);
         self.close_namespaces(out);
+        self.write_specializations(out, b);
         self.write_footers(out);
         self.write_trailer(out, b);
     }
 
+    /// Writes the code derived from the items which has to come after all of
+    /// them, like the specializations of templates, which have to be outside
+    /// of the namespaces of the bindings.
+    fn write_specializations<W: Write>(&mut self, _out: &mut SourceWriter<W>, _b: &Bindings) {}
+
     fn write_primitive_constants<W: Write>(&mut self, out: &mut SourceWriter<W>, b: &Bindings) {
         for constant in &b.constants {
             if constant.uses_only_primitive_types() {
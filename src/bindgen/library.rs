@@ -2,17 +2,24 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
 
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::path::PathBuf;
 
 use crate::bindgen::bindings::Bindings;
-use crate::bindgen::config::{Config, Language, SortKey};
+use crate::bindgen::config::{Config, Language, SortKey, TypeMapping};
 use crate::bindgen::declarationtyperesolver::DeclarationTypeResolver;
-use crate::bindgen::dependencies::Dependencies;
+use crate::bindgen::dependencies::{Dependencies, DependencyNode};
+use crate::bindgen::diagnostic::{Diagnostic, DiagnosticCode};
 use crate::bindgen::error::Error;
+use crate::bindgen::ir::{AnnotationSet, AnnotationTarget, Documentation, VariantBody};
 use crate::bindgen::ir::{Constant, Enum, Function, Item, ItemContainer, ItemMap};
+use crate::bindgen::ir::{GenericArgument, GenericParams, GenericPath, Type};
 use crate::bindgen::ir::{OpaqueItem, Path, Static, Struct, Typedef, Union};
+use crate::bindgen::mangle;
 use crate::bindgen::monomorph::Monomorphs;
+use crate::bindgen::pattern::NamePatterns;
+use crate::bindgen::plugin;
+use crate::bindgen::provenance::Provenance;
 use crate::bindgen::ItemType;
 
 #[derive(Debug, Clone)]
@@ -28,6 +35,10 @@ pub struct Library {
     functions: Vec<Function>,
     source_files: Vec<PathBuf>,
     package_version: String,
+    diagnostics: Vec<Diagnostic>,
+    provenance: Provenance,
+    /// The parsed keys of `export.monomorph_names`, with the names they give.
+    monomorph_names: Vec<(GenericPath, Path)>,
 }
 
 impl Library {
@@ -44,7 +55,16 @@ impl Library {
         functions: Vec<Function>,
         source_files: Vec<PathBuf>,
         package_version: String,
+        diagnostics: Vec<Diagnostic>,
+        provenance: Provenance,
     ) -> Library {
+        // Invalid keys are reported by `Config::validate`.
+        let monomorph_names = config
+            .export
+            .monomorph_names
+            .iter()
+            .filter_map(|(ty, name)| Some((GenericPath::parse(ty, &config).ok()?, Path::new(name))))
+            .collect();
         Library {
             config,
             constants,
@@ -57,22 +77,36 @@ impl Library {
             functions,
             source_files,
             package_version,
+            diagnostics,
+            provenance,
+            monomorph_names,
         }
     }
 
     pub fn generate(mut self) -> Result<Bindings, Error> {
+        let include = NamePatterns::new("export.include", &self.config.export.include)?;
+        let exclude = NamePatterns::new("export.exclude", &self.config.export.exclude)?;
+        let opaque = NamePatterns::new("export.opaque", &self.config.export.opaque)?;
+
+        self.validate_annotations();
+        self.check_diagnostics()?;
         self.transfer_annotations();
         self.simplify_standard_types();
+        self.make_opaque(&opaque);
 
         match self.config.function.sort_by.unwrap_or(self.config.sort_by) {
             SortKey::Name => self.functions.sort_by(|x, y| x.path.cmp(&y.path)),
             SortKey::None => { /* keep input order */ }
         }
 
-        if self.config.language != Language::Cxx {
-            self.instantiate_monomorphs();
-        }
-        self.remove_excluded();
+        let instantiations = self.requested_instantiations();
+        self.check_diagnostics()?;
+        let instantiated = if self.config.language != Language::Cxx {
+            self.instantiate_monomorphs(&instantiations)
+        } else {
+            self.add_instantiation_aliases(&instantiations)
+        };
+        self.remove_excluded(&exclude);
         if self.config.language == Language::C {
             self.resolve_declaration_types();
         }
@@ -82,21 +116,38 @@ impl Library {
         let mut dependencies = Dependencies::new();
 
         for function in &self.functions {
+            dependencies.enter(DependencyNode::Function(function.path.clone()));
             function.add_dependencies(&self, &mut dependencies);
+            dependencies.exit();
         }
         self.globals.for_all_items(|global| {
+            dependencies.enter(DependencyNode::Global(global.path.clone()));
             global.add_dependencies(&self, &mut dependencies);
+            dependencies.exit();
         });
         self.constants.for_all_items(|constant| {
+            dependencies.enter(DependencyNode::Constant(constant.path.clone()));
             constant.add_dependencies(&self, &mut dependencies);
+            dependencies.exit();
         });
-        for name in &self.config.export.include {
-            let path = Path::new(name.clone());
+        let included_paths = self.included_paths(&include);
+        let included = included_paths
+            .into_iter()
+            .map(|path| (DependencyNode::ExportInclude, path));
+        let instantiated = instantiated
+            .into_iter()
+            .map(|path| (DependencyNode::ExportInstantiate, path));
+        for (node, path) in included.chain(instantiated) {
             if let Some(items) = self.get_items(&path) {
-                if dependencies.items.insert(path) {
+                dependencies.enter(node);
+                dependencies.add_edge(&path);
+                dependencies.exit();
+                if dependencies.items.insert(path.clone()) {
+                    dependencies.enter(DependencyNode::Item(path));
                     for item in &items {
                         item.deref().add_dependencies(&self, &mut dependencies);
                     }
+                    dependencies.exit();
                     for item in items {
                         dependencies.order.push(item);
                     }
@@ -105,7 +156,9 @@ impl Library {
         }
 
         dependencies.sort();
+        self.add_type_map_includes(&dependencies.mapped_types);
 
+        self.provenance.edges = dependencies.edges.into_iter().collect();
         let items = dependencies.order;
         let constants = if self.config.export.should_generate(ItemType::Constants) {
             let mut constants = self.constants.to_vec();
@@ -134,7 +187,7 @@ impl Library {
             vec![]
         };
 
-        Ok(Bindings::new(
+        let mut bindings = Bindings::new(
             self.config,
             self.structs,
             self.typedefs,
@@ -145,7 +198,11 @@ impl Library {
             self.source_files,
             false,
             self.package_version,
-        ))
+            self.diagnostics,
+            self.provenance,
+        );
+        plugin::run_plugins(&mut bindings)?;
+        Ok(bindings)
     }
 
     pub fn get_items(&self, p: &Path) -> Option<Vec<ItemContainer>> {
@@ -172,25 +229,181 @@ impl Library {
         &self.config
     }
 
-    fn remove_excluded(&mut self) {
+    /// The path of the instantiation of `path` with `generic_values`: the name
+    /// `export.monomorph_names` gives it, or its mangled name.
+    pub fn monomorph_path(&self, path: &Path, generic_values: &[GenericArgument]) -> Path {
+        self.monomorph_names
+            .iter()
+            .find(|(generic, _)| generic.path() == path && generic.generics() == generic_values)
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| {
+                mangle::mangle_path(path, generic_values, &self.config.export.mangle)
+            })
+    }
+
+    /// The paths of the items selected by the `export.include` patterns, in the
+    /// order of the patterns.
+    fn included_paths(&self, include: &NamePatterns) -> Vec<Path> {
+        let modules = self.provenance.modules_by_name();
+        let mut all_paths = vec![];
+        let mut paths = vec![];
+        for pattern in include.iter() {
+            if let Some(name) = pattern.exact_name() {
+                paths.push(Path::new(name));
+                continue;
+            }
+            if all_paths.is_empty() {
+                let mut push = |x: &dyn Item| all_paths.push(x.path().clone());
+                self.enums.for_all_items(|x| push(x));
+                self.structs.for_all_items(|x| push(x));
+                self.unions.for_all_items(|x| push(x));
+                self.opaque_items.for_all_items(|x| push(x));
+                self.typedefs.for_all_items(|x| push(x));
+            }
+            for path in &all_paths {
+                let modules = modules.get(path.name()).map_or(&[][..], Vec::as_slice);
+                if pattern.matches(path.name(), modules) && !paths.contains(path) {
+                    paths.push(path.clone());
+                }
+            }
+        }
+        paths
+    }
+
+    fn remove_excluded(&mut self, exclude: &NamePatterns) {
+        let modules = self.provenance.modules_by_name();
         let config = &self.config;
-        // FIXME: interpret `config.export.exclude` as `Path`s.
-        self.functions
-            .retain(|x| !config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.enums
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.structs
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.unions
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.opaque_items
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.typedefs
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.globals
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
-        self.constants
-            .filter(|x| config.export.exclude.iter().any(|y| y == x.path().name()));
+        let is_excluded = |path: &Path| {
+            let modules = modules.get(path.name()).map_or(&[][..], Vec::as_slice);
+            exclude.matches(path.name(), modules)
+        };
+        // Types of `export.type_map` are written as the foreign type they map
+        // to, so they must not be defined.
+        let is_excluded_type = |path: &Path| {
+            is_excluded(path)
+                || config
+                    .export
+                    .mapped_type(path.name(), config.language)
+                    .is_some()
+        };
+        self.functions.retain(|x| !is_excluded(x.path()));
+        self.enums.filter(|x| is_excluded_type(x.path()));
+        self.structs.filter(|x| is_excluded_type(x.path()));
+        self.unions.filter(|x| is_excluded_type(x.path()));
+        self.opaque_items.filter(|x| is_excluded_type(x.path()));
+        self.typedefs.filter(|x| is_excluded_type(x.path()));
+        self.globals.filter(|x| is_excluded(x.path()));
+        self.constants.filter(|x| is_excluded(x.path()));
+    }
+
+    /// Turns the structs, unions and enums selected by `export.opaque` or
+    /// annotated with `cbindgen:opaque` into opaque items, so that only a
+    /// forward declaration of them is written and their fields don't pull
+    /// other types in.
+    fn make_opaque(&mut self, opaque: &NamePatterns) {
+        let modules = self.provenance.modules_by_name();
+        let is_opaque = |item: &dyn Item| {
+            let modules = modules.get(item.name()).map_or(&[][..], Vec::as_slice);
+            item.annotations().bool("opaque").unwrap_or(false)
+                || opaque.matches(item.name(), modules)
+        };
+
+        let mut opaque_items = vec![];
+        let mut make_opaque = |item: &dyn Item| {
+            if is_opaque(item) {
+                opaque_items.push(OpaqueItem::new(
+                    item.path().clone(),
+                    item.generic_params().clone(),
+                    item.cfg().cloned(),
+                    item.annotations().clone(),
+                    item.documentation().clone(),
+                ));
+            }
+        };
+        self.structs.for_all_items(|x| make_opaque(x));
+        self.unions.for_all_items(|x| make_opaque(x));
+        self.enums.for_all_items(|x| make_opaque(x));
+
+        self.structs.filter(|x| is_opaque(x));
+        self.unions.filter(|x| is_opaque(x));
+        self.enums.filter(|x| is_opaque(x));
+        for item in opaque_items {
+            self.opaque_items.try_insert(item);
+        }
+    }
+
+    fn validate_annotations(&mut self) {
+        let mut diagnostics = vec![];
+
+        self.structs.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Struct, x.path.name(), &mut diagnostics);
+            for field in &x.fields {
+                let name = format!("{}::{}", x.path, field.name);
+                field
+                    .annotations
+                    .validate(AnnotationTarget::Field, &name, &mut diagnostics);
+            }
+        });
+        self.unions.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Union, x.path.name(), &mut diagnostics);
+            for field in &x.fields {
+                let name = format!("{}::{}", x.path, field.name);
+                field
+                    .annotations
+                    .validate(AnnotationTarget::Field, &name, &mut diagnostics);
+            }
+        });
+        self.enums.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Enum, x.path.name(), &mut diagnostics);
+            for variant in &x.variants {
+                let name = format!("{}::{}", x.path, variant.name);
+                variant.body.annotations().validate(
+                    AnnotationTarget::Variant,
+                    &name,
+                    &mut diagnostics,
+                );
+                if let VariantBody::Body { ref body, .. } = variant.body {
+                    for field in body.fields.iter().skip(body.has_tag_field as usize) {
+                        let name = format!("{name}::{}", field.name);
+                        field.annotations.validate(
+                            AnnotationTarget::Field,
+                            &name,
+                            &mut diagnostics,
+                        );
+                    }
+                }
+            }
+        });
+        self.typedefs.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Typedef, x.path.name(), &mut diagnostics);
+        });
+        self.globals.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Static, x.path.name(), &mut diagnostics);
+        });
+        self.constants.for_all_items(|x| {
+            x.annotations
+                .validate(AnnotationTarget::Constant, x.path.name(), &mut diagnostics);
+        });
+        for x in &self.functions {
+            x.annotations
+                .validate(AnnotationTarget::Function, x.path.name(), &mut diagnostics);
+        }
+
+        self.diagnostics.extend(diagnostics);
+    }
+
+    /// Fails if any of the diagnostics collected so far should abort the run.
+    fn check_diagnostics(&self) -> Result<(), Error> {
+        let strict = self.config.parse.strict;
+        if self.diagnostics.iter().any(|x| strict || x.code.is_fatal()) {
+            return Err(Error::Diagnostics(self.diagnostics.clone()));
+        }
+        Ok(())
     }
 
     fn transfer_annotations(&mut self) {
@@ -324,8 +537,14 @@ impl Library {
             x.collect_declaration_types(&mut resolver);
         });
 
+        let config = &self.config;
         self.enums.for_all_items(|x| {
-            x.collect_declaration_types(&mut resolver);
+            if x.is_written_as_constants(config) {
+                // It's a typedef, so it's never written as `enum Foo`.
+                resolver.add_none(x.path());
+            } else {
+                x.collect_declaration_types(&mut resolver);
+            }
         });
 
         self.unions.for_all_items(|x| {
@@ -380,15 +599,101 @@ impl Library {
         self.typedefs.for_all_items_mut(|x| {
             x.simplify_standard_types(config);
         });
+        self.constants.for_all_items_mut(|x| {
+            x.simplify_standard_types(config);
+        });
         for x in &mut self.functions {
             x.simplify_standard_types(config);
         }
     }
 
-    fn instantiate_monomorphs(&mut self) {
+    /// Adds the headers the types of `export.type_map` in `used` need.
+    fn add_type_map_includes(&mut self, used: &HashSet<Path>) {
+        let mut includes = BTreeSet::new();
+        let mut sys_includes = BTreeSet::new();
+        for (name, _, mapping) in self.config.export.type_mappings(self.config.language) {
+            if !used.iter().any(|path| path.name() == name) {
+                continue;
+            }
+            if let TypeMapping::Table(ref table) = *mapping {
+                includes.extend(table.includes.iter().cloned());
+                sys_includes.extend(table.sys_includes.iter().cloned());
+            }
+        }
+        for include in includes {
+            if !self.config.includes.contains(&include) {
+                self.config.includes.push(include);
+            }
+        }
+        for include in sys_includes {
+            if !self.config.sys_includes.contains(&include) {
+                self.config.sys_includes.push(include);
+            }
+        }
+    }
+
+    /// The instantiations of generic types requested by `export.instantiate`
+    /// and `cbindgen:instantiate` annotations.
+    fn requested_instantiations(&mut self) -> Vec<GenericPath> {
+        let mut types = vec![];
+        for (name, arguments) in &self.config.export.instantiate {
+            types.extend(arguments.iter().map(|x| format!("{name}<{x}>")));
+        }
+        let mut push = |x: &dyn Item| {
+            if let Some(list) = x.annotations().list("instantiate") {
+                types.extend(split_generic_list(&list));
+            }
+        };
+        self.structs.for_all_items(|x| push(x));
+        self.unions.for_all_items(|x| push(x));
+        self.enums.for_all_items(|x| push(x));
+        self.typedefs.for_all_items(|x| push(x));
+
+        let mut instantiations = vec![];
+        for ty in types {
+            match GenericPath::parse(&ty, &self.config) {
+                Ok(path) if !instantiations.contains(&path) => instantiations.push(path),
+                Ok(_) => {}
+                Err(e) => self.diagnostics.push(Diagnostic::warning(
+                    DiagnosticCode::InvalidInstantiation,
+                    format!("Can't instantiate `{ty}`: {e}."),
+                )),
+            }
+        }
+        instantiations
+    }
+
+    /// Writes each requested instantiation as a `using` alias of the template,
+    /// since C++ doesn't need monomorphs. Returns the paths of the aliases.
+    fn add_instantiation_aliases(&mut self, instantiations: &[GenericPath]) -> Vec<Path> {
+        let mut paths = vec![];
+        for generic in instantiations {
+            let path = self.monomorph_path(generic.path(), generic.generics());
+            let alias = Typedef::new(
+                path.clone(),
+                GenericParams::default(),
+                Type::Path(generic.clone()),
+                None,
+                AnnotationSet::new(),
+                Documentation::none(),
+            );
+            if self.typedefs.try_insert(alias) {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    /// Instantiates the generic types used by the bindings, along with the
+    /// requested `instantiations`, and returns the paths of the latter.
+    fn instantiate_monomorphs(&mut self, instantiations: &[GenericPath]) -> Vec<Path> {
         // Collect a list of monomorphs
         let mut monomorphs = Monomorphs::default();
 
+        for generic in instantiations {
+            Type::Path(generic.clone()).add_monomorphs(self, &mut monomorphs);
+        }
+
         self.structs.for_all_items(|x| {
             x.add_monomorphs(self, &mut monomorphs);
         });
@@ -441,5 +746,33 @@ impl Library {
         for x in &mut self.functions {
             x.mangle_paths(&monomorphs);
         }
+
+        instantiations
+            .iter()
+            .filter_map(|generic| monomorphs.mangle_path(generic).cloned())
+            .collect()
+    }
+}
+
+/// Splits a list of generic types, which the annotation parser also splits at
+/// the commas between generic arguments, into the types.
+fn split_generic_list(list: &[String]) -> Vec<String> {
+    let mut types = vec![];
+    let mut current = String::new();
+    let mut depth = 0usize;
+    for part in list {
+        if !current.is_empty() {
+            current.push_str(", ");
+        }
+        current.push_str(part);
+        depth += part.matches('<').count();
+        depth = depth.saturating_sub(part.matches('>').count());
+        if depth == 0 {
+            types.push(std::mem::take(&mut current));
+        }
+    }
+    if !current.is_empty() {
+        types.push(current);
     }
+    types
 }
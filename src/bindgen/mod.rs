@@ -36,6 +36,61 @@ macro_rules! deserialize_enum_str {
     };
 }
 
+/// A helper macro for enums written as strings in the config. Each variant
+/// lists the strings it can be written as, the first one being what it
+/// serializes to (which is how defaults end up in the schema). Parsing, the
+/// JSON schema and serialization are all generated from that one table, so
+/// they can't disagree. `ignore_case` also accepts the strings in any case.
+macro_rules! config_enum_str {
+    (@eq $s:ident, $value:ident) => {
+        $s == $value
+    };
+    (@eq ignore_case $s:ident, $value:ident) => {
+        $s.eq_ignore_ascii_case($value)
+    };
+    ($name:ident, $what:literal $(, $case:ident)? { $($variant:ident => [$value:literal $(, $alias:literal)*]),+ $(,)? }) => {
+        impl ::std::str::FromStr for $name {
+            type Err = String;
+
+            fn from_str(s: &str) -> Result<$name, Self::Err> {
+                let matches = |value: &str| config_enum_str!(@eq $($case)? s, value);
+                $(
+                    if matches($value) $(|| matches($alias))* {
+                        return Ok($name::$variant);
+                    }
+                )+
+                Err(format!("Unrecognized {}: '{s}'.", $what))
+            }
+        }
+
+        deserialize_enum_str!($name);
+
+        impl ::serde::Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: ::serde::Serializer,
+            {
+                serializer.serialize_str(match *self {
+                    $($name::$variant => $value,)+
+                })
+            }
+        }
+
+        impl ::schemars::JsonSchema for $name {
+            fn schema_name() -> ::std::borrow::Cow<'static, str> {
+                stringify!($name).into()
+            }
+
+            fn json_schema(_: &mut ::schemars::SchemaGenerator) -> ::schemars::Schema {
+                ::schemars::json_schema!({
+                    "type": "string",
+                    "enum": [$($value, $($alias,)*)+],
+                })
+            }
+        }
+    };
+}
+
 mod bindings;
 mod bitflags;
 mod builder;
@@ -44,6 +99,7 @@ mod cdecl;
 mod config;
 mod declarationtyperesolver;
 mod dependencies;
+mod diagnostic;
 mod error;
 #[cfg(feature = "unstable_ir")]
 pub mod ir;
@@ -54,8 +110,12 @@ mod library;
 mod mangle;
 mod monomorph;
 mod parser;
+mod pattern;
+mod plugin;
+mod provenance;
 mod rename;
 mod reserved;
+mod transform;
 mod utilities;
 mod writer;
 
@@ -66,4 +126,11 @@ pub use self::bindings::Bindings;
 pub use self::builder::Builder;
 pub use self::config::Profile; // disambiguate with cargo::Profile
 pub use self::config::*;
+#[allow(unused)]
+pub use self::diagnostic::{
+    Diagnostic, DiagnosticCode, DiagnosticLevel, SourceLocation, DIAGNOSTIC_LOG_TARGET,
+};
 pub use self::error::Error;
+pub use self::provenance::DependencyGraphFormat;
+#[allow(unused)]
+pub use self::transform::{IrItem, IrView};
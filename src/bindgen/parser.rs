@@ -9,15 +9,19 @@ use std::io::Read;
 use std::path::{Path as FilePath, PathBuf as FilePathBuf};
 
 use syn::ext::IdentExt;
+use syn::spanned::Spanned;
 
 use crate::bindgen::bitflags;
 use crate::bindgen::cargo::{Cargo, PackageRef};
-use crate::bindgen::config::{Config, ParseConfig};
+use crate::bindgen::config::{Config, ItemType, ParseConfig};
+use crate::bindgen::diagnostic::{Diagnostic, DiagnosticCode, SourceLocation};
 use crate::bindgen::error::Error;
 use crate::bindgen::ir::{
     AnnotationSet, AnnotationValue, Cfg, Constant, Documentation, Enum, Function, GenericParam,
     GenericParams, ItemMap, OpaqueItem, Path, Static, Struct, Type, Typedef, Union,
 };
+use crate::bindgen::pattern::NamePatterns;
+use crate::bindgen::provenance::{ItemSource, Provenance};
 use crate::bindgen::utilities::{SynAbiHelpers, SynAttributeHelpers, SynItemHelpers};
 
 const STD_CRATES: &[&str] = &[
@@ -48,6 +52,9 @@ pub fn parse_src(src_file: &FilePath, config: &Config) -> ParseResult {
         cache_src: HashMap::new(),
         cache_expanded_crate: HashMap::new(),
         cfg_stack: Vec::new(),
+        mod_stack: Vec::new(),
+        parse_include: None,
+        parse_exclude: NamePatterns::default(),
         out: Parse::new(),
     };
 
@@ -67,6 +74,12 @@ pub fn parse_src(src_file: &FilePath, config: &Config) -> ParseResult {
 /// and parsed. To find an external crate, the parser uses the `cargo metadata`
 /// command to find the location of dependencies.
 pub(crate) fn parse_lib(lib: Cargo, config: &Config) -> ParseResult {
+    let parse_include = match config.parse.include {
+        Some(ref include) => Some(NamePatterns::new("parse.include", include)?),
+        None => None,
+    };
+    let parse_exclude = NamePatterns::new("parse.exclude", &config.parse.exclude)?;
+
     let mut context = Parser {
         binding_crate_name: lib.binding_crate_name().to_owned(),
         config,
@@ -75,6 +88,9 @@ pub(crate) fn parse_lib(lib: Cargo, config: &Config) -> ParseResult {
         cache_src: HashMap::new(),
         cache_expanded_crate: HashMap::new(),
         cfg_stack: Vec::new(),
+        mod_stack: Vec::new(),
+        parse_include,
+        parse_exclude,
         out: Parse::new(),
     };
 
@@ -102,6 +118,11 @@ struct Parser<'a> {
     cache_expanded_crate: HashMap<String, Vec<syn::Item>>,
 
     cfg_stack: Vec<Cfg>,
+    /// The path of the module being parsed, relative to its crate.
+    mod_stack: Vec<String>,
+
+    parse_include: Option<NamePatterns>,
+    parse_exclude: NamePatterns,
 
     out: Parse,
 }
@@ -129,21 +150,15 @@ impl Parser<'_> {
         }
 
         // If we have a whitelist, check it
-        if let Some(ref include) = self.config.parse.include {
-            if !include.iter().any(|name| name == pkg_name) {
+        if let Some(ref include) = self.parse_include {
+            if !include.matches(pkg_name, &[]) {
                 debug!("Excluding crate {pkg_name}");
                 return false;
             }
         }
 
         // Check the blacklist
-        !STD_CRATES.contains(&pkg_name)
-            && !self
-                .config
-                .parse
-                .exclude
-                .iter()
-                .any(|name| name == pkg_name)
+        !STD_CRATES.contains(&pkg_name) && !self.parse_exclude.matches(pkg_name, &[])
     }
 
     fn parse_crate(&mut self, pkg: &PackageRef) -> Result<(), Error> {
@@ -164,11 +179,17 @@ impl Parser<'_> {
                 Some(crate_src) => self.parse_mod(pkg, crate_src.as_path(), 0)?,
                 None => {
                     // This should be an error, but is common enough to just elicit a warning
-                    warn!(
-                        "Parsing crate `{}`: can't find lib.rs with `cargo metadata`. \
-                        The crate may be available only on a particular platform, \
-                        so consider setting `fetch_all_dependencies` in your cbindgen configuration.",
-                        pkg.name
+                    self.out.report(
+                        Diagnostic::warning(
+                            DiagnosticCode::MissingCrateSource,
+                            format!(
+                                "Parsing crate `{}`: can't find lib.rs with `cargo metadata`. \
+                                The crate may be available only on a particular platform, \
+                                so consider setting `fetch_all_dependencies` in your cbindgen configuration.",
+                                pkg.name
+                            ),
+                        )
+                        .with_item(pkg.name.clone()),
                     );
                 }
             }
@@ -222,7 +243,7 @@ impl Parser<'_> {
         };
 
         self.process_mod(
-            pkg, None, None, &mod_items, 0, /* is_mod_rs = */ true,
+            pkg, None, None, None, &mod_items, 0, /* is_mod_rs = */ true,
             /* is_inline = */ false,
         )
     }
@@ -276,6 +297,7 @@ impl Parser<'_> {
 
         self.process_mod(
             pkg,
+            Some(mod_path),
             Some(mod_dir),
             Some(submod_dir),
             &mod_items,
@@ -285,8 +307,9 @@ impl Parser<'_> {
         )
     }
 
-    /// `mod_dir` is the path to the current directory of the module. It may be
-    /// `None` for pre-expanded modules.
+    /// `src_file` is the file the items come from, and `mod_dir` is the path to
+    /// the current directory of the module. They may be `None` for pre-expanded
+    /// modules.
     ///
     /// `submod_dir` is the path to search submodules in by default, which might
     /// be different for rust 2018 for example.
@@ -294,6 +317,7 @@ impl Parser<'_> {
     fn process_mod(
         &mut self,
         pkg: &PackageRef,
+        src_file: Option<&FilePath>,
         mod_dir: Option<&FilePath>,
         submod_dir: Option<&FilePath>,
         items: &[syn::Item],
@@ -303,11 +327,13 @@ impl Parser<'_> {
     ) -> Result<(), Error> {
         debug_assert_eq!(mod_dir.is_some(), submod_dir.is_some());
         // We process the items first then the nested modules.
+        self.out.current_module.clone_from(&self.mod_stack);
         let nested_modules = self.out.load_syn_crate_mod(
             self.config,
             &self.binding_crate_name,
             &pkg.name,
             Cfg::join(&self.cfg_stack).as_ref(),
+            src_file,
             items,
         );
 
@@ -317,6 +343,7 @@ impl Parser<'_> {
             if let Some(ref cfg) = cfg {
                 self.cfg_stack.push(cfg.clone());
             }
+            self.mod_stack.push(next_mod_name.clone());
 
             if let Some((_, ref inline_items)) = item.content {
                 // TODO(emilio): This should use #[path] attribute if present,
@@ -325,6 +352,7 @@ impl Parser<'_> {
                 let next_mod_dir = mod_dir.map(|dir| dir.join(&next_mod_name));
                 self.process_mod(
                     pkg,
+                    src_file,
                     next_mod_dir.as_deref(),
                     next_submod_dir.as_deref(),
                     inline_items,
@@ -386,19 +414,38 @@ impl Parser<'_> {
                     // This should be an error, but it's common enough to
                     // just elicit a warning
                     if !path_attr_found {
-                        warn!(
-                            "Parsing crate `{}`: can't find mod {}`.",
-                            pkg.name, next_mod_name
+                        // The items of the other modules were loaded since, so
+                        // the location has to come from this one.
+                        let location = src_file.map(|file| {
+                            SourceLocation::from_span(file.to_path_buf(), item.ident.span())
+                        });
+                        self.out.report(
+                            Diagnostic::warning(
+                                DiagnosticCode::MissingModule,
+                                format!(
+                                    "Parsing crate `{}`: can't find mod {}`.",
+                                    pkg.name, next_mod_name
+                                ),
+                            )
+                            .with_item(format!("{}::{}", pkg.name, next_mod_name))
+                            .with_location(location),
                         );
                     }
                 }
             } else {
-                warn!(
-                    "Parsing expanded crate `{}`: can't find mod {}`.",
-                    pkg.name, next_mod_name
+                self.out.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::MissingModule,
+                        format!(
+                            "Parsing expanded crate `{}`: can't find mod {}`.",
+                            pkg.name, next_mod_name
+                        ),
+                    )
+                    .with_item(format!("{}::{}", pkg.name, next_mod_name)),
                 );
             }
 
+            self.mod_stack.pop();
             if cfg.is_some() {
                 self.cfg_stack.pop();
             }
@@ -420,6 +467,12 @@ pub struct Parse {
     pub functions: Vec<Function>,
     pub source_files: Vec<FilePathBuf>,
     pub package_version: String,
+    pub diagnostics: Vec<Diagnostic>,
+    pub provenance: Provenance,
+    /// The file the items currently being loaded come from, if any.
+    current_file: Option<FilePathBuf>,
+    /// The path of the module the items currently being loaded come from.
+    current_module: Vec<String>,
 }
 
 impl Parse {
@@ -435,9 +488,69 @@ impl Parse {
             functions: Vec::new(),
             source_files: Vec::new(),
             package_version: String::new(),
+            diagnostics: Vec::new(),
+            provenance: Provenance::default(),
+            current_file: None,
+            current_module: Vec::new(),
         }
     }
 
+    /// Logs a diagnostic and records it to be returned with the bindings.
+    fn report(&mut self, diagnostic: Diagnostic) {
+        diagnostic.log();
+        self.diagnostics.push(diagnostic);
+    }
+
+    /// Resolves a span of the items currently being loaded to a location.
+    fn location(&self, span: proc_macro2::Span) -> Option<SourceLocation> {
+        let file = self.current_file.clone()?;
+        Some(SourceLocation::from_span(file, span))
+    }
+
+    /// Records where an item that was loaded comes from.
+    fn record_source(
+        &mut self,
+        name: String,
+        item_type: ItemType,
+        crate_name: &str,
+        span: proc_macro2::Span,
+        note: Option<String>,
+    ) {
+        let location = self.location(span);
+        self.provenance.sources.push(ItemSource {
+            name,
+            item_type,
+            crate_name: crate_name.to_owned(),
+            module: self.current_module.clone(),
+            location,
+            note,
+        });
+    }
+
+    /// Records an item that was skipped because of `cbindgen:ignore` or a
+    /// test attribute.
+    fn record_ignored(&mut self, crate_name: &str, item: &syn::Item) {
+        let (ident, item_type) = match *item {
+            syn::Item::Fn(ref item) => (&item.sig.ident, ItemType::Functions),
+            syn::Item::Const(ref item) => (&item.ident, ItemType::Constants),
+            syn::Item::Static(ref item) => (&item.ident, ItemType::Globals),
+            syn::Item::Struct(ref item) => (&item.ident, ItemType::Structs),
+            syn::Item::Union(ref item) => (&item.ident, ItemType::Unions),
+            syn::Item::Enum(ref item) => (&item.ident, ItemType::Enums),
+            syn::Item::Type(ref item) => (&item.ident, ItemType::Typedefs),
+            _ => return,
+        };
+        let location = self.location(ident.span());
+        self.provenance.ignored.push(ItemSource {
+            name: ident.unraw().to_string(),
+            item_type,
+            crate_name: crate_name.to_owned(),
+            module: self.current_module.clone(),
+            location,
+            note: None,
+        });
+    }
+
     pub fn add_std_types(&mut self) {
         let mut add_opaque = |path: &str, generic_params: Vec<&str>| {
             let path = Path::new(path);
@@ -484,6 +597,8 @@ impl Parse {
         self.functions.extend_from_slice(&other.functions);
         self.source_files.extend_from_slice(&other.source_files);
         self.package_version.clone_from(&other.package_version);
+        self.diagnostics.extend_from_slice(&other.diagnostics);
+        self.provenance.extend_with(&other.provenance);
     }
 
     fn load_syn_crate_mod<'a>(
@@ -492,13 +607,16 @@ impl Parse {
         binding_crate_name: &str,
         crate_name: &str,
         mod_cfg: Option<&Cfg>,
+        src_file: Option<&FilePath>,
         items: &'a [syn::Item],
     ) -> Vec<&'a syn::ItemMod> {
+        self.current_file = src_file.map(FilePath::to_path_buf);
         let mut impls_with_assoc_consts = Vec::new();
         let mut nested_modules = Vec::new();
 
         for item in items {
             if item.should_skip_parsing() {
+                self.record_ignored(crate_name, item);
                 continue;
             }
             match item {
@@ -623,7 +741,7 @@ impl Parse {
                 {
                     info!(
                         "Skip {}::{} - (fn's outside of the binding crate are not used).",
-                        crate_name, &function.sig.ident
+                        crate_name, function.sig.ident
                     );
                     return;
                 }
@@ -637,14 +755,29 @@ impl Parse {
                     mod_cfg.as_ref(),
                 ) {
                     Ok(func) => {
-                        info!("Take {}::{}.", crate_name, &function.sig.ident);
-
+                        info!("Take {}::{}.", crate_name, function.sig.ident);
+
+                        self.record_source(
+                            func.path.name().to_owned(),
+                            ItemType::Functions,
+                            crate_name,
+                            function.sig.ident.span(),
+                            None,
+                        );
                         self.functions.push(func);
                     }
                     Err(msg) => {
-                        error!(
-                            "Cannot use fn {}::{} ({}).",
-                            crate_name, &function.sig.ident, msg
+                        let location = self.location(function.sig.ident.span());
+                        self.report(
+                            Diagnostic::error(
+                                DiagnosticCode::Unsupported,
+                                format!(
+                                    "Cannot use fn {}::{} ({}).",
+                                    crate_name, function.sig.ident, msg
+                                ),
+                            )
+                            .with_item(format!("{}::{}", crate_name, function.sig.ident))
+                            .with_location(location),
                         );
                     }
                 }
@@ -713,7 +846,7 @@ impl Parse {
         {
             info!(
                 "Skip {}::{} - (fn's outside of the binding crate are not used).",
-                crate_name, &sig.ident
+                crate_name, sig.ident
             );
             return;
         }
@@ -730,6 +863,7 @@ impl Parse {
 
         let is_extern_c = sig.abi.is_omitted() || sig.abi.is_c() || sig.abi.is_cmse();
         let exported_name = named_symbol.exported_name();
+        let location = self.location(sig.ident.span());
 
         match (is_extern_c, exported_name) {
             (true, Some(exported_name)) => {
@@ -737,21 +871,51 @@ impl Parse {
                 match Function::load(path, self_type, sig, false, attrs, mod_cfg) {
                     Ok(func) => {
                         info!("Take {}.", loggable_item_name());
+                        self.record_source(
+                            func.path.name().to_owned(),
+                            ItemType::Functions,
+                            crate_name,
+                            sig.ident.span(),
+                            None,
+                        );
                         self.functions.push(func);
                     }
                     Err(msg) => {
-                        error!("Cannot use fn {} ({}).", loggable_item_name(), msg);
+                        self.report(
+                            Diagnostic::error(
+                                DiagnosticCode::Unsupported,
+                                format!("Cannot use fn {} ({}).", loggable_item_name(), msg),
+                            )
+                            .with_item(loggable_item_name())
+                            .with_location(location),
+                        );
                     }
                 }
             }
             (true, None) => {
-                warn!(
-                    "Skipping {} - (not `no_mangle`, and has no `export_name` attribute)",
-                    loggable_item_name()
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::NotNoMangle,
+                        format!(
+                            "Skipping {} - (not `no_mangle`, and has no `export_name` attribute)",
+                            loggable_item_name()
+                        ),
+                    )
+                    .with_help("add `#[no_mangle]` or an `#[export_name]` attribute")
+                    .with_item(loggable_item_name())
+                    .with_location(location),
                 );
             }
             (false, Some(_exported_name)) => {
-                warn!("Skipping {} - (not `extern \"C\"`)", loggable_item_name());
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::NotExternC,
+                        format!("Skipping {} - (not `extern \"C\"`)", loggable_item_name()),
+                    )
+                    .with_help("declare the function as `extern \"C\"`")
+                    .with_item(loggable_item_name())
+                    .with_location(location),
+                );
             }
             (false, None) => {}
         }
@@ -770,7 +934,14 @@ impl Parse {
         let ty = match Type::load(impl_ty) {
             Ok(ty) => ty,
             Err(e) => {
-                warn!("Skipping associated constants for {impl_ty:?}: {e:?}");
+                let location = self.location(impl_ty.span());
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::Unsupported,
+                        format!("Skipping associated constants for {impl_ty:?}: {e:?}"),
+                    )
+                    .with_location(location),
+                );
                 return;
             }
         };
@@ -783,15 +954,32 @@ impl Parse {
         let impl_path = match ty.get_root_path() {
             Some(p) => p,
             None => {
-                warn!("Couldn't find path for {ty:?}, skipping associated constants");
+                let location = self.location(impl_ty.span());
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::Unsupported,
+                        format!("Couldn't find path for {ty:?}, skipping associated constants"),
+                    )
+                    .with_location(location),
+                );
                 return;
             }
         };
 
         for item in items.into_iter() {
+            let item_name = format!("{}::{}::{}", crate_name, impl_path, item.ident);
+            let location = self.location(item.ident.span());
             if let syn::Visibility::Public(_) = item.vis {
             } else {
-                warn!("Skip {}::{} - (not `pub`).", crate_name, &item.ident);
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::NotPublic,
+                        format!("Skip {}::{} - (not `pub`).", crate_name, item.ident),
+                    )
+                    .with_help("make the item `pub`")
+                    .with_item(item_name)
+                    .with_location(location),
+                );
                 return;
             }
 
@@ -805,7 +993,14 @@ impl Parse {
                 Some(impl_path.clone()),
             ) {
                 Ok(constant) => {
-                    info!("Take {}::{}::{}.", crate_name, impl_path, &item.ident);
+                    info!("Take {}::{}::{}.", crate_name, impl_path, item.ident);
+                    self.record_source(
+                        constant.path.name().to_owned(),
+                        ItemType::Constants,
+                        crate_name,
+                        item.ident.span(),
+                        None,
+                    );
                     let mut any = false;
                     self.structs.for_items_mut(&impl_path, |item| {
                         any = true;
@@ -814,14 +1009,25 @@ impl Parse {
                     // Handle associated constants to other item types that are
                     // not structs like enums or such as regular constants.
                     if !any && !self.constants.try_insert(constant) {
-                        error!(
-                            "Conflicting name for constant {}::{}::{}.",
-                            crate_name, impl_path, &item.ident,
+                        self.report(
+                            Diagnostic::error(
+                                DiagnosticCode::ConflictingName,
+                                format!("Conflicting name for constant {item_name}."),
+                            )
+                            .with_item(item_name)
+                            .with_location(location),
                         );
                     }
                 }
                 Err(msg) => {
-                    warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                    self.report(
+                        Diagnostic::warning(
+                            DiagnosticCode::Unsupported,
+                            format!("Skip {}::{} - ({})", crate_name, item.ident, msg),
+                        )
+                        .with_item(item_name)
+                        .with_location(location),
+                    );
                 }
             }
         }
@@ -842,29 +1048,60 @@ impl Parse {
         {
             info!(
                 "Skip {}::{} - (const's outside of the binding crate are not used).",
-                crate_name, &item.ident
+                crate_name, item.ident
             );
             return;
         }
 
+        let item_name = format!("{}::{}", crate_name, item.ident);
+        let location = self.location(item.ident.span());
         if let syn::Visibility::Public(_) = item.vis {
         } else {
-            warn!("Skip {}::{} - (not `pub`).", crate_name, &item.ident);
+            self.report(
+                Diagnostic::warning(
+                    DiagnosticCode::NotPublic,
+                    format!("Skip {item_name} - (not `pub`)."),
+                )
+                .with_help("make the item `pub`")
+                .with_item(item_name)
+                .with_location(location),
+            );
             return;
         }
 
         let path = Path::new(item.ident.unraw().to_string());
         match Constant::load(path, mod_cfg, &item.ty, &item.expr, &item.attrs, None) {
             Ok(constant) => {
-                info!("Take {}::{}.", crate_name, &item.ident);
+                info!("Take {item_name}.");
+                self.record_source(
+                    constant.path.name().to_owned(),
+                    ItemType::Constants,
+                    crate_name,
+                    item.ident.span(),
+                    None,
+                );
 
                 let full_name = constant.path.clone();
                 if !self.constants.try_insert(constant) {
-                    error!("Conflicting name for constant {full_name}");
+                    self.report(
+                        Diagnostic::error(
+                            DiagnosticCode::ConflictingName,
+                            format!("Conflicting name for constant {full_name}"),
+                        )
+                        .with_item(item_name)
+                        .with_location(location),
+                    );
                 }
             }
             Err(msg) => {
-                warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::Unsupported,
+                        format!("Skip {item_name} - ({msg})"),
+                    )
+                    .with_item(item_name)
+                    .with_location(location),
+                );
             }
         }
     }
@@ -884,24 +1121,48 @@ impl Parse {
         {
             info!(
                 "Skip {}::{} - (static's outside of the binding crate are not used).",
-                crate_name, &item.ident
+                crate_name, item.ident
             );
             return;
         }
 
+        let item_name = format!("{}::{}", crate_name, item.ident);
+        let location = self.location(item.ident.span());
         if let Some(exported_name) = item.exported_name() {
             let path = Path::new(exported_name);
             match Static::load(path, item, mod_cfg) {
                 Ok(constant) => {
-                    info!("Take {}::{}.", crate_name, &item.ident);
+                    info!("Take {item_name}.");
+                    self.record_source(
+                        constant.path.name().to_owned(),
+                        ItemType::Globals,
+                        crate_name,
+                        item.ident.span(),
+                        None,
+                    );
                     self.globals.try_insert(constant);
                 }
                 Err(msg) => {
-                    warn!("Skip {}::{} - ({})", crate_name, &item.ident, msg);
+                    self.report(
+                        Diagnostic::warning(
+                            DiagnosticCode::Unsupported,
+                            format!("Skip {item_name} - ({msg})"),
+                        )
+                        .with_item(item_name)
+                        .with_location(location),
+                    );
                 }
             }
         } else {
-            warn!("Skip {}::{} - (not `no_mangle`).", crate_name, &item.ident);
+            self.report(
+                Diagnostic::warning(
+                    DiagnosticCode::NotNoMangle,
+                    format!("Skip {item_name} - (not `no_mangle`)."),
+                )
+                .with_help("add `#[no_mangle]` or an `#[export_name]` attribute")
+                .with_item(item_name)
+                .with_location(location),
+            );
         }
     }
 
@@ -915,11 +1176,25 @@ impl Parse {
     ) {
         match Struct::load(&config.layout, item, mod_cfg) {
             Ok(st) => {
-                info!("Take {}::{}.", crate_name, &item.ident);
+                info!("Take {}::{}.", crate_name, item.ident);
+                self.record_source(
+                    st.path.name().to_owned(),
+                    ItemType::Structs,
+                    crate_name,
+                    item.ident.span(),
+                    None,
+                );
                 self.structs.try_insert(st);
             }
             Err(msg) => {
-                info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
+                info!("Take {}::{} - opaque ({}).", crate_name, item.ident, msg);
+                self.record_source(
+                    item.ident.unraw().to_string(),
+                    ItemType::OpaqueItems,
+                    crate_name,
+                    item.ident.span(),
+                    Some(msg),
+                );
                 let path = Path::new(item.ident.unraw().to_string());
                 self.opaque_items.try_insert(
                     OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
@@ -938,12 +1213,26 @@ impl Parse {
     ) {
         match Union::load(&config.layout, item, mod_cfg) {
             Ok(st) => {
-                info!("Take {}::{}.", crate_name, &item.ident);
+                info!("Take {}::{}.", crate_name, item.ident);
 
+                self.record_source(
+                    st.path.name().to_owned(),
+                    ItemType::Unions,
+                    crate_name,
+                    item.ident.span(),
+                    None,
+                );
                 self.unions.try_insert(st);
             }
             Err(msg) => {
-                info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
+                info!("Take {}::{} - opaque ({}).", crate_name, item.ident, msg);
+                self.record_source(
+                    item.ident.unraw().to_string(),
+                    ItemType::OpaqueItems,
+                    crate_name,
+                    item.ident.span(),
+                    Some(msg),
+                );
                 let path = Path::new(item.ident.unraw().to_string());
                 self.opaque_items.try_insert(
                     OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
@@ -962,11 +1251,25 @@ impl Parse {
     ) {
         match Enum::load(item, mod_cfg, config) {
             Ok(en) => {
-                info!("Take {}::{}.", crate_name, &item.ident);
+                info!("Take {}::{}.", crate_name, item.ident);
+                self.record_source(
+                    en.path.name().to_owned(),
+                    ItemType::Enums,
+                    crate_name,
+                    item.ident.span(),
+                    None,
+                );
                 self.enums.try_insert(en);
             }
             Err(msg) => {
-                info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
+                info!("Take {}::{} - opaque ({}).", crate_name, item.ident, msg);
+                self.record_source(
+                    item.ident.unraw().to_string(),
+                    ItemType::OpaqueItems,
+                    crate_name,
+                    item.ident.span(),
+                    Some(msg),
+                );
                 let path = Path::new(item.ident.unraw().to_string());
                 self.opaque_items.try_insert(
                     OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
@@ -979,12 +1282,26 @@ impl Parse {
     fn load_syn_ty(&mut self, crate_name: &str, mod_cfg: Option<&Cfg>, item: &syn::ItemType) {
         match Typedef::load(item, mod_cfg) {
             Ok(st) => {
-                info!("Take {}::{}.", crate_name, &item.ident);
+                info!("Take {}::{}.", crate_name, item.ident);
 
+                self.record_source(
+                    st.path.name().to_owned(),
+                    ItemType::Typedefs,
+                    crate_name,
+                    item.ident.span(),
+                    None,
+                );
                 self.typedefs.try_insert(st);
             }
             Err(msg) => {
-                info!("Take {}::{} - opaque ({}).", crate_name, &item.ident, msg);
+                info!("Take {}::{} - opaque ({}).", crate_name, item.ident, msg);
+                self.record_source(
+                    item.ident.unraw().to_string(),
+                    ItemType::OpaqueItems,
+                    crate_name,
+                    item.ident.span(),
+                    Some(msg),
+                );
                 let path = Path::new(item.ident.unraw().to_string());
                 self.opaque_items.try_insert(
                     OpaqueItem::load(path, &item.generics, &item.attrs, mod_cfg).unwrap(),
@@ -1012,7 +1329,15 @@ impl Parse {
         let bitflags = match bitflags::parse(item.mac.tokens.clone()) {
             Ok(bf) => bf,
             Err(e) => {
-                warn!("Failed to parse bitflags invocation: {e:?}");
+                let location = self.location(item.mac.path.span());
+                self.report(
+                    Diagnostic::warning(
+                        DiagnosticCode::BitflagsParse,
+                        format!("Failed to parse bitflags invocation: {e:?}"),
+                    )
+                    .with_item(crate_name)
+                    .with_location(location),
+                );
                 return;
             }
         };
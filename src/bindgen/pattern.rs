@@ -0,0 +1,174 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::str::FromStr;
+
+use regex::Regex;
+
+use crate::bindgen::error::Error;
+
+/// A selector for items or crates, as used in `export.include`,
+/// `export.exclude`, `parse.include` and `parse.exclude`.
+///
+/// * `re:<regex>` selects the names the regular expression matches.
+/// * A pattern containing `*` or `?` is a glob. `*` and `?` don't match across
+///   `::`, `**` does.
+/// * Any other pattern selects exactly that name.
+///
+/// Patterns containing `::` are matched against the module-qualified path of an
+/// item, which starts either with the name of its crate or with `crate`, for
+/// example `my_crate::ffi::v2::*` or `crate::ffi::v2::*`.
+#[derive(Debug, Clone)]
+pub struct NamePattern {
+    matcher: Matcher,
+    /// Whether the pattern is matched against module-qualified paths.
+    qualified: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Matcher {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl FromStr for NamePattern {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let qualified = s.contains("::");
+        let matcher = if let Some(re) = s.strip_prefix("re:") {
+            Matcher::Regex(Regex::new(re).map_err(|e| e.to_string())?)
+        } else if s.contains(['*', '?']) {
+            Matcher::Regex(Regex::new(&glob_to_regex(s)).map_err(|e| e.to_string())?)
+        } else {
+            Matcher::Exact(s.to_owned())
+        };
+        Ok(NamePattern { matcher, qualified })
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                re.push_str(".*");
+            }
+            '*' => re.push_str("[^:]*"),
+            '?' => re.push_str("[^:]"),
+            c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    re.push('$');
+    re
+}
+
+impl NamePattern {
+    /// Returns the name this pattern selects if it is a plain name.
+    pub fn exact_name(&self) -> Option<&str> {
+        match self.matcher {
+            Matcher::Exact(ref name) if !self.qualified => Some(name),
+            _ => None,
+        }
+    }
+
+    fn is_match(&self, s: &str) -> bool {
+        match self.matcher {
+            Matcher::Exact(ref name) => name == s,
+            Matcher::Regex(ref re) => re.is_match(s),
+        }
+    }
+
+    /// Whether the pattern selects an item named `name`, defined in the
+    /// modules `modules` (a list of `(crate name, module path)` pairs, as an
+    /// item may be defined more than once under different `cfg`s).
+    pub fn matches(&self, name: &str, modules: &[(&str, &[String])]) -> bool {
+        if !self.qualified {
+            return self.is_match(name);
+        }
+        modules.iter().any(|(crate_name, module)| {
+            let mut path = module.join("::");
+            if !path.is_empty() {
+                path.push_str("::");
+            }
+            path.push_str(name);
+            self.is_match(&format!("{crate_name}::{path}"))
+                || self.is_match(&format!("crate::{path}"))
+        })
+    }
+}
+
+/// A list of `NamePattern`s, parsed from the strings of a config.
+#[derive(Debug, Clone, Default)]
+pub struct NamePatterns(Vec<NamePattern>);
+
+impl NamePatterns {
+    /// Parses `patterns`, which come from the config option named `option`.
+    pub fn new(option: &str, patterns: &[String]) -> Result<NamePatterns, Error> {
+        patterns
+            .iter()
+            .map(|pattern| {
+                pattern.parse().map_err(|reason| Error::InvalidPattern {
+                    option: option.to_owned(),
+                    pattern: pattern.clone(),
+                    reason,
+                })
+            })
+            .collect::<Result<_, _>>()
+            .map(NamePatterns)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &NamePattern> {
+        self.0.iter()
+    }
+
+    /// Whether any pattern selects the item named `name`, see
+    /// `NamePattern::matches`.
+    pub fn matches(&self, name: &str, modules: &[(&str, &[String])]) -> bool {
+        self.0.iter().any(|pattern| pattern.matches(name, modules))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pattern(s: &str) -> NamePattern {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn unqualified_patterns() {
+        assert!(pattern("Foo").matches("Foo", &[]));
+        assert!(!pattern("Foo").matches("FooBar", &[]));
+        assert!(pattern("Internal*").matches("InternalState", &[]));
+        assert!(!pattern("Internal*").matches("PublicState", &[]));
+        assert!(pattern("Vec?").matches("Vec3", &[]));
+        assert!(pattern("re:^Test.*").matches("TestHelper", &[]));
+        assert!(!pattern("re:^Test.*").matches("MyTest", &[]));
+    }
+
+    #[test]
+    fn qualified_patterns() {
+        let v2 = ["ffi".to_owned(), "v2".to_owned()];
+        let nested = ["ffi".to_owned(), "v2".to_owned(), "detail".to_owned()];
+        let in_v2: &[(&str, &[String])] = &[("my_crate", &v2)];
+        let in_nested: &[(&str, &[String])] = &[("my_crate", &nested)];
+
+        assert!(pattern("crate::ffi::v2::*").matches("Foo", in_v2));
+        assert!(pattern("my_crate::ffi::v2::*").matches("Foo", in_v2));
+        assert!(!pattern("other::ffi::v2::*").matches("Foo", in_v2));
+        assert!(!pattern("crate::ffi::v2::*").matches("Foo", in_nested));
+        assert!(pattern("crate::ffi::**").matches("Foo", in_nested));
+        assert!(pattern("crate::ffi::v2::Foo").matches("Foo", in_v2));
+        assert!(!pattern("crate::ffi::v2::*").matches("Foo", &[]));
+    }
+
+    #[test]
+    fn invalid_regex() {
+        assert!("re:(".parse::<NamePattern>().is_err());
+    }
+}
@@ -0,0 +1,463 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+//! Plugins are programs that post-process the items of the bindings, in the
+//! spirit of protoc plugins. Each one is given a `PluginRequest` as JSON on
+//! stdin and prints a `PluginResponse` as JSON on stdout.
+
+use std::collections::{HashMap, HashSet};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::thread;
+
+use crate::bindgen::bindings::Bindings;
+use crate::bindgen::config::{ItemType, Language};
+use crate::bindgen::error::Error;
+use crate::bindgen::ir::{
+    Field, FunctionArgument, ItemContainer, Path as BindgenPath, Type, VariantBody,
+};
+use crate::bindgen::transform::{Entry, EntryMut, IrView};
+
+/// The version of the protocol, bumped on incompatible changes.
+const PROTOCOL_VERSION: u32 = 2;
+
+#[derive(Serialize)]
+struct PluginRequest {
+    version: u32,
+    language: Language,
+    items: Vec<PluginItem>,
+}
+
+#[derive(Serialize)]
+struct PluginItem {
+    /// The position of the item in the request, used to refer to it in the
+    /// response.
+    id: usize,
+    name: String,
+    rust_name: String,
+    kind: ItemType,
+    documentation: Vec<String>,
+    /// The type of constants and globals, and the aliased type of typedefs.
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    ty: Option<Type>,
+    /// The fields of structs and unions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<PluginField>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    variants: Option<Vec<PluginVariant>>,
+    /// The arguments and return type of functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    args: Option<Vec<PluginArg>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ret: Option<Type>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginResponse {
+    /// Reports a failure of the plugin.
+    #[serde(default)]
+    error: Option<String>,
+    /// The items to keep, in the order to write them in. Leaving this out
+    /// keeps the items as they are.
+    #[serde(default)]
+    items: Option<Vec<PluginItemChange>>,
+    #[serde(default)]
+    files: Vec<PluginFile>,
+}
+
+/// The changes to an item. Everything left out is kept as it is.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginItemChange {
+    id: usize,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    documentation: Option<Vec<String>>,
+    #[serde(default)]
+    annotations: Vec<String>,
+    #[serde(rename = "type", default)]
+    ty: Option<Type>,
+    /// The fields to keep or add, in order.
+    #[serde(default)]
+    fields: Option<Vec<PluginField>>,
+    /// The variants to change. Variants can't be added or removed.
+    #[serde(default)]
+    variants: Option<Vec<PluginVariant>>,
+    /// The arguments to keep or add, in order.
+    #[serde(default)]
+    args: Option<Vec<PluginArg>>,
+    #[serde(default)]
+    ret: Option<Type>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginField {
+    /// The position of the field in the request. Fields of a response without
+    /// one are added, and need a name and a type.
+    #[serde(default)]
+    id: Option<usize>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "type", default)]
+    ty: Option<Type>,
+    #[serde(default)]
+    documentation: Option<Vec<String>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginVariant {
+    id: usize,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    documentation: Option<Vec<String>>,
+    /// The fields of variants with a body, without the tag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    fields: Option<Vec<PluginField>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginArg {
+    /// The position of the argument in the request. Arguments of a response
+    /// without one are added, and need a type.
+    #[serde(default)]
+    id: Option<usize>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(rename = "type", default)]
+    ty: Option<Type>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PluginFile {
+    /// The path of the file, relative to the directory of the header.
+    path: PathBuf,
+    content: String,
+}
+
+/// Runs the plugins of the config on the bindings, in order.
+pub(crate) fn run_plugins(bindings: &mut Bindings) -> Result<(), Error> {
+    for command in bindings.config.plugin_commands() {
+        let error = |reason: String| Error::Plugin {
+            plugin: command.display().to_string(),
+            reason,
+        };
+        let response = run_plugin(&command, bindings).map_err(error)?;
+        apply_response(bindings, response).map_err(error)?;
+    }
+    Ok(())
+}
+
+fn run_plugin(command: &Path, bindings: &mut Bindings) -> Result<PluginResponse, String> {
+    let ir = IrView::new(bindings);
+    let items = ir
+        .items()
+        .into_iter()
+        .enumerate()
+        .map(|(id, item)| {
+            let mut request = PluginItem {
+                id,
+                documentation: ir.documentation_at(id).unwrap_or_default().to_vec(),
+                name: item.name,
+                rust_name: item.rust_name,
+                kind: item.kind,
+                ty: None,
+                fields: None,
+                variants: None,
+                args: None,
+                ret: None,
+            };
+            if let Some(entry) = ir.entry(id) {
+                describe(entry, &mut request);
+            }
+            request
+        })
+        .collect();
+    let request = PluginRequest {
+        version: PROTOCOL_VERSION,
+        language: ir.config().language,
+        items,
+    };
+    let request = serde_json::to_vec(&request).map_err(|e| e.to_string())?;
+
+    let mut child = Command::new(command)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .map_err(|e| format!("couldn't run it: {e}"))?;
+    // Write from another thread so that a plugin which answers before reading
+    // all of its input can't dead-lock us.
+    let mut stdin = child.stdin.take().unwrap();
+    let writer = thread::spawn(move || stdin.write_all(&request));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("couldn't run it: {e}"))?;
+    // The plugin may not care about its input.
+    let _ = writer.join();
+
+    if !output.status.success() {
+        return Err(format!("it exited with {}", output.status));
+    }
+    let response: PluginResponse = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("couldn't parse its response: {e}"))?;
+    match response.error {
+        Some(error) => Err(error),
+        None => Ok(response),
+    }
+}
+
+/// Fills in the types, fields, variants and arguments of an item.
+fn describe(entry: Entry, request: &mut PluginItem) {
+    match entry {
+        Entry::Constant(x) => request.ty = Some(x.ty.clone()),
+        Entry::Global(x) => request.ty = Some(x.ty.clone()),
+        Entry::Item(x) => match *x {
+            ItemContainer::Constant(ref x) => request.ty = Some(x.ty.clone()),
+            ItemContainer::Static(ref x) => request.ty = Some(x.ty.clone()),
+            ItemContainer::Typedef(ref x) => request.ty = Some(x.aliased.clone()),
+            ItemContainer::Struct(ref x) => request.fields = Some(describe_fields(&x.fields)),
+            ItemContainer::Union(ref x) => request.fields = Some(describe_fields(&x.fields)),
+            ItemContainer::Enum(ref x) => {
+                let variants = x
+                    .variants
+                    .iter()
+                    .enumerate()
+                    .map(|(id, variant)| PluginVariant {
+                        id,
+                        name: Some(variant.export_name.clone()),
+                        documentation: Some(variant.documentation.doc_comment.clone()),
+                        fields: match variant.body {
+                            VariantBody::Body { ref body, .. } => {
+                                Some(describe_fields(&body.fields[body.has_tag_field as usize..]))
+                            }
+                            VariantBody::Empty(..) => None,
+                        },
+                    })
+                    .collect();
+                request.variants = Some(variants);
+            }
+            ItemContainer::OpaqueItem(..) => {}
+        },
+        Entry::Function(x) => {
+            let args = x
+                .args
+                .iter()
+                .enumerate()
+                .map(|(id, arg)| PluginArg {
+                    id: Some(id),
+                    name: arg.name.clone(),
+                    ty: Some(arg.ty.clone()),
+                })
+                .collect();
+            request.args = Some(args);
+            request.ret = Some(x.ret.clone());
+        }
+    }
+}
+
+fn describe_fields(fields: &[Field]) -> Vec<PluginField> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(id, field)| PluginField {
+            id: Some(id),
+            name: Some(field.name.clone()),
+            ty: Some(field.ty.clone()),
+            documentation: Some(field.documentation.doc_comment.clone()),
+        })
+        .collect()
+}
+
+fn apply_response(bindings: &mut Bindings, response: PluginResponse) -> Result<(), String> {
+    for file in response.files {
+        if file.path.is_absolute() {
+            return Err(format!("file `{}` isn't relative", file.path.display()));
+        }
+        bindings.plugin_files.push((file.path, file.content));
+    }
+
+    let changes = match response.items {
+        Some(changes) => changes,
+        None => return Ok(()),
+    };
+    // Items are changed by id rather than by name, since the `cfg` variants
+    // of an item share its name. Ids stay valid until the items are selected.
+    let mut ir = IrView::new(bindings);
+    let items = ir.items();
+    let mut order = HashMap::new();
+    let mut changed_structs = vec![];
+    for (position, mut change) in changes.into_iter().enumerate() {
+        let id = change.id;
+        if id >= items.len() || order.insert(id, position).is_some() {
+            return Err(format!("item id {id} is unknown or repeated"));
+        }
+        // Change the types before renaming, so that the references to the
+        // item in the new types are renamed too.
+        let changed_struct = ir
+            .entry_at(id, |entry| apply_shape(entry, &mut change))
+            .unwrap()
+            .map_err(|e| format!("item {id}: {e}"))?;
+        changed_structs.extend(changed_struct);
+        if let Some(new_name) = change.name {
+            if new_name != items[id].name {
+                ir.rename_at(id, &new_name)
+                    .map_err(|e| format!("item {id}: {e}"))?;
+            }
+        }
+        if let Some(documentation) = change.documentation {
+            ir.set_documentation_at(id, documentation);
+        }
+        for annotation in &change.annotations {
+            ir.annotate_at(id, annotation)?;
+        }
+    }
+    ir.select(&order);
+    for path in changed_structs {
+        bindings.refresh_struct(&path);
+    }
+    Ok(())
+}
+
+/// Applies the changes to the types, fields, variants and arguments of an
+/// item. Returns the path of the struct whose fields changed, if any.
+fn apply_shape(
+    mut entry: EntryMut,
+    change: &mut PluginItemChange,
+) -> Result<Option<BindgenPath>, String> {
+    if let Some(ty) = change.ty.take() {
+        let target = match entry {
+            EntryMut::Constant(ref mut x) => &mut x.ty,
+            EntryMut::Global(ref mut x) => &mut x.ty,
+            EntryMut::Item(ref mut x) => match **x {
+                ItemContainer::Constant(ref mut x) => &mut x.ty,
+                ItemContainer::Static(ref mut x) => &mut x.ty,
+                ItemContainer::Typedef(ref mut x) => &mut x.aliased,
+                _ => return Err("it has no type".to_owned()),
+            },
+            EntryMut::Function(..) => return Err("it has no type".to_owned()),
+        };
+        *target = ty;
+    }
+
+    let mut changed_struct = None;
+    if let Some(fields) = change.fields.take() {
+        match entry {
+            EntryMut::Item(ref mut x) => match **x {
+                ItemContainer::Struct(ref mut x) => {
+                    apply_fields(&mut x.fields, 0, fields)?;
+                    changed_struct = Some(x.path.clone());
+                }
+                ItemContainer::Union(ref mut x) => apply_fields(&mut x.fields, 0, fields)?,
+                _ => return Err("it has no fields".to_owned()),
+            },
+            _ => return Err("it has no fields".to_owned()),
+        }
+    }
+
+    if let Some(variants) = change.variants.take() {
+        let item = match entry {
+            EntryMut::Item(&mut ItemContainer::Enum(ref mut x)) => x,
+            _ => return Err("it has no variants".to_owned()),
+        };
+        let mut seen = HashSet::new();
+        for change in variants {
+            let id = change.id;
+            let variant = match item.variants.get_mut(id) {
+                Some(variant) if seen.insert(id) => variant,
+                _ => return Err(format!("variant id {id} is unknown or repeated")),
+            };
+            if let Some(name) = change.name {
+                variant.export_name = name;
+            }
+            if let Some(documentation) = change.documentation {
+                variant.documentation.doc_comment = documentation;
+            }
+            if let Some(fields) = change.fields {
+                match variant.body {
+                    VariantBody::Body { ref mut body, .. } => {
+                        let tag = body.has_tag_field as usize;
+                        apply_fields(&mut body.fields, tag, fields)?
+                    }
+                    VariantBody::Empty(..) => {
+                        return Err(format!("variant {id} has no fields"));
+                    }
+                }
+            }
+        }
+    }
+
+    if change.args.is_some() || change.ret.is_some() {
+        let function = match entry {
+            EntryMut::Function(x) => x,
+            _ => return Err("it isn't a function".to_owned()),
+        };
+        if let Some(ret) = change.ret.take() {
+            function.ret = ret;
+        }
+        if let Some(changes) = change.args.take() {
+            let mut old: Vec<_> = function.args.drain(..).map(Some).collect();
+            for change in changes {
+                let mut arg = match change.id {
+                    Some(id) => match old.get_mut(id).and_then(Option::take) {
+                        Some(arg) => arg,
+                        None => return Err(format!("argument id {id} is unknown or repeated")),
+                    },
+                    None => FunctionArgument {
+                        name: None,
+                        ty: change.ty.clone().ok_or("new arguments need a type")?,
+                        array_length: None,
+                    },
+                };
+                if let Some(name) = change.name {
+                    arg.name = Some(name);
+                }
+                if let Some(ty) = change.ty {
+                    arg.ty = ty;
+                }
+                function.args.push(arg);
+            }
+        }
+    }
+    Ok(changed_struct)
+}
+
+/// Replaces the fields after the first `skip` ones, which plugins don't see.
+fn apply_fields(
+    fields: &mut Vec<Field>,
+    skip: usize,
+    changes: Vec<PluginField>,
+) -> Result<(), String> {
+    let mut old: Vec<_> = fields.drain(skip..).map(Some).collect();
+    for change in changes {
+        let mut field = match change.id {
+            Some(id) => match old.get_mut(id).and_then(Option::take) {
+                Some(field) => field,
+                None => return Err(format!("field id {id} is unknown or repeated")),
+            },
+            None => match (&change.name, &change.ty) {
+                (Some(name), Some(ty)) => Field::from_name_and_type(name.clone(), ty.clone()),
+                _ => return Err("new fields need a name and a type".to_owned()),
+            },
+        };
+        if let Some(name) = change.name {
+            field.name = name;
+        }
+        if let Some(ty) = change.ty {
+            field.ty = ty;
+        }
+        if let Some(documentation) = change.documentation {
+            field.documentation.doc_comment = documentation;
+        }
+        fields.push(field);
+    }
+    Ok(())
+}
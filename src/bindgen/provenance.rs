@@ -0,0 +1,402 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write as _;
+use std::io::Write;
+use std::str::FromStr;
+
+use serde_json::json;
+
+use crate::bindgen::bindings::Bindings;
+use crate::bindgen::config::ItemType;
+use crate::bindgen::dependencies::DependencyNode;
+use crate::bindgen::diagnostic::SourceLocation;
+use crate::bindgen::ir::Path;
+use crate::bindgen::pattern::NamePatterns;
+
+/// Where an item was found while parsing.
+#[derive(Debug, Clone)]
+pub struct ItemSource {
+    pub name: String,
+    pub item_type: ItemType,
+    pub crate_name: String,
+    /// The path of the module the item is defined in, relative to its crate.
+    pub module: Vec<String>,
+    pub location: Option<SourceLocation>,
+    /// Why the item is represented differently than declared, if it is. For
+    /// example the reason a struct is emitted as an opaque type.
+    pub note: Option<String>,
+}
+
+/// A record of where the items come from and why they are part of the
+/// bindings, used to explain the contents of the generated header.
+#[derive(Debug, Clone, Default)]
+pub struct Provenance {
+    pub sources: Vec<ItemSource>,
+    /// Items skipped because of `cbindgen:ignore`, `#[test]` or `#[cfg(test)]`.
+    pub ignored: Vec<ItemSource>,
+    /// Every `(dependent, dependency)` pair found while collecting the items
+    /// used by the functions, globals, constants and `export.include`.
+    pub edges: Vec<(DependencyNode, Path)>,
+}
+
+impl Provenance {
+    pub(crate) fn extend_with(&mut self, other: &Provenance) {
+        self.sources.extend_from_slice(&other.sources);
+        self.ignored.extend_from_slice(&other.ignored);
+        self.edges.extend_from_slice(&other.edges);
+    }
+
+    /// The crates and modules the items are defined in, by item name, as
+    /// `NamePattern::matches` expects them.
+    pub(crate) fn modules_by_name(&self) -> HashMap<&str, Vec<(&str, &[String])>> {
+        let mut modules: HashMap<_, Vec<_>> = HashMap::new();
+        for source in &self.sources {
+            modules
+                .entry(source.name.as_str())
+                .or_default()
+                .push((source.crate_name.as_str(), source.module.as_slice()));
+        }
+        modules
+    }
+
+    /// Finds the shortest chain of dependencies from a function, global,
+    /// constant or `export.include` to the item named `name`.
+    fn find_chain(&self, name: &str) -> Option<Vec<&DependencyNode>> {
+        let mut dependents: HashMap<&str, Vec<&DependencyNode>> = HashMap::new();
+        for (dependent, dependency) in &self.edges {
+            dependents
+                .entry(dependency.name())
+                .or_default()
+                .push(dependent);
+        }
+
+        let mut visited = HashSet::new();
+        let mut queue: VecDeque<Vec<&DependencyNode>> = VecDeque::new();
+        queue.push_back(vec![]);
+        while let Some(chain) = queue.pop_front() {
+            let current = match chain.last() {
+                Some(node) => node.name(),
+                None => name,
+            };
+            for dependent in dependents.get(current).into_iter().flatten() {
+                if !visited.insert(*dependent) {
+                    continue;
+                }
+                let mut chain = chain.clone();
+                chain.push(*dependent);
+                if dependent.is_root() {
+                    chain.reverse();
+                    return Some(chain);
+                }
+                queue.push_back(chain);
+            }
+        }
+        None
+    }
+
+    pub(crate) fn write_dependency_graph<F: Write>(
+        &self,
+        format: DependencyGraphFormat,
+        out: &mut F,
+    ) -> std::io::Result<()> {
+        match format {
+            DependencyGraphFormat::Dot => {
+                writeln!(out, "digraph dependencies {{")?;
+                let mut roots = HashSet::new();
+                for (dependent, _) in &self.edges {
+                    if dependent.is_root() && roots.insert(dependent) {
+                        writeln!(out, "    {:?} [shape=box];", node_label(dependent))?;
+                    }
+                }
+                for (dependent, dependency) in &self.edges {
+                    writeln!(
+                        out,
+                        "    {:?} -> {:?};",
+                        node_label(dependent),
+                        dependency.name()
+                    )?;
+                }
+                writeln!(out, "}}")
+            }
+            DependencyGraphFormat::Json => {
+                let mut seen = HashSet::new();
+                let mut nodes = vec![];
+                for (dependent, dependency) in &self.edges {
+                    for node in [dependent.clone(), DependencyNode::Item(dependency.clone())] {
+                        let label = node_label(&node);
+                        if seen.insert(label.clone()) {
+                            nodes.push(json!({
+                                "id": label,
+                                "name": node.name(),
+                                "kind": node_kind(&node),
+                            }));
+                        }
+                    }
+                }
+                let edges: Vec<_> = self
+                    .edges
+                    .iter()
+                    .map(|(dependent, dependency)| {
+                        json!({
+                            "from": node_label(dependent),
+                            "to": dependency.name(),
+                        })
+                    })
+                    .collect();
+                let graph = json!({ "nodes": nodes, "edges": edges });
+                writeln!(out, "{graph}")
+            }
+        }
+    }
+}
+
+/// The formats `Bindings::write_dependency_graph` can write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DependencyGraphFormat {
+    Dot,
+    Json,
+}
+
+impl FromStr for DependencyGraphFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "dot" => Ok(DependencyGraphFormat::Dot),
+            "json" => Ok(DependencyGraphFormat::Json),
+            _ => Err(format!("Unrecognized dependency graph format: '{s}'.")),
+        }
+    }
+}
+
+fn node_label(node: &DependencyNode) -> String {
+    match *node {
+        DependencyNode::Function(ref path) => format!("fn {path}"),
+        DependencyNode::Global(ref path) => format!("static {path}"),
+        DependencyNode::Constant(ref path) => format!("const {path}"),
+        DependencyNode::ExportInclude => "export.include".to_owned(),
+        DependencyNode::ExportInstantiate => "export.instantiate".to_owned(),
+        DependencyNode::Item(ref path) => path.name().to_owned(),
+    }
+}
+
+fn node_kind(node: &DependencyNode) -> &'static str {
+    match *node {
+        DependencyNode::Function(..) => "function",
+        DependencyNode::Global(..) => "global",
+        DependencyNode::Constant(..) => "constant",
+        DependencyNode::ExportInclude => "export-include",
+        DependencyNode::ExportInstantiate => "export-instantiate",
+        DependencyNode::Item(..) => "item",
+    }
+}
+
+fn item_type_name(item_type: &ItemType) -> &'static str {
+    match *item_type {
+        ItemType::Constants => "a constant",
+        ItemType::Globals => "a static",
+        ItemType::Enums => "an enum",
+        ItemType::Structs => "a struct",
+        ItemType::Unions => "a union",
+        ItemType::Typedefs => "a type alias",
+        ItemType::OpaqueItems => "an opaque type",
+        ItemType::Functions => "a function",
+    }
+}
+
+fn item_type_config_name(item_type: &ItemType) -> &'static str {
+    match *item_type {
+        ItemType::Constants => "constants",
+        ItemType::Globals => "globals",
+        ItemType::Enums => "enums",
+        ItemType::Structs => "structs",
+        ItemType::Unions => "unions",
+        ItemType::Typedefs => "typedefs",
+        ItemType::OpaqueItems => "opaque",
+        ItemType::Functions => "functions",
+    }
+}
+
+fn describe_location(location: &Option<SourceLocation>) -> String {
+    match *location {
+        Some(ref location) => format!(
+            " at {}:{}:{}",
+            location.file.display(),
+            location.line,
+            location.column
+        ),
+        None => String::new(),
+    }
+}
+
+/// Describes whether the item named `name` was found, and why it is or isn't
+/// part of `bindings`.
+pub(crate) fn explain(bindings: &Bindings, name: &str) -> String {
+    let provenance = &bindings.provenance;
+    let config = &bindings.config;
+    let mut out = String::new();
+
+    let sources: Vec<_> = provenance
+        .sources
+        .iter()
+        .filter(|x| x.name == name)
+        .collect();
+    let ignored: Vec<_> = provenance
+        .ignored
+        .iter()
+        .filter(|x| x.name == name)
+        .collect();
+    let suffix = format!("::{name}");
+    let skipped: Vec<_> = bindings
+        .diagnostics
+        .iter()
+        .filter(|x| {
+            x.item
+                .as_deref()
+                .is_some_and(|item| item == name || item.ends_with(&suffix))
+        })
+        .collect();
+
+    if sources.is_empty() && ignored.is_empty() && skipped.is_empty() {
+        let _ = writeln!(out, "`{name}` was not found in any parsed crate.");
+        return out;
+    }
+
+    for source in &ignored {
+        let _ = writeln!(
+            out,
+            "`{name}` is {} in crate `{}`{}, but was ignored because of a \
+             `cbindgen:ignore` annotation or a test attribute.",
+            item_type_name(&source.item_type),
+            source.crate_name,
+            describe_location(&source.location),
+        );
+    }
+
+    for diagnostic in &skipped {
+        let _ = writeln!(
+            out,
+            "`{}`{}: {}",
+            diagnostic.item.as_deref().unwrap_or(name),
+            describe_location(&diagnostic.location),
+            diagnostic.message
+        );
+    }
+
+    for source in &sources {
+        let _ = writeln!(
+            out,
+            "`{name}` is {} from crate `{}`{}.",
+            item_type_name(&source.item_type),
+            source.crate_name,
+            describe_location(&source.location),
+        );
+        if let Some(ref note) = source.note {
+            let _ = writeln!(out, "  It is emitted as an opaque type: {note}");
+        }
+
+        let modules = [(source.crate_name.as_str(), source.module.as_slice())];
+        let excluded = NamePatterns::new("export.exclude", &config.export.exclude)
+            .is_ok_and(|exclude| exclude.matches(name, &modules));
+        if excluded {
+            let _ = writeln!(out, "  It is excluded by `export.exclude`.");
+            continue;
+        }
+        if !config.export.should_generate(source.item_type.clone()) {
+            let _ = writeln!(
+                out,
+                "  It is excluded because `export.item_types` doesn't contain `{}`.",
+                item_type_config_name(&source.item_type)
+            );
+            continue;
+        }
+
+        let exported = match source.item_type {
+            ItemType::Functions => bindings
+                .functions
+                .iter()
+                .find(|x| x.path.name() == name)
+                .map(|x| x.annotations.should_export()),
+            ItemType::Globals => bindings
+                .globals
+                .iter()
+                .find(|x| x.path.name() == name)
+                .map(|x| x.annotations.should_export()),
+            ItemType::Constants => bindings
+                .constants
+                .iter()
+                .find(|x| x.path.name() == name)
+                .map(|_| true),
+            _ => {
+                let exported = bindings
+                    .items
+                    .iter()
+                    .find(|x| x.deref().path().name() == name)
+                    .map(|x| x.deref().annotations().should_export());
+                match provenance.find_chain(name) {
+                    Some(chain) if exported.is_some() => {
+                        let chain: Vec<_> = chain
+                            .iter()
+                            .map(|node| match **node {
+                                DependencyNode::Item(ref path) => format!("`{path}`"),
+                                ref root => format!("`{}`", node_label(root)),
+                            })
+                            .chain(std::iter::once(format!("`{name}`")))
+                            .collect();
+                        let _ = writeln!(out, "  It is included via {}.", chain.join(" -> "));
+                    }
+                    _ => {}
+                }
+                exported
+            }
+        };
+
+        match exported {
+            Some(true) => {
+                let _ = writeln!(out, "  It is part of the bindings.");
+            }
+            Some(false) => {
+                let _ = writeln!(
+                    out,
+                    "  It is known, but not emitted because of its `cbindgen:no-export` annotation."
+                );
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "  It is not part of the bindings: it isn't used by any exported function, \
+                     global or constant, and isn't listed in `export.include`."
+                );
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortest_chain_to_root() {
+        let item = |name: &str| DependencyNode::Item(Path::new(name));
+        let provenance = Provenance {
+            edges: vec![
+                (DependencyNode::Function(Path::new("f")), Path::new("A")),
+                (item("A"), Path::new("B")),
+                (item("B"), Path::new("C")),
+                (DependencyNode::ExportInclude, Path::new("B")),
+            ],
+            ..Default::default()
+        };
+
+        let chain = provenance.find_chain("C").unwrap();
+        let labels: Vec<_> = chain.iter().map(|node| node_label(node)).collect();
+        assert_eq!(labels, ["export.include", "B"]);
+        assert!(provenance.find_chain("f").is_none());
+    }
+}
@@ -102,47 +102,100 @@ impl FromStr for RenameRule {
 
     fn from_str(s: &str) -> Result<RenameRule, Self::Err> {
         const PREFIX: &str = "prefix:";
-        const PREFIX_LEN: usize = PREFIX.len();
 
-        match s {
-            "none" => Ok(RenameRule::None),
-            "None" => Ok(RenameRule::None),
-
-            "mGeckoCase" => Ok(RenameRule::GeckoCase),
-            "GeckoCase" => Ok(RenameRule::GeckoCase),
-            "gecko_case" => Ok(RenameRule::GeckoCase),
-
-            "lowercase" => Ok(RenameRule::LowerCase),
-            "LowerCase" => Ok(RenameRule::LowerCase),
-            "lower_case" => Ok(RenameRule::LowerCase),
-
-            "UPPERCASE" => Ok(RenameRule::UpperCase),
-            "UpperCase" => Ok(RenameRule::UpperCase),
-            "upper_case" => Ok(RenameRule::UpperCase),
-
-            "PascalCase" => Ok(RenameRule::PascalCase),
-            "pascal_case" => Ok(RenameRule::PascalCase),
-
-            "camelCase" => Ok(RenameRule::CamelCase),
-            "CamelCase" => Ok(RenameRule::CamelCase),
-            "camel_case" => Ok(RenameRule::CamelCase),
-
-            "snake_case" => Ok(RenameRule::SnakeCase),
-            "SnakeCase" => Ok(RenameRule::SnakeCase),
-
-            "SCREAMING_SNAKE_CASE" => Ok(RenameRule::ScreamingSnakeCase),
-            "ScreamingSnakeCase" => Ok(RenameRule::ScreamingSnakeCase),
-            "screaming_snake_case" => Ok(RenameRule::ScreamingSnakeCase),
+        if let Some(prefix) = s.strip_prefix(PREFIX) {
+            return Ok(RenameRule::Prefix(prefix.to_owned()));
+        }
+        RenameRule::NAMES
+            .iter()
+            .find(|(name, _)| *name == s)
+            .map(|(_, rule)| rule.clone())
+            .ok_or_else(|| format!("Unrecognized RenameRule: '{s}'."))
+    }
+}
 
-            "QUALIFIED_SCREAMING_SNAKE_CASE" => Ok(RenameRule::QualifiedScreamingSnakeCase),
-            "QualifiedScreamingSnakeCase" => Ok(RenameRule::QualifiedScreamingSnakeCase),
-            "qualified_screaming_snake_case" => Ok(RenameRule::QualifiedScreamingSnakeCase),
+deserialize_enum_str!(RenameRule);
 
-            s if s.starts_with(PREFIX) => Ok(RenameRule::Prefix(s[PREFIX_LEN..].to_string())),
+impl RenameRule {
+    /// The names of the rules in config files, except for `prefix:`. Both
+    /// parsing and the JSON schema use this table.
+    const NAMES: &'static [(&'static str, RenameRule)] = &[
+        ("None", RenameRule::None),
+        ("none", RenameRule::None),
+        ("GeckoCase", RenameRule::GeckoCase),
+        ("mGeckoCase", RenameRule::GeckoCase),
+        ("gecko_case", RenameRule::GeckoCase),
+        ("LowerCase", RenameRule::LowerCase),
+        ("lowercase", RenameRule::LowerCase),
+        ("lower_case", RenameRule::LowerCase),
+        ("UpperCase", RenameRule::UpperCase),
+        ("UPPERCASE", RenameRule::UpperCase),
+        ("upper_case", RenameRule::UpperCase),
+        ("PascalCase", RenameRule::PascalCase),
+        ("pascal_case", RenameRule::PascalCase),
+        ("CamelCase", RenameRule::CamelCase),
+        ("camelCase", RenameRule::CamelCase),
+        ("camel_case", RenameRule::CamelCase),
+        ("SnakeCase", RenameRule::SnakeCase),
+        ("snake_case", RenameRule::SnakeCase),
+        ("ScreamingSnakeCase", RenameRule::ScreamingSnakeCase),
+        ("SCREAMING_SNAKE_CASE", RenameRule::ScreamingSnakeCase),
+        ("screaming_snake_case", RenameRule::ScreamingSnakeCase),
+        (
+            "QualifiedScreamingSnakeCase",
+            RenameRule::QualifiedScreamingSnakeCase,
+        ),
+        (
+            "QUALIFIED_SCREAMING_SNAKE_CASE",
+            RenameRule::QualifiedScreamingSnakeCase,
+        ),
+        (
+            "qualified_screaming_snake_case",
+            RenameRule::QualifiedScreamingSnakeCase,
+        ),
+    ];
+}
 
-            _ => Err(format!("Unrecognized RenameRule: '{s}'.")),
+impl serde::Serialize for RenameRule {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            RenameRule::None => serializer.serialize_str("None"),
+            RenameRule::GeckoCase => serializer.serialize_str("GeckoCase"),
+            RenameRule::LowerCase => serializer.serialize_str("LowerCase"),
+            RenameRule::UpperCase => serializer.serialize_str("UpperCase"),
+            RenameRule::PascalCase => serializer.serialize_str("PascalCase"),
+            RenameRule::CamelCase => serializer.serialize_str("CamelCase"),
+            RenameRule::SnakeCase => serializer.serialize_str("SnakeCase"),
+            RenameRule::ScreamingSnakeCase => serializer.serialize_str("ScreamingSnakeCase"),
+            RenameRule::QualifiedScreamingSnakeCase => {
+                serializer.serialize_str("QualifiedScreamingSnakeCase")
+            }
+            RenameRule::Prefix(prefix) => serializer.serialize_str(&format!("prefix:{prefix}")),
         }
     }
 }
 
-deserialize_enum_str!(RenameRule);
+impl schemars::JsonSchema for RenameRule {
+    fn schema_name() -> Cow<'static, str> {
+        "RenameRule".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "anyOf": [
+                {
+                    "type": "string",
+                    "enum": RenameRule::NAMES.iter().map(|(name, _)| *name).collect::<Vec<_>>(),
+                },
+                {
+                    "type": "string",
+                    "pattern": "^prefix:",
+                    "description": "Adds the given prefix, for example `prefix:m_`.",
+                },
+            ],
+        })
+    }
+}
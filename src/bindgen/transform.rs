@@ -0,0 +1,432 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+use std::mem;
+use std::sync::{Arc, Mutex};
+
+use crate::bindgen::bindings::Bindings;
+use crate::bindgen::config::{Config, ItemType};
+use crate::bindgen::ir::{
+    AnnotationSet, Constant, Documentation, Function, Item, ItemContainer, Path, Static,
+    VariantBody,
+};
+
+/// An item of the bindings, as seen by a transform.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IrItem {
+    /// The name the item is written as.
+    pub name: String,
+    /// The name of the item in the Rust source.
+    pub rust_name: String,
+    pub kind: ItemType,
+}
+
+/// A mutable view of the bindings, after renaming and monomorphization but
+/// before they are written, handed to the transforms registered with
+/// `Builder::with_transform`.
+///
+/// Items are looked up by the name they are written as. Names derived from an
+/// item's name before the transform runs, like the prefixes of enum variants,
+/// aren't changed by renaming it, and annotations only have an effect if they
+/// are read while writing the bindings.
+pub struct IrView<'a> {
+    bindings: &'a mut Bindings,
+}
+
+#[allow(unused)]
+impl<'a> IrView<'a> {
+    pub(crate) fn new(bindings: &'a mut Bindings) -> Self {
+        IrView { bindings }
+    }
+
+    /// The config the bindings are written with.
+    pub fn config(&self) -> &Config {
+        &self.bindings.config
+    }
+
+    /// All the items of the bindings, in the order they are written in.
+    pub fn items(&self) -> Vec<IrItem> {
+        self.entries().map(|entry| entry.info()).collect()
+    }
+
+    /// Renames an item, along with all the references to it. Returns whether
+    /// the item was found. Functions and globals can't be renamed, as their
+    /// names are the symbols the library exports.
+    pub fn rename(&mut self, name: &str, new_name: &str) -> Result<bool, String> {
+        match self.position(name) {
+            Some(index) => self.rename_at(index, new_name),
+            None => Ok(false),
+        }
+    }
+
+    /// Like `rename`, for the item at `index` in `items()`. Unlike names,
+    /// indices tell apart the items that share a name, like the `cfg`
+    /// variants of an item.
+    pub(crate) fn rename_at(&mut self, index: usize, new_name: &str) -> Result<bool, String> {
+        let path = match self.entry_at(index, |entry| entry.rename(new_name)) {
+            Some(path) => path?,
+            None => return Ok(false),
+        };
+
+        let bindings = &mut *self.bindings;
+        for constant in &mut bindings.constants {
+            rename_references_in_constant(constant, &path, new_name);
+        }
+        for global in &mut bindings.globals {
+            global.ty.rename_references(&path, new_name);
+        }
+        for item in &mut bindings.items {
+            match *item {
+                ItemContainer::Constant(ref mut constant) => {
+                    rename_references_in_constant(constant, &path, new_name)
+                }
+                ItemContainer::Static(ref mut global) => {
+                    global.ty.rename_references(&path, new_name)
+                }
+                ItemContainer::OpaqueItem(..) => {}
+                ItemContainer::Struct(ref mut item) => {
+                    for field in &mut item.fields {
+                        field.ty.rename_references(&path, new_name);
+                    }
+                    for constant in &mut item.associated_constants {
+                        rename_references_in_constant(constant, &path, new_name);
+                    }
+                }
+                ItemContainer::Union(ref mut item) => {
+                    for field in &mut item.fields {
+                        field.ty.rename_references(&path, new_name);
+                    }
+                }
+                ItemContainer::Enum(ref mut item) => {
+                    for variant in &mut item.variants {
+                        if let VariantBody::Body { ref mut body, .. } = variant.body {
+                            for field in &mut body.fields {
+                                field.ty.rename_references(&path, new_name);
+                            }
+                        }
+                    }
+                }
+                ItemContainer::Typedef(ref mut item) => {
+                    item.aliased.rename_references(&path, new_name)
+                }
+            }
+        }
+        for function in &mut bindings.functions {
+            function.ret.rename_references(&path, new_name);
+            for arg in &mut function.args {
+                arg.ty.rename_references(&path, new_name);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Removes an item from the bindings. References to it are left as they
+    /// are. Returns whether the item was found.
+    pub fn remove(&mut self, name: &str) -> bool {
+        let bindings = &mut *self.bindings;
+        let mut found = false;
+        let mut keep = |entry: Entry| {
+            let matches = !found && entry.name() == name;
+            found |= matches;
+            !matches
+        };
+        bindings.constants.retain(|x| keep(Entry::Constant(x)));
+        bindings.globals.retain(|x| keep(Entry::Global(x)));
+        bindings.items.retain(|x| keep(Entry::Item(x)));
+        bindings.functions.retain(|x| keep(Entry::Function(x)));
+        found
+    }
+
+    /// Keeps only the items at the given indices of `items()`, ordered by the
+    /// position each index maps to within their section.
+    pub(crate) fn select(&mut self, order: &HashMap<usize, usize>) {
+        let bindings = &mut *self.bindings;
+        let mut index = 0;
+        select_section(&mut bindings.constants, &mut index, order);
+        select_section(&mut bindings.globals, &mut index, order);
+        select_section(&mut bindings.items, &mut index, order);
+        select_section(&mut bindings.functions, &mut index, order);
+    }
+
+    /// The documentation of an item, one line per element.
+    pub fn documentation(&self, name: &str) -> Option<&[String]> {
+        self.documentation_at(self.position(name)?)
+    }
+
+    /// Like `documentation`, for the item at `index` in `items()`.
+    pub(crate) fn documentation_at(&self, index: usize) -> Option<&[String]> {
+        self.entries()
+            .nth(index)
+            .map(|entry| &entry.documentation().doc_comment[..])
+    }
+
+    /// Replaces the documentation of an item. Returns whether the item was
+    /// found.
+    pub fn set_documentation(&mut self, name: &str, lines: Vec<String>) -> bool {
+        match self.position(name) {
+            Some(index) => self.set_documentation_at(index, lines),
+            None => false,
+        }
+    }
+
+    /// Like `set_documentation`, for the item at `index` in `items()`.
+    pub(crate) fn set_documentation_at(&mut self, index: usize, lines: Vec<String>) -> bool {
+        self.entry_at(index, |entry| entry.documentation().doc_comment = lines)
+            .is_some()
+    }
+
+    /// Adds an annotation to an item, written like a `cbindgen:` doc comment
+    /// line without the prefix, e.g. `derive-eq` or `prefix=FOO_`. Returns
+    /// whether the item was found.
+    pub fn annotate(&mut self, name: &str, annotation: &str) -> Result<bool, String> {
+        match self.position(name) {
+            Some(index) => self.annotate_at(index, annotation),
+            None => Ok(false),
+        }
+    }
+
+    /// Like `annotate`, for the item at `index` in `items()`.
+    pub(crate) fn annotate_at(&mut self, index: usize, annotation: &str) -> Result<bool, String> {
+        self.entry_at(index, |entry| entry.annotations().add(annotation))
+            .transpose()
+            .map(|found| found.is_some())
+    }
+
+    /// Sorts the items of the bindings. Constants, globals, types and functions
+    /// are written in separate sections and are only reordered within them.
+    /// Types are written in dependency order, so moving a type before one it
+    /// uses by value can produce a header that doesn't compile.
+    pub fn sort_by(&mut self, mut compare: impl FnMut(&IrItem, &IrItem) -> Ordering) {
+        let bindings = &mut *self.bindings;
+        sort_section(
+            &mut bindings.constants,
+            |x| Entry::Constant(x).info(),
+            &mut compare,
+        );
+        sort_section(
+            &mut bindings.globals,
+            |x| Entry::Global(x).info(),
+            &mut compare,
+        );
+        sort_section(&mut bindings.items, |x| Entry::Item(x).info(), &mut compare);
+        sort_section(
+            &mut bindings.functions,
+            |x| Entry::Function(x).info(),
+            &mut compare,
+        );
+    }
+
+    fn entries(&self) -> impl Iterator<Item = Entry<'_>> {
+        let bindings = &*self.bindings;
+        let constants = bindings.constants.iter().map(Entry::Constant);
+        let globals = bindings.globals.iter().map(Entry::Global);
+        let items = bindings.items.iter().map(Entry::Item);
+        let functions = bindings.functions.iter().map(Entry::Function);
+        constants.chain(globals).chain(items).chain(functions)
+    }
+
+    /// The item at `index` in `items()`.
+    pub(crate) fn entry(&self, index: usize) -> Option<Entry<'_>> {
+        self.entries().nth(index)
+    }
+
+    /// The index of the first item with the given name.
+    fn position(&self, name: &str) -> Option<usize> {
+        self.entries().position(|entry| entry.name() == name)
+    }
+
+    pub(crate) fn entry_at<T>(&mut self, index: usize, f: impl FnOnce(EntryMut) -> T) -> Option<T> {
+        let bindings = &mut *self.bindings;
+        let constants = bindings.constants.iter_mut().map(EntryMut::Constant);
+        let globals = bindings.globals.iter_mut().map(EntryMut::Global);
+        let items = bindings.items.iter_mut().map(EntryMut::Item);
+        let functions = bindings.functions.iter_mut().map(EntryMut::Function);
+        constants
+            .chain(globals)
+            .chain(items)
+            .chain(functions)
+            .nth(index)
+            .map(f)
+    }
+}
+
+fn select_section<T>(section: &mut Vec<T>, index: &mut usize, order: &HashMap<usize, usize>) {
+    let mut kept: Vec<_> = mem::take(section)
+        .into_iter()
+        .filter_map(|x| {
+            let position = order.get(index).copied();
+            *index += 1;
+            Some((position?, x))
+        })
+        .collect();
+    kept.sort_by_key(|(position, _)| *position);
+    section.extend(kept.into_iter().map(|(_, x)| x));
+}
+
+fn rename_references_in_constant(constant: &mut Constant, path: &Path, export_name: &str) {
+    constant.ty.rename_references(path, export_name);
+    constant.value.rename_references(path, export_name);
+}
+
+fn sort_section<T>(
+    section: &mut Vec<T>,
+    info: impl Fn(&T) -> IrItem,
+    compare: &mut impl FnMut(&IrItem, &IrItem) -> Ordering,
+) {
+    let mut keyed: Vec<_> = mem::take(section)
+        .into_iter()
+        .map(|x| (info(&x), x))
+        .collect();
+    keyed.sort_by(|a, b| compare(&a.0, &b.0));
+    section.extend(keyed.into_iter().map(|(_, x)| x));
+}
+
+pub(crate) enum Entry<'a> {
+    Constant(&'a Constant),
+    Global(&'a Static),
+    Item(&'a ItemContainer),
+    Function(&'a Function),
+}
+
+impl<'a> Entry<'a> {
+    fn documentation(&self) -> &'a Documentation {
+        match *self {
+            Entry::Constant(x) => x.documentation(),
+            Entry::Global(x) => x.documentation(),
+            Entry::Item(x) => x.deref().documentation(),
+            Entry::Function(x) => &x.documentation,
+        }
+    }
+
+    fn name(&self) -> &'a str {
+        match *self {
+            Entry::Constant(x) => x.export_name(),
+            Entry::Global(x) => x.export_name(),
+            Entry::Item(x) => x.deref().export_name(),
+            Entry::Function(x) => x.path.name(),
+        }
+    }
+
+    fn info(&self) -> IrItem {
+        let (rust_name, kind) = match *self {
+            Entry::Constant(x) => (x.path.name(), ItemType::Constants),
+            Entry::Global(x) => (x.path.name(), ItemType::Globals),
+            Entry::Item(x) => (
+                x.deref().name(),
+                match *x {
+                    ItemContainer::Constant(..) => ItemType::Constants,
+                    ItemContainer::Static(..) => ItemType::Globals,
+                    ItemContainer::OpaqueItem(..) => ItemType::OpaqueItems,
+                    ItemContainer::Struct(..) => ItemType::Structs,
+                    ItemContainer::Union(..) => ItemType::Unions,
+                    ItemContainer::Enum(..) => ItemType::Enums,
+                    ItemContainer::Typedef(..) => ItemType::Typedefs,
+                },
+            ),
+            Entry::Function(x) => (x.path.name(), ItemType::Functions),
+        };
+        IrItem {
+            name: self.name().to_owned(),
+            rust_name: rust_name.to_owned(),
+            kind,
+        }
+    }
+}
+
+pub(crate) enum EntryMut<'a> {
+    Constant(&'a mut Constant),
+    Global(&'a mut Static),
+    Item(&'a mut ItemContainer),
+    Function(&'a mut Function),
+}
+
+impl<'a> EntryMut<'a> {
+    /// Renames the item and returns the path references to it use.
+    fn rename(self, new_name: &str) -> Result<Path, String> {
+        let symbol = |name: &str| {
+            format!("`{name}` can't be renamed, its name is the symbol the library exports")
+        };
+        match self {
+            EntryMut::Constant(x) => {
+                new_name.clone_into(&mut x.export_name);
+                Ok(x.path.clone())
+            }
+            EntryMut::Global(x) => Err(symbol(&x.export_name)),
+            EntryMut::Item(x) => {
+                let export_name = match *x {
+                    ItemContainer::Constant(ref mut x) => &mut x.export_name,
+                    ItemContainer::Static(ref x) => return Err(symbol(&x.export_name)),
+                    ItemContainer::OpaqueItem(ref mut x) => &mut x.export_name,
+                    ItemContainer::Struct(ref mut x) => &mut x.export_name,
+                    ItemContainer::Union(ref mut x) => &mut x.export_name,
+                    ItemContainer::Enum(ref mut x) => &mut x.export_name,
+                    ItemContainer::Typedef(ref mut x) => &mut x.export_name,
+                };
+                new_name.clone_into(export_name);
+                Ok(x.deref().path().clone())
+            }
+            EntryMut::Function(x) => Err(symbol(x.path.name())),
+        }
+    }
+
+    fn documentation(self) -> &'a mut Documentation {
+        match self {
+            EntryMut::Constant(x) => &mut x.documentation,
+            EntryMut::Global(x) => &mut x.documentation,
+            EntryMut::Item(x) => match *x {
+                ItemContainer::Constant(ref mut x) => &mut x.documentation,
+                ItemContainer::Static(ref mut x) => &mut x.documentation,
+                ItemContainer::OpaqueItem(ref mut x) => &mut x.documentation,
+                ItemContainer::Struct(ref mut x) => &mut x.documentation,
+                ItemContainer::Union(ref mut x) => &mut x.documentation,
+                ItemContainer::Enum(ref mut x) => &mut x.documentation,
+                ItemContainer::Typedef(ref mut x) => &mut x.documentation,
+            },
+            EntryMut::Function(x) => &mut x.documentation,
+        }
+    }
+
+    fn annotations(self) -> &'a mut AnnotationSet {
+        match self {
+            EntryMut::Constant(x) => &mut x.annotations,
+            EntryMut::Global(x) => &mut x.annotations,
+            EntryMut::Item(x) => match *x {
+                ItemContainer::Constant(ref mut x) => &mut x.annotations,
+                ItemContainer::Static(ref mut x) => &mut x.annotations,
+                ItemContainer::OpaqueItem(ref mut x) => &mut x.annotations,
+                ItemContainer::Struct(ref mut x) => &mut x.annotations,
+                ItemContainer::Union(ref mut x) => &mut x.annotations,
+                ItemContainer::Enum(ref mut x) => &mut x.annotations,
+                ItemContainer::Typedef(ref mut x) => &mut x.annotations,
+            },
+            EntryMut::Function(x) => &mut x.annotations,
+        }
+    }
+}
+
+type TransformFn = dyn FnMut(&mut IrView) + Send;
+
+/// A transform registered with `Builder::with_transform`.
+#[derive(Clone)]
+pub(crate) struct Transform(Arc<Mutex<TransformFn>>);
+
+impl Transform {
+    pub(crate) fn new(transform: impl FnMut(&mut IrView) + Send + 'static) -> Self {
+        Transform(Arc::new(Mutex::new(transform)))
+    }
+
+    pub(crate) fn apply(&self, bindings: &mut Bindings) {
+        let mut transform = self.0.lock().unwrap();
+        (*transform)(&mut IrView::new(bindings))
+    }
+}
+
+impl fmt::Debug for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Transform")
+    }
+}
@@ -24,7 +24,8 @@ pub use crate::bindgen::*;
 use std::path::Path;
 
 /// A utility function for build scripts to generate bindings for a crate, using
-/// a `cbindgen.toml` if it exists.
+/// a `cbindgen.toml` or the `[package.metadata.cbindgen]` of its `Cargo.toml` if
+/// either exists.
 pub fn generate<P: AsRef<Path>>(crate_dir: P) -> Result<Bindings, Error> {
     let config = Config::from_root_or_default(crate_dir.as_ref());
 
@@ -6,6 +6,9 @@ use std::io;
 use std::io::Write;
 
 use log::*;
+use serde_json::json;
+
+use crate::bindgen::{Diagnostic, DiagnosticLevel, DIAGNOSTIC_LOG_TARGET};
 
 pub struct TraceLogger;
 pub struct WarnLogger;
@@ -103,3 +106,116 @@ impl log::Log for InfoLogger {
         io::stderr().flush().unwrap();
     }
 }
+
+/// Logs messages as JSON objects, one per line, in the same shape as the
+/// messages of `cargo build --message-format=json`.
+///
+/// Diagnostics are not logged as they happen, but reported through
+/// `JsonLogger::report` once generation is done, so that they carry their
+/// location, reason code and suggested fix.
+pub struct JsonLogger;
+
+impl JsonLogger {
+    pub fn init(level: LevelFilter) -> Result<(), SetLoggerError> {
+        log::set_logger(&JsonLogger)?;
+        log::set_max_level(level);
+        Ok(())
+    }
+
+    /// Prints a diagnostic collected during generation.
+    pub fn report(diagnostic: &Diagnostic) {
+        // The diagnostics skip the logger, so `-q` has to be checked here.
+        let level = match diagnostic.level {
+            DiagnosticLevel::Warning => Level::Warn,
+            DiagnosticLevel::Error => Level::Error,
+        };
+        if level > log::max_level() {
+            return;
+        }
+        let spans = match diagnostic.location {
+            Some(ref location) => vec![json!({
+                "file_name": location.file,
+                "line_start": location.line,
+                "line_end": location.end_line,
+                "column_start": location.column,
+                "column_end": location.end_column,
+                "is_primary": true,
+                "text": [],
+                "label": null,
+                "suggested_replacement": null,
+                "suggestion_applicability": null,
+                "expansion": null,
+            })],
+            None => vec![],
+        };
+        let children = match diagnostic.help {
+            Some(ref help) => vec![json!({
+                "message": help,
+                "code": null,
+                "level": "help",
+                "spans": [],
+                "children": [],
+                "rendered": null,
+            })],
+            None => vec![],
+        };
+        Self::emit(
+            json!({
+                "$message_type": "diagnostic",
+                "message": diagnostic.message,
+                "code": {
+                    "code": diagnostic.code.as_str(),
+                    "explanation": null,
+                },
+                "level": diagnostic.level.as_str(),
+                "spans": spans,
+                "children": children,
+                "rendered": diagnostic.to_string(),
+            }),
+            diagnostic.item.as_deref(),
+        );
+    }
+
+    fn emit(message: serde_json::Value, item: Option<&str>) {
+        let line = json!({
+            "reason": "cbindgen-message",
+            "item": item,
+            "message": message,
+        });
+        eprintln!("{line}");
+    }
+}
+
+impl log::Log for JsonLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level() && metadata.target() != DIAGNOSTIC_LOG_TARGET
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        let level = match record.level() {
+            Level::Error => "error",
+            Level::Warn => "warning",
+            Level::Info | Level::Debug | Level::Trace => "note",
+        };
+        let message = record.args().to_string();
+        Self::emit(
+            json!({
+                "$message_type": "diagnostic",
+                "message": message,
+                "code": null,
+                "level": level,
+                "spans": [],
+                "children": [],
+                "rendered": format!("{level}: {message}"),
+            }),
+            None,
+        );
+    }
+
+    fn flush(&self) {
+        io::stderr().flush().unwrap();
+    }
+}
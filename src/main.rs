@@ -25,7 +25,87 @@ use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
 mod bindgen;
 mod logging;
 
-use bindgen::{Bindings, Builder, Cargo, Config, Error};
+use bindgen::{Bindings, Builder, Cargo, Config, DependencyGraphFormat, Error};
+
+fn apply_overlays(config: &mut Config, matches: &ArgMatches) -> Result<(), Error> {
+    for overlay in matches.get_many::<String>("overlay").into_iter().flatten() {
+        config
+            .apply_overlay(overlay)
+            .map_err(|e| Error::InvalidConfig(vec![e]))?;
+    }
+    Ok(())
+}
+
+/// Applies the `CBINDGEN_*` environment variables and then the `--set` options
+/// to the config. `CBINDGEN_EXPORT__PREFIX=MY_` is the same as
+/// `--set export.prefix=MY_`.
+fn apply_key_overrides(config: &mut Config, matches: &ArgMatches) -> Result<(), Error> {
+    // Other tools use `CBINDGEN_` variables too, so only the ones naming a
+    // config option are applied.
+    let schema = Config::schema();
+    let mut overrides: Vec<_> = env::vars_os()
+        .filter_map(|(name, value)| {
+            let name = name.into_string().ok()?;
+            let key = name
+                .strip_prefix("CBINDGEN_")?
+                .to_lowercase()
+                .replace("__", ".");
+            if !is_config_option(&schema, &key) {
+                info!("Ignoring `{name}`, which doesn't name a config option.");
+                return None;
+            }
+            match value.into_string() {
+                Ok(value) => Some(format!("{key}={value}")),
+                Err(_) => {
+                    warn!("Ignoring `{name}`, whose value isn't valid UTF-8.");
+                    None
+                }
+            }
+        })
+        .collect();
+    overrides.sort();
+    overrides.extend(
+        matches
+            .get_many::<String>("set")
+            .into_iter()
+            .flatten()
+            .cloned(),
+    );
+
+    config
+        .apply_overrides(overrides)
+        .map_err(|e| Error::InvalidConfig(vec![e]))
+}
+
+/// Whether the dotted `key` names an option of the config described by
+/// `schema`, or an entry of one of its tables that take any key, like
+/// `defines`.
+fn is_config_option(schema: &serde_json::Value, key: &str) -> bool {
+    let mut node = schema;
+    for part in key.split('.') {
+        // Follow the references to the definitions of nested tables.
+        while let Some(reference) = node
+            .get("$ref")
+            .or_else(|| node.get("allOf")?.get(0)?.get("$ref"))
+            .and_then(|reference| reference.as_str())
+        {
+            let name = reference.trim_start_matches("#/definitions/");
+            node = &schema["definitions"][name];
+        }
+        node = match node.get("properties") {
+            Some(properties) => match properties.get(part) {
+                Some(property) => property,
+                None => return false,
+            },
+            None => {
+                return node
+                    .get("additionalProperties")
+                    .is_some_and(|additional| *additional != false)
+            }
+        };
+    }
+    true
+}
 
 fn apply_config_overrides(config: &mut Config, matches: &ArgMatches) {
     // We allow specifying a language to override the config default. This is
@@ -78,9 +158,28 @@ fn apply_config_overrides(config: &mut Config, matches: &ArgMatches) {
     if matches.get_flag("d") {
         config.parse.parse_deps = true;
     }
+
+    if matches.get_flag("deny-warnings") {
+        config.parse.strict = true;
+    }
+
+    // Explaining a run that would fail on skipped items is more useful than
+    // failing.
+    let explain = matches.try_contains_id("ITEM").unwrap_or(false);
+    if explain {
+        config.parse.strict = false;
+    }
+
+    // Plugins only change the bindings that are written.
+    let graph_only = matches.contains_id("dependency-graph") && !matches.contains_id("out");
+    if explain || graph_only {
+        config.plugins.clear();
+    }
 }
 
-fn load_bindings(input: &Path, matches: &ArgMatches) -> Result<Bindings, Error> {
+/// Creates the builder for `input` and loads the config it should use, which
+/// is returned separately so that the caller can look at it first.
+fn load_builder(input: &Path, matches: &ArgMatches) -> Result<(Builder, Config), Error> {
     // If a file is specified then we load it as a single source
     if !input.is_dir() {
         // Load any config specified or search in the input directory
@@ -93,12 +192,11 @@ fn load_bindings(input: &Path, matches: &ArgMatches) -> Result<Bindings, Error>
             ),
         };
 
+        apply_overlays(&mut config, matches)?;
+        apply_key_overrides(&mut config, matches)?;
         apply_config_overrides(&mut config, matches);
 
-        return Builder::new()
-            .with_config(config)
-            .with_src(input)
-            .generate();
+        return Ok((Builder::new().with_src(input), config));
     }
 
     // We have to load a whole crate, so we use cargo to gather metadata
@@ -127,12 +225,11 @@ fn load_bindings(input: &Path, matches: &ArgMatches) -> Result<Bindings, Error>
         }
     };
 
+    apply_overlays(&mut config, matches)?;
+    apply_key_overrides(&mut config, matches)?;
     apply_config_overrides(&mut config, matches);
 
-    Builder::new()
-        .with_config(config)
-        .with_cargo(lib)
-        .generate()
+    Ok((Builder::new().with_cargo(lib), config))
 }
 
 fn main() {
@@ -159,6 +256,29 @@ fn main() {
                 .value_parser(value_parser!(PathBuf))
                 .help("Specify path to a `cbindgen.toml` config to use"),
         )
+        .arg(
+            Arg::new("overlay")
+                .long("overlay")
+                .value_name("NAME")
+                .action(ArgAction::Append)
+                .help(
+                    "Apply the `[overlay.NAME]` table of the config on top of the rest of \
+                    the config. May be given more than once.",
+                ),
+        )
+        .arg(
+            Arg::new("set")
+                .long("set")
+                .value_name("KEY=VALUE")
+                .action(ArgAction::Append)
+                .help(
+                    "Set any config option, as if it was written in the config file, for \
+                    example `--set export.prefix=MY_` or `--set enum.prefix_with_name=true`. \
+                    Values that aren't valid TOML are taken as strings. May be given more \
+                    than once. `CBINDGEN_<KEY>` environment variables, with `__` between \
+                    the parts of the key, work the same way.",
+                ),
+        )
         .arg(
             Arg::new("lang")
                 .short('l')
@@ -201,6 +321,26 @@ fn main() {
                 .action(ArgAction::SetTrue)
                 .help("Whether to parse dependencies when generating bindings"),
         )
+        .arg(
+            Arg::new("message-format")
+                .long("message-format")
+                .value_name("FORMAT")
+                .value_parser(["human", "json"])
+                .default_value("human")
+                .help(
+                    "How to print warnings and errors. `json` prints one JSON object per \
+                    line to stderr, in the same shape as cargo's `--message-format=json`.",
+                ),
+        )
+        .arg(
+            Arg::new("deny-warnings")
+                .long("deny-warnings")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Fail instead of generating bindings if any item is skipped or \
+                    any annotation is unknown or misplaced. Same as `parse.strict`.",
+                ),
+        )
         .arg(
             Arg::new("clean")
                 .long("clean")
@@ -298,6 +438,18 @@ fn main() {
                     This option is ignored if `--out` is missing."
                 )
         )
+        .arg(
+            Arg::new("dependency-graph")
+                .long("dependency-graph")
+                .value_name("FORMAT")
+                .value_parser(["dot", "json"])
+                .help(
+                    "Print the graph of which functions, globals, constants and \
+                    `export.include` entries pulled which items into the bindings \
+                    to stdout, instead of the bindings. The bindings are still \
+                    written if `--output` is given.",
+                ),
+        )
         .arg(
             Arg::new("symfile")
                 .value_name("PATH")
@@ -309,17 +461,63 @@ fn main() {
                     dynamic symbols. Useful when creating a plugin system with a C interface."
                 )
         )
+        .arg(
+            Arg::new("print-config-schema")
+                .long("print-config-schema")
+                .action(ArgAction::SetTrue)
+                .help(
+                    "Print the JSON schema of cbindgen.toml to stdout and exit. Editors \
+                    can use it to complete and validate config files.",
+                ),
+        )
+        .subcommand(
+            Command::new("explain")
+                .about(
+                    "Explain whether an item was found, where it comes from, and why it \
+                    is or isn't part of the bindings",
+                )
+                .arg(
+                    Arg::new("ITEM")
+                        .help("The name of the item to explain")
+                        .required(true)
+                        .index(1),
+                )
+                .arg(
+                    Arg::new("INPUT")
+                        .help("A crate directory or source file to generate bindings for")
+                        .required(false)
+                        .value_parser(value_parser!(PathBuf))
+                        .index(2),
+                ),
+        )
+        .mut_args(|arg| if arg.is_positional() { arg } else { arg.global(true) })
         .get_matches();
 
-    if matches.get_flag("verify") && !matches.contains_id("out") {
-        error!(
-            "Cannot verify bindings against `stdout`, please specify a file to compare against."
-        );
-        std::process::exit(2);
+    if matches.get_flag("print-config-schema") {
+        println!("{:#}", Config::schema());
+        return;
     }
 
+    let explain = matches.subcommand_matches("explain");
+    let matches = explain.unwrap_or(&matches);
+
+    let json_messages = matches
+        .get_one::<String>("message-format")
+        .is_some_and(|format| format == "json");
+
     // Initialize logging
-    if matches.get_flag("quiet") {
+    if json_messages {
+        let level = if matches.get_flag("quiet") {
+            log::LevelFilter::Error
+        } else {
+            match matches.get_count("v") {
+                0 => log::LevelFilter::Warn,
+                1 => log::LevelFilter::Info,
+                _ => log::LevelFilter::Trace,
+            }
+        };
+        logging::JsonLogger::init(level).unwrap();
+    } else if matches.get_flag("quiet") {
         logging::ErrorLogger::init().unwrap();
     } else {
         match matches.get_count("v") {
@@ -335,8 +533,43 @@ fn main() {
         .cloned()
         .unwrap_or_else(|| env::current_dir().unwrap());
 
-    let bindings = match load_bindings(&input, &matches) {
-        Ok(bindings) => bindings,
+    // Generate every `[[output]]` of the config, unless a single output is
+    // asked for.
+    let single =
+        matches.contains_id("out") || matches.contains_id("dependency-graph") || explain.is_some();
+    let outputs = load_builder(&input, matches).and_then(|(builder, config)| {
+        if single || config.output.is_empty() {
+            let bindings = builder.with_config(config).generate()?;
+            Ok(vec![(None, bindings)])
+        } else {
+            let outputs = builder.with_config(config).generate_many()?;
+            Ok(outputs
+                .into_iter()
+                .map(|(path, bindings)| (Some(path), bindings))
+                .collect())
+        }
+    });
+
+    let outputs = match outputs {
+        Ok(outputs) => {
+            if json_messages {
+                // The outputs share the parse, and so most of their
+                // diagnostics, which are only reported once.
+                let mut reported = Vec::new();
+                for diagnostic in outputs.iter().flat_map(|(_, b)| &b.diagnostics) {
+                    if !reported.contains(&diagnostic) {
+                        logging::JsonLogger::report(diagnostic);
+                        reported.push(diagnostic);
+                    }
+                }
+            }
+            outputs
+        }
+        Err(Error::Diagnostics(ref diagnostics)) if json_messages => {
+            diagnostics.iter().for_each(logging::JsonLogger::report);
+            error!("Couldn't generate bindings for {}.", input.display());
+            std::process::exit(1);
+        }
         Err(msg) => {
             error!("{msg}");
             error!("Couldn't generate bindings for {}.", input.display());
@@ -344,6 +577,28 @@ fn main() {
         }
     };
 
+    if outputs[0].0.is_some() {
+        write_outputs(&outputs, matches);
+        return;
+    }
+    let bindings = &outputs[0].1;
+
+    if let Some(item) = explain.and_then(|m| m.get_one::<String>("ITEM")) {
+        print!("{}", bindings.explain(item));
+        return;
+    }
+
+    if let Some(format) = matches.get_one::<String>("dependency-graph") {
+        let format = DependencyGraphFormat::from_str(format).unwrap();
+        if let Err(e) = bindings.write_dependency_graph(format, io::stdout()) {
+            error!("Couldn't write the dependency graph: {e}");
+            std::process::exit(1);
+        }
+        if !matches.contains_id("out") {
+            return;
+        }
+    }
+
     // Write the bindings file
     match matches.get_one::<PathBuf>("out") {
         Some(file) => {
@@ -361,7 +616,43 @@ fn main() {
             }
         }
         _ => {
+            if matches.get_flag("verify") {
+                error!(
+                    "Cannot verify bindings against `stdout`, please specify a file to compare \
+                     against."
+                );
+                std::process::exit(2);
+            }
+            if !bindings.plugin_files.is_empty() {
+                warn!("Plugins returned files that are only written along with `--output`.");
+            }
             bindings.write(io::stdout());
         }
     }
 }
+
+/// Writes the bindings of the `[[output]]` tables of the config to their paths.
+fn write_outputs(outputs: &[(Option<PathBuf>, Bindings)], matches: &ArgMatches) {
+    let mut changed = vec![];
+    for (path, bindings) in outputs {
+        let path = path.as_ref().unwrap();
+        if bindings.write_to_file(path) {
+            changed.push(path);
+        }
+    }
+
+    if matches.get_flag("verify") && !changed.is_empty() {
+        for path in changed {
+            error!("Bindings changed: {}", path.display());
+        }
+        std::process::exit(2);
+    }
+    let bindings = &outputs[0].1;
+    if let Some(depfile) = matches.get_one::<PathBuf>("depfile") {
+        let paths: Vec<_> = outputs.iter().flat_map(|(path, _)| path).collect();
+        bindings.generate_depfile_for_outputs(&paths, depfile);
+    }
+    if let Some(symfile) = matches.get_one::<String>("symfile") {
+        bindings.generate_symfile(symfile);
+    }
+}
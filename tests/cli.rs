@@ -0,0 +1,301 @@
+use std::path::Path;
+use std::process::{Command, Output};
+
+fn run_cbindgen(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_cbindgen"))
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .expect("cbindgen should run")
+}
+
+/// Writes a source file with a module that can't be found, which is only a
+/// warning unless warnings are denied.
+fn write_missing_module(dir: &Path) {
+    std::fs::write(
+        dir.join("lib.rs"),
+        "mod missing;\n#[no_mangle] pub extern \"C\" fn f() {}\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn deny_warnings() {
+    let dir = tempfile::tempdir().unwrap();
+    write_missing_module(dir.path());
+
+    let output = run_cbindgen(dir.path(), &["lib.rs"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("void f();"));
+
+    let output = run_cbindgen(dir.path(), &["--deny-warnings", "lib.rs"]);
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("can't find mod missing"), "{stderr}");
+}
+
+#[test]
+fn json_messages() {
+    let dir = tempfile::tempdir().unwrap();
+    write_missing_module(dir.path());
+
+    let output = run_cbindgen(dir.path(), &["--message-format", "json", "lib.rs"]);
+    assert!(output.status.success());
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let lines: Vec<serde_json::Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(lines.len(), 1, "{stderr}");
+
+    let line = &lines[0];
+    assert_eq!(line["reason"], "cbindgen-message");
+    assert_eq!(line["item"], "lib::missing");
+    let message = &line["message"];
+    assert_eq!(message["$message_type"], "diagnostic");
+    assert_eq!(message["level"], "warning");
+    assert_eq!(message["code"]["code"], "missing-module");
+    let span = &message["spans"][0];
+    assert_eq!(span["file_name"], "lib.rs");
+    assert_eq!(
+        (&span["line_start"], &span["column_start"]),
+        (&1.into(), &5.into())
+    );
+
+    let output = run_cbindgen(dir.path(), &["--message-format", "json", "-q", "lib.rs"]);
+    assert!(output.status.success());
+    assert!(output.stderr.is_empty());
+}
+
+/// Writes a source file with a function that pulls a struct into the
+/// bindings.
+fn write_struct_user(dir: &Path) {
+    std::fs::write(
+        dir.join("lib.rs"),
+        "#[repr(C)]\npub struct Foo { x: i32 }\n\
+         #[no_mangle] pub extern \"C\" fn f(foo: Foo) {}\n",
+    )
+    .unwrap();
+}
+
+#[test]
+fn explain() {
+    let dir = tempfile::tempdir().unwrap();
+    write_struct_user(dir.path());
+
+    let output = run_cbindgen(dir.path(), &["explain", "Foo", "lib.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "`Foo` is a struct from crate `lib` at lib.rs:2:12.\n  \
+         It is included via `fn f` -> `Foo`.\n  \
+         It is part of the bindings.\n"
+    );
+
+    let output = run_cbindgen(dir.path(), &["explain", "Bar", "lib.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "`Bar` was not found in any parsed crate.\n");
+}
+
+#[test]
+fn dependency_graph() {
+    let dir = tempfile::tempdir().unwrap();
+    write_struct_user(dir.path());
+
+    let output = run_cbindgen(dir.path(), &["--dependency-graph", "dot", "lib.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(
+        stdout,
+        "digraph dependencies {\n    \"fn f\" [shape=box];\n    \"fn f\" -> \"Foo\";\n}\n"
+    );
+
+    let output = run_cbindgen(dir.path(), &["--dependency-graph", "json", "lib.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    let graph: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        graph,
+        serde_json::json!({
+            "nodes": [
+                { "id": "fn f", "name": "f", "kind": "function" },
+                { "id": "Foo", "name": "Foo", "kind": "item" },
+            ],
+            "edges": [{ "from": "fn f", "to": "Foo" }],
+        })
+    );
+}
+
+#[test]
+fn json_messages_for_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "mod missing;\n/// cbindgen:bogus\n#[no_mangle] pub extern \"C\" fn f() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("cbindgen.toml"),
+        "[[output]]\npath = \"f.h\"\nlanguage = \"C\"\n\
+         [[output]]\npath = \"f.hpp\"\n",
+    )
+    .unwrap();
+
+    let output = run_cbindgen(
+        dir.path(),
+        &[
+            "--message-format",
+            "json",
+            "--config",
+            "cbindgen.toml",
+            "lib.rs",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    assert!(dir.path().join("f.h").exists());
+    assert!(dir.path().join("f.hpp").exists());
+    // The parse warning and the annotation warning of each output are only
+    // reported once.
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    let codes: Vec<_> = stderr
+        .lines()
+        .map(|line| {
+            let line: serde_json::Value = serde_json::from_str(line).unwrap();
+            line["message"]["code"]["code"].as_str().unwrap().to_owned()
+        })
+        .collect();
+    assert_eq!(codes, ["missing-module", "unknown-annotation"], "{stderr}");
+}
+
+#[test]
+fn warnings_for_outputs() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "/// cbindgen:bogus\n#[no_mangle] pub extern \"C\" fn f() {}\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("cbindgen.toml"),
+        "[[output]]\npath = \"f.h\"\nlanguage = \"C\"\n\
+         [[output]]\npath = \"f.hpp\"\n",
+    )
+    .unwrap();
+
+    let output = run_cbindgen(dir.path(), &["--config", "cbindgen.toml", "lib.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+    assert_eq!(stderr.matches("bogus").count(), 1, "{stderr}");
+}
+
+#[test]
+fn source_in_virtual_workspace() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+    std::fs::write(
+        dir.path().join("x.rs"),
+        "#[no_mangle] pub extern \"C\" fn f() {}\n",
+    )
+    .unwrap();
+
+    let output = run_cbindgen(dir.path(), &["x.rs"]);
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("void f();"));
+}
+
+#[test]
+fn env_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("lib.rs"),
+        "#[no_mangle] pub extern \"C\" fn f() {}\n",
+    )
+    .unwrap();
+
+    // Variables that don't name an option are ignored, however deep the key.
+    let output = Command::new(env!("CARGO_BIN_EXE_cbindgen"))
+        .current_dir(dir.path())
+        .env("CBINDGEN_LANGUAGE", "C")
+        .env("CBINDGEN_TEST_VERIFY", "1")
+        .env("CBINDGEN_EXPORT__BOGUS", "1")
+        .arg("-v")
+        .arg("lib.rs")
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "{output:?}");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("void f(void);"));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Ignoring `CBINDGEN_TEST_VERIFY`"),
+        "{stderr}"
+    );
+    assert!(
+        stderr.contains("Ignoring `CBINDGEN_EXPORT__BOGUS`"),
+        "{stderr}"
+    );
+
+    // Other variables don't need to be valid UTF-8.
+    #[cfg(unix)]
+    {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let output = Command::new(env!("CARGO_BIN_EXE_cbindgen"))
+            .current_dir(dir.path())
+            .env("UNRELATED", OsStr::from_bytes(b"\xff"))
+            .arg("lib.rs")
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_cbindgen"))
+        .current_dir(dir.path())
+        .env("CBINDGEN_LANGUAGE", "Rust")
+        .arg("lib.rs")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+}
+
+#[test]
+fn plugins_only_run_for_output() {
+    let dir = tempfile::tempdir().unwrap();
+    write_struct_user(dir.path());
+    std::fs::write(
+        dir.path().join("cbindgen.toml"),
+        "plugins = [\"./missing\"]\n",
+    )
+    .unwrap();
+
+    let output = run_cbindgen(dir.path(), &["--config", "cbindgen.toml", "lib.rs"]);
+    assert!(!output.status.success());
+
+    for args in [
+        &["explain", "Foo", "--config", "cbindgen.toml", "lib.rs"][..],
+        &[
+            "--dependency-graph",
+            "dot",
+            "--config",
+            "cbindgen.toml",
+            "lib.rs",
+        ],
+    ] {
+        let output = run_cbindgen(dir.path(), args);
+        assert!(output.status.success(), "{output:?}");
+    }
+
+    let args = [
+        "--dependency-graph",
+        "dot",
+        "--config",
+        "cbindgen.toml",
+        "--output",
+        "lib.h",
+        "lib.rs",
+    ];
+    let output = run_cbindgen(dir.path(), &args);
+    assert!(!output.status.success());
+}
@@ -0,0 +1,101 @@
+use cbindgen::Config;
+
+use serde_json::{Map, Value};
+use std::path::Path;
+
+#[test]
+fn published_schema_is_up_to_date() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("cbindgen.schema.json");
+    let expected = format!("{:#}\n", Config::schema());
+    if std::env::var_os("CBINDGEN_TEST_VERIFY").is_none() {
+        std::fs::write(&path, &expected).unwrap();
+    }
+    let published = std::fs::read_to_string(&path).unwrap();
+    assert!(
+        published == expected,
+        "cbindgen.schema.json is out of date, regenerate it with \
+         `cargo run -- --print-config-schema > cbindgen.schema.json`"
+    );
+}
+
+/// Follows references, `allOf` wrappers and the `null` alternative of
+/// optional values down to the schema that describes the value itself.
+fn resolve<'a>(definitions: &'a Map<String, Value>, mut node: &'a Value) -> &'a Value {
+    loop {
+        if let Some(reference) = node["$ref"].as_str() {
+            node = &definitions[reference.trim_start_matches("#/definitions/")];
+        } else if let Some(inner) = node["allOf"].get(0) {
+            node = inner;
+        } else if let Some(inner) = node["anyOf"].as_array().and_then(|variants| {
+            let mut non_null = variants.iter().filter(|v| v["type"] != "null");
+            let inner = non_null.next();
+            (variants.len() == 2 && non_null.next().is_none()).then_some(inner)?
+        }) {
+            node = inner;
+        } else {
+            return node;
+        }
+    }
+}
+
+fn enum_values(node: &Value) -> Vec<&Value> {
+    match node["anyOf"].as_array() {
+        Some(variants) => variants.iter().flat_map(enum_values).collect(),
+        None => node["enum"].as_array().into_iter().flatten().collect(),
+    }
+}
+
+fn check_enum_values(
+    definitions: &Map<String, Value>,
+    node: &Value,
+    table: &str,
+    checked: &mut Vec<String>,
+) {
+    for (key, property) in node["properties"].as_object().into_iter().flatten() {
+        let property = resolve(definitions, property);
+        let path = if table.is_empty() {
+            key.clone()
+        } else {
+            format!("{table}.{key}")
+        };
+        let (values, array) = match property["type"].as_str() {
+            Some("array") => (enum_values(resolve(definitions, &property["items"])), true),
+            _ => (enum_values(property), false),
+        };
+        for value in values {
+            let value = if array {
+                format!("[{value}]")
+            } else {
+                value.to_string()
+            };
+            let toml = if table.is_empty() {
+                format!("{key} = {value}")
+            } else {
+                format!("[{table}]\n{key} = {value}")
+            };
+            if let Err(e) = toml::from_str::<Config>(&toml) {
+                panic!("`{toml}` is in the schema but isn't accepted: {e}");
+            }
+            checked.push(path.clone());
+        }
+        check_enum_values(definitions, property, &path, checked);
+    }
+}
+
+#[test]
+fn schema_enum_values_are_accepted() {
+    let schema = Config::schema();
+    let definitions = schema["definitions"].as_object().unwrap();
+    let mut checked = Vec::new();
+    check_enum_values(definitions, &schema, "", &mut checked);
+    for path in [
+        "language",
+        "fn.sort_by",
+        "fn.rename_args",
+        "enum.rename_variants",
+        "export.item_types",
+        "documentation_style",
+    ] {
+        assert!(checked.iter().any(|p| p == path), "{path} wasn't checked");
+    }
+}
@@ -6,6 +6,8 @@
 enum C {
   X = 2,
   Y,
+  Z,
+  W,
 };
 typedef uint32_t C;
 
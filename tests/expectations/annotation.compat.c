@@ -10,6 +10,8 @@ enum C
  {
   X = 2,
   Y,
+  Z,
+  W,
 };
 #ifndef __cplusplus
 typedef uint32_t C;
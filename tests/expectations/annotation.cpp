@@ -7,6 +7,8 @@
 enum class C : uint32_t {
   X = 2,
   Y,
+  Z,
+  W,
 };
 
 struct A {
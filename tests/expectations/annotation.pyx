@@ -9,6 +9,8 @@ cdef extern from *:
   cdef enum:
     X # = 2,
     Y,
+    Z,
+    W,
   ctypedef uint32_t C;
 
   ctypedef struct A:
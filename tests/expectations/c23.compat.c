@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+static constexpr uint32_t LIMIT = 16;
+
+typedef enum {
+  X,
+  Y,
+} Plain;
+
+typedef enum : uint8_t {
+  A,
+  B,
+} Small;
+
+typedef struct [[nodiscard]] {
+  Small first;
+  Plain second;
+  bool flag;
+} Pair;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+[[deprecated("use `other` instead")]] void root(Pair pair);
+
+[[noreturn]] void stop(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
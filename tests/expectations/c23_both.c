@@ -0,0 +1,25 @@
+#include <stdarg.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+static constexpr uint32_t LIMIT = 16;
+
+typedef enum Plain {
+  X,
+  Y,
+} Plain;
+
+typedef enum Small : uint8_t {
+  A,
+  B,
+} Small;
+
+typedef struct [[nodiscard]] Pair {
+  Small first;
+  enum Plain second;
+  bool flag;
+} Pair;
+
+[[deprecated("use `other` instead")]] void root(struct Pair pair);
+
+[[noreturn]] void stop(void);
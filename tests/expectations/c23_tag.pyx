@@ -0,0 +1,27 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  const uint32_t LIMIT # = 16
+
+  cdef enum Plain:
+    X,
+    Y,
+
+  cdef enum:
+    A,
+    B,
+  ctypedef uint8_t Small;
+
+  cdef struct Pair:
+    Small first;
+    Plain second;
+    bool flag;
+
+  void root(Pair pair);
+
+  void stop();
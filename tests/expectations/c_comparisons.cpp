@@ -0,0 +1,166 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+enum class Color {
+  Red,
+  Green,
+};
+
+struct Point {
+  int32_t x;
+  int32_t y;
+
+  bool operator==(const Point& other) const {
+    return x == other.x &&
+           y == other.y;
+  }
+};
+
+struct Grid {
+  uint8_t cells[2][3];
+  Point origin;
+  Point corners[4];
+  Color color;
+#if defined(DEFINE_BLUE)
+  float blue
+#endif
+  ;
+  const uint8_t *data;
+};
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Circle,
+    Triangle,
+    Empty,
+  };
+
+  struct Circle_Body {
+    Point center;
+    float radius;
+
+    bool operator==(const Circle_Body& other) const {
+      return center == other.center &&
+             radius == other.radius;
+    }
+  };
+
+  struct Triangle_Body {
+    Point _0[3];
+  };
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+    Triangle_Body triangle;
+  };
+};
+
+union Token {
+  enum class Tag : uint8_t {
+    Number,
+    Pair,
+#if defined(DEFINE_BLUE)
+    Blue,
+#endif
+  };
+
+  struct Number_Body {
+    Tag tag;
+    int64_t _0;
+
+    bool operator==(const Number_Body& other) const {
+      return _0 == other._0;
+    }
+  };
+
+  struct Pair_Body {
+    Tag tag;
+    Point first;
+    Point second;
+
+    bool operator==(const Pair_Body& other) const {
+      return first == other.first &&
+             second == other.second;
+    }
+  };
+
+#if defined(DEFINE_BLUE)
+  struct Blue_Body {
+    Tag tag;
+    uint32_t _0;
+
+    bool operator==(const Blue_Body& other) const {
+      return _0 == other._0;
+    }
+  };
+#endif
+
+  struct {
+    Tag tag;
+  };
+  Number_Body number;
+  Pair_Body pair;
+#if defined(DEFINE_BLUE)
+  Blue_Body blue;
+#endif
+
+  bool operator==(const Token& other) const {
+    if (tag != other.tag) {
+      return false;
+    }
+    switch (tag) {
+      case Tag::Number: return number == other.number;
+      case Tag::Pair: return pair == other.pair;
+#if defined(DEFINE_BLUE)
+      case Tag::Blue: return blue == other.blue;
+#endif
+
+    }
+    return true;
+  }
+};
+
+struct Scene {
+  Point origin;
+  Token token;
+  void (*callback)();
+
+  bool operator==(const Scene& other) const {
+    return origin == other.origin &&
+           token == other.token &&
+           callback == other.callback;
+  }
+};
+
+/// `Range_eq` is exported below, so only `Range_cmp` is derived.
+struct Range {
+  uint32_t start;
+  uint32_t end;
+
+  bool operator==(const Range& other) const {
+    return start == other.start &&
+           end == other.end;
+  }
+};
+
+struct Size {
+  uint32_t width;
+  uint32_t height;
+
+  bool operator==(const Size& other) const {
+    return width == other.width &&
+           height == other.height;
+  }
+};
+
+extern "C" {
+
+void root(Grid grid, Shape shape, Scene scene, Range range, Size size);
+
+bool Range_eq(const Range *a, const Range *b);
+
+}  // extern "C"
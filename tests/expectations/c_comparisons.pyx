@@ -0,0 +1,73 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef enum Color:
+    Red,
+    Green,
+
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+  ctypedef struct Grid:
+    uint8_t cells[2][3];
+    Point origin;
+    Point corners[4];
+    Color color;
+    float blue;
+    const uint8_t *data;
+
+  cdef enum:
+    Circle,
+    Triangle,
+    Empty,
+  ctypedef uint8_t Shape_Tag;
+
+  ctypedef struct Circle_Body:
+    Point center;
+    float radius;
+
+  ctypedef struct Shape:
+    Shape_Tag tag;
+    Circle_Body circle;
+    Point triangle[3];
+
+  cdef enum:
+    Number,
+    Pair,
+    Blue,
+  ctypedef uint8_t Token_Tag;
+
+  ctypedef struct Pair_Body:
+    Token_Tag tag;
+    Point first;
+    Point second;
+
+  ctypedef union Token:
+    Token_Tag tag;
+    int64_t number;
+    Pair_Body pair;
+    uint32_t blue;
+
+  ctypedef struct Scene:
+    Point origin;
+    Token token;
+    void (*callback)();
+
+  # `Range_eq` is exported below, so only `Range_cmp` is derived.
+  ctypedef struct Range:
+    uint32_t start;
+    uint32_t end;
+
+  ctypedef struct Size:
+    uint32_t width;
+    uint32_t height;
+
+  void root(Grid grid, Shape shape, Scene scene, Range range, Size size);
+
+  bool Range_eq(const Range *a, const Range *b);
@@ -0,0 +1,331 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Color {
+  Red,
+  Green,
+};
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Grid {
+  uint8_t cells[2][3];
+  struct Point origin;
+  struct Point corners[4];
+  enum Color color;
+#if defined(DEFINE_BLUE)
+  float blue
+#endif
+  ;
+  const uint8_t *data;
+};
+
+enum Shape_Tag {
+  Circle,
+  Triangle,
+  Empty,
+};
+typedef uint8_t Shape_Tag;
+
+struct Circle_Body {
+  struct Point center;
+  float radius;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct Circle_Body circle;
+    struct {
+      struct Point triangle[3];
+    };
+  };
+};
+
+enum Token_Tag {
+  Number,
+  Pair,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+typedef uint8_t Token_Tag;
+
+struct Pair_Body {
+  Token_Tag tag;
+  struct Point first;
+  struct Point second;
+};
+
+union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+  struct Pair_Body pair;
+#if defined(DEFINE_BLUE)
+  struct {
+    Token_Tag blue_tag;
+    uint32_t blue;
+  };
+#endif
+};
+
+struct Scene {
+  struct Point origin;
+  union Token token;
+  void (*callback)(void);
+};
+
+/**
+ * `Range_eq` is exported below, so only `Range_cmp` is derived.
+ */
+struct Range {
+  uint32_t start;
+  uint32_t end;
+};
+
+struct Size {
+  uint32_t width;
+  uint32_t height;
+};
+
+void root(struct Grid grid,
+          struct Shape shape,
+          struct Scene scene,
+          struct Range range,
+          struct Size size);
+
+bool Range_eq(const struct Range *a, const struct Range *b);
+
+static inline bool Point_eq(const struct Point *a, const struct Point *b) {
+  if (a->x != b->x) {
+    return false;
+  }
+  if (a->y != b->y) {
+    return false;
+  }
+  return true;
+}
+
+static inline int Point_cmp(const struct Point *a, const struct Point *b) {
+  if (a->x != b->x) {
+    return a->x < b->x ? -1 : 1;
+  }
+  if (a->y != b->y) {
+    return a->y < b->y ? -1 : 1;
+  }
+  return 0;
+}
+
+static inline bool Grid_eq(const struct Grid *a, const struct Grid *b) {
+  size_t i, j;
+  for (i = 0; i < 2; i++) {
+    for (j = 0; j < 3; j++) {
+      if (a->cells[i][j] != b->cells[i][j]) {
+        return false;
+      }
+    }
+  }
+  if (!Point_eq(&a->origin, &b->origin)) {
+    return false;
+  }
+  for (i = 0; i < 4; i++) {
+    if (!Point_eq(&a->corners[i], &b->corners[i])) {
+      return false;
+    }
+  }
+  if (a->color != b->color) {
+    return false;
+  }
+#if defined(DEFINE_BLUE)
+  if (a->blue != b->blue) {
+    return false;
+  }
+#endif
+  if (a->data != b->data) {
+    return false;
+  }
+  return true;
+}
+
+static inline int Grid_cmp(const struct Grid *a, const struct Grid *b) {
+  size_t i, j;
+  int result;
+  for (i = 0; i < 2; i++) {
+    for (j = 0; j < 3; j++) {
+      if (a->cells[i][j] != b->cells[i][j]) {
+        return a->cells[i][j] < b->cells[i][j] ? -1 : 1;
+      }
+    }
+  }
+  result = Point_cmp(&a->origin, &b->origin);
+  if (result != 0) {
+    return result;
+  }
+  for (i = 0; i < 4; i++) {
+    result = Point_cmp(&a->corners[i], &b->corners[i]);
+    if (result != 0) {
+      return result;
+    }
+  }
+  if (a->color != b->color) {
+    return a->color < b->color ? -1 : 1;
+  }
+#if defined(DEFINE_BLUE)
+  if (a->blue != b->blue) {
+    return a->blue < b->blue ? -1 : 1;
+  }
+#endif
+  if (a->data != b->data) {
+    return a->data < b->data ? -1 : 1;
+  }
+  return 0;
+}
+
+static inline bool Shape_eq(const struct Shape *a, const struct Shape *b) {
+  size_t i;
+  if (a->tag != b->tag) {
+    return false;
+  }
+  switch (a->tag) {
+    case Circle:
+      if (!Point_eq(&a->circle.center, &b->circle.center)) {
+        return false;
+      }
+      if (a->circle.radius != b->circle.radius) {
+        return false;
+      }
+      break;
+    case Triangle:
+      for (i = 0; i < 3; i++) {
+        if (!Point_eq(&a->triangle[i], &b->triangle[i])) {
+          return false;
+        }
+      }
+      break;
+    default:
+      break;
+  }
+  return true;
+}
+
+static inline int Shape_cmp(const struct Shape *a, const struct Shape *b) {
+  size_t i;
+  int result;
+  if (a->tag != b->tag) {
+    return a->tag < b->tag ? -1 : 1;
+  }
+  switch (a->tag) {
+    case Circle:
+      result = Point_cmp(&a->circle.center, &b->circle.center);
+      if (result != 0) {
+        return result;
+      }
+      if (a->circle.radius != b->circle.radius) {
+        return a->circle.radius < b->circle.radius ? -1 : 1;
+      }
+      break;
+    case Triangle:
+      for (i = 0; i < 3; i++) {
+        result = Point_cmp(&a->triangle[i], &b->triangle[i]);
+        if (result != 0) {
+          return result;
+        }
+      }
+      break;
+    default:
+      break;
+  }
+  return 0;
+}
+
+static inline bool Token_eq(const union Token *a, const union Token *b) {
+  if (a->tag != b->tag) {
+    return false;
+  }
+  switch (a->tag) {
+    case Number:
+      if (a->number != b->number) {
+        return false;
+      }
+      break;
+    case Pair:
+      if (!Point_eq(&a->pair.first, &b->pair.first)) {
+        return false;
+      }
+      if (!Point_eq(&a->pair.second, &b->pair.second)) {
+        return false;
+      }
+      break;
+#if defined(DEFINE_BLUE)
+    case Blue:
+      if (a->blue != b->blue) {
+        return false;
+      }
+      break;
+#endif
+  }
+  return true;
+}
+
+static inline int Token_cmp(const union Token *a, const union Token *b) {
+  int result;
+  if (a->tag != b->tag) {
+    return a->tag < b->tag ? -1 : 1;
+  }
+  switch (a->tag) {
+    case Number:
+      if (a->number != b->number) {
+        return a->number < b->number ? -1 : 1;
+      }
+      break;
+    case Pair:
+      result = Point_cmp(&a->pair.first, &b->pair.first);
+      if (result != 0) {
+        return result;
+      }
+      result = Point_cmp(&a->pair.second, &b->pair.second);
+      if (result != 0) {
+        return result;
+      }
+      break;
+#if defined(DEFINE_BLUE)
+    case Blue:
+      if (a->blue != b->blue) {
+        return a->blue < b->blue ? -1 : 1;
+      }
+      break;
+#endif
+  }
+  return 0;
+}
+
+static inline bool Scene_eq(const struct Scene *a, const struct Scene *b) {
+  if (!Point_eq(&a->origin, &b->origin)) {
+    return false;
+  }
+  if (!Token_eq(&a->token, &b->token)) {
+    return false;
+  }
+  if (a->callback != b->callback) {
+    return false;
+  }
+  return true;
+}
+
+static inline int Range_cmp(const struct Range *a, const struct Range *b) {
+  if (a->start != b->start) {
+    return a->start < b->start ? -1 : 1;
+  }
+  if (a->end != b->end) {
+    return a->end < b->end ? -1 : 1;
+  }
+  return 0;
+}
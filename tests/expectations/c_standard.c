@@ -0,0 +1,33 @@
+#include <stdarg.h>
+#if defined(__STDC_VERSION__) && __STDC_VERSION__ >= 199901L
+#include <stdbool.h>
+#elif !defined(__cplusplus) && !defined(__bool_true_false_are_defined)
+typedef unsigned char bool;
+#define true 1
+#define false 0
+#endif
+#include <stdint.h>
+#include <stdlib.h>
+
+#define LIMIT 16
+
+typedef enum {
+  X,
+  Y
+} Plain;
+
+enum Small {
+  A,
+  B
+};
+typedef uint8_t Small;
+
+typedef struct {
+  Small first;
+  Plain second;
+  bool flag;
+} Pair;
+
+void root(Pair pair);
+
+void stop(void);
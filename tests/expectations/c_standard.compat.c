@@ -0,0 +1,47 @@
+#include <stdarg.h>
+#if defined(__STDC_VERSION__) && __STDC_VERSION__ >= 199901L
+#include <stdbool.h>
+#elif !defined(__cplusplus) && !defined(__bool_true_false_are_defined)
+typedef unsigned char bool;
+#define true 1
+#define false 0
+#endif
+#include <stdint.h>
+#include <stdlib.h>
+
+#define LIMIT 16
+
+typedef enum {
+  X,
+  Y
+} Plain;
+
+enum Small
+#ifdef __cplusplus
+  : uint8_t
+#endif /* __cplusplus */
+ {
+  A,
+  B
+};
+#ifndef __cplusplus
+typedef uint8_t Small;
+#endif /* __cplusplus */
+
+typedef struct {
+  Small first;
+  Plain second;
+  bool flag;
+} Pair;
+
+#ifdef __cplusplus
+extern "C" {
+#endif /* __cplusplus */
+
+void root(Pair pair);
+
+void stop(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  /* __cplusplus */
@@ -0,0 +1,31 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+constexpr static const uint32_t LIMIT = 16;
+
+enum class Plain {
+  X,
+  Y,
+};
+
+enum class Small : uint8_t {
+  A,
+  B,
+};
+
+struct Pair {
+  Small first;
+  Plain second;
+  bool flag;
+};
+
+extern "C" {
+
+void root(Pair pair);
+
+void stop();
+
+}  // extern "C"
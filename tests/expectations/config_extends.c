@@ -0,0 +1,16 @@
+/* Shared header */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t y;
+} Base_Renamed;
+
+typedef struct {
+  int32_t x;
+} Base_Orphan;
+
+void root(Base_Renamed u);
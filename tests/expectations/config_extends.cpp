@@ -0,0 +1,21 @@
+/* Shared header */
+
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Base_Renamed {
+  int32_t y;
+};
+
+struct Base_Orphan {
+  int32_t x;
+};
+
+extern "C" {
+
+void root(Base_Renamed u);
+
+}  // extern "C"
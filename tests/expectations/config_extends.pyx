@@ -0,0 +1,17 @@
+/* Shared header */
+
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Base_Renamed:
+    int32_t y;
+
+  ctypedef struct Base_Orphan:
+    int32_t x;
+
+  void root(Base_Renamed u);
@@ -0,0 +1,24 @@
+/* Shared header */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Base_Renamed {
+  int32_t y;
+} Base_Renamed;
+
+typedef struct Base_Orphan {
+  int32_t x;
+} Base_Orphan;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Base_Renamed u);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,16 @@
+/* Shared header */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Base_Renamed {
+  int32_t y;
+};
+
+struct Base_Orphan {
+  int32_t x;
+};
+
+void root(struct Base_Renamed u);
@@ -0,0 +1,24 @@
+/* Shared header */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Base_Renamed {
+  int32_t y;
+};
+
+struct Base_Orphan {
+  int32_t x;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Base_Renamed u);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#define EQ_ATTRS [[nodiscard]]
+#define CMP_ATTRS [[nodiscard]]
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Point;
+
+typedef struct {
+  Point start;
+  Point end;
+  float weights[2];
+} Segment;
+
+typedef struct {
+  uint32_t id;
+  bool visible;
+} Label;
+
+typedef struct {
+  uint32_t major;
+  uint32_t minor;
+} Version;
+
+void root(Segment segment, Label label, Version version);
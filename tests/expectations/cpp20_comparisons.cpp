@@ -0,0 +1,42 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <compare>
+#define EQ_ATTRS [[nodiscard]]
+#define CMP_ATTRS [[nodiscard]]
+
+struct Point {
+  int32_t x;
+  int32_t y;
+
+  friend bool operator==(const Point&, const Point&) = default;
+  friend auto operator<=>(const Point&, const Point&) = default;
+};
+
+struct Segment {
+  Point start;
+  Point end;
+  float weights[2];
+};
+
+struct Label {
+  uint32_t id;
+  bool visible;
+
+  EQ_ATTRS friend bool operator==(const Label&, const Label&) = default;
+};
+
+struct Version {
+  uint32_t major;
+  uint32_t minor;
+
+  CMP_ATTRS friend auto operator<=>(const Version&, const Version&) = default;
+};
+
+extern "C" {
+
+void root(Segment segment, Label label, Version version);
+
+}  // extern "C"
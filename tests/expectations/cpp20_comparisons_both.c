@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#define EQ_ATTRS [[nodiscard]]
+#define CMP_ATTRS [[nodiscard]]
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+typedef struct Segment {
+  struct Point start;
+  struct Point end;
+  float weights[2];
+} Segment;
+
+typedef struct Label {
+  uint32_t id;
+  bool visible;
+} Label;
+
+typedef struct Version {
+  uint32_t major;
+  uint32_t minor;
+} Version;
+
+void root(struct Segment segment, struct Label label, struct Version version);
@@ -0,0 +1,28 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+#define EQ_ATTRS [[nodiscard]]
+#define CMP_ATTRS [[nodiscard]]
+
+cdef extern from *:
+
+  cdef struct Point:
+    int32_t x;
+    int32_t y;
+
+  cdef struct Segment:
+    Point start;
+    Point end;
+    float weights[2];
+
+  cdef struct Label:
+    uint32_t id;
+    bool visible;
+
+  cdef struct Version:
+    uint32_t major;
+    uint32_t minor;
+
+  void root(Segment segment, Label label, Version version);
@@ -0,0 +1,280 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <format>
+
+namespace ffi {
+
+enum class Color {
+  Red,
+  Green,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Circle,
+    Segment,
+    Empty,
+  };
+
+  struct Circle_Body {
+    Point center;
+    float radius;
+  };
+
+  struct Segment_Body {
+    Point _0;
+    Point _1;
+  };
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+    Segment_Body segment;
+  };
+};
+
+struct Event {
+  enum class Tag {
+    Click,
+    Key,
+  };
+
+  struct Click_Body {
+    Point _0;
+  };
+
+  struct Key_Body {
+    uint32_t code;
+    bool pressed;
+  };
+
+  Tag tag;
+  union {
+    Click_Body click;
+    Key_Body key;
+  };
+};
+
+struct Fields {
+  const Point *point;
+  uint32_t *data;
+  const char *name;
+  int32_t (*callback)(int32_t);
+  uint16_t values[2];
+  bool flag;
+  uint8_t byte;
+};
+
+struct Opaque {
+  uint64_t value;
+};
+
+extern "C" {
+
+void root(Point point, Color color, Shape shape, Event event, Fields fields, Opaque opaque);
+
+}  // extern "C"
+
+}  // namespace ffi
+
+template<>
+struct std::formatter<ffi::Color> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Color& value, FormatContext& ctx) const {
+    switch (value) {
+      case ffi::Color::Red: return std::format_to(ctx.out(), "Red");
+      case ffi::Color::Green: return std::format_to(ctx.out(), "Green");
+#if defined(DEFINE_BLUE)
+      case ffi::Color::Blue: return std::format_to(ctx.out(), "Blue");
+#endif
+    }
+    return ctx.out();
+  }
+};
+
+template<>
+struct std::formatter<ffi::Point> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Point& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "x={}", value.x);
+    out = std::format_to(out, ", y={}", value.y);
+    return std::format_to(out, " }}");
+  }
+};
+
+template<>
+struct std::formatter<ffi::Shape::Tag> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Shape::Tag& value, FormatContext& ctx) const {
+    switch (value) {
+      case ffi::Shape::Tag::Circle: return std::format_to(ctx.out(), "Circle");
+      case ffi::Shape::Tag::Segment: return std::format_to(ctx.out(), "Segment");
+      case ffi::Shape::Tag::Empty: return std::format_to(ctx.out(), "Empty");
+    }
+    return ctx.out();
+  }
+};
+
+template<>
+struct std::formatter<ffi::Shape::Circle_Body> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Shape::Circle_Body& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "center={}", value.center);
+    out = std::format_to(out, ", radius={}", value.radius);
+    return std::format_to(out, " }}");
+  }
+};
+
+template<>
+struct std::formatter<ffi::Shape::Segment_Body> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Shape::Segment_Body& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "_0={}", value._0);
+    out = std::format_to(out, ", _1={}", value._1);
+    return std::format_to(out, " }}");
+  }
+};
+
+template<>
+struct std::formatter<ffi::Shape> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Shape& value, FormatContext& ctx) const {
+    switch (value.tag) {
+      case ffi::Shape::Tag::Circle: return std::format_to(ctx.out(), "Circle{}", value.circle);
+      case ffi::Shape::Tag::Segment: return std::format_to(ctx.out(), "Segment{}", value.segment);
+      case ffi::Shape::Tag::Empty: return std::format_to(ctx.out(), "Empty");
+    }
+    return ctx.out();
+  }
+};
+
+template<>
+struct std::formatter<ffi::Event::Tag> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Event::Tag& value, FormatContext& ctx) const {
+    switch (value) {
+      case ffi::Event::Tag::Click: return std::format_to(ctx.out(), "Click");
+      case ffi::Event::Tag::Key: return std::format_to(ctx.out(), "Key");
+    }
+    return ctx.out();
+  }
+};
+
+template<>
+struct std::formatter<ffi::Event::Click_Body> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Event::Click_Body& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "_0={}", value._0);
+    return std::format_to(out, " }}");
+  }
+};
+
+template<>
+struct std::formatter<ffi::Event::Key_Body> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Event::Key_Body& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "code={}", value.code);
+    out = std::format_to(out, ", pressed={:d}", value.pressed);
+    return std::format_to(out, " }}");
+  }
+};
+
+template<>
+struct std::formatter<ffi::Event> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Event& value, FormatContext& ctx) const {
+    switch (value.tag) {
+      case ffi::Event::Tag::Click: return std::format_to(ctx.out(), "Click{}", value.click);
+      case ffi::Event::Tag::Key: return std::format_to(ctx.out(), "Key{}", value.key);
+    }
+    return ctx.out();
+  }
+};
+
+template<>
+struct std::formatter<ffi::Fields> {
+  constexpr auto parse(std::format_parse_context& ctx) {
+    return ctx.begin();
+  }
+
+  template <class FormatContext>
+  auto format(const ffi::Fields& value, FormatContext& ctx) const {
+    auto out = ctx.out();
+    out = std::format_to(out, "{{ ");
+    out = std::format_to(out, "point={}", static_cast<const void*>(value.point));
+    out = std::format_to(out, ", data={}", static_cast<const void*>(value.data));
+    out = std::format_to(out, ", name={}", static_cast<const void*>(value.name));
+    out = std::format_to(out, ", callback={}", reinterpret_cast<const void*>(value.callback));
+    out = std::format_to(out, ", values={}", static_cast<const void*>(value.values));
+    out = std::format_to(out, ", flag={:d}", value.flag);
+    out = std::format_to(out, ", byte={}", static_cast<char>(value.byte));
+    return std::format_to(out, " }}");
+  }
+};
+
+#include <string>
+
+inline std::string format_all(const ffi::Fields& fields, const ffi::Shape& shape, const ffi::Event& event) {
+  return std::format("{} {} {}", fields, shape, event);
+}
@@ -0,0 +1,105 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace ffi {
+#endif  // __cplusplus
+
+typedef enum Color {
+  Red,
+  Green,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+} Color;
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Circle,
+  Segment,
+  Empty,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+typedef struct Circle_Body {
+  struct Point center;
+  float radius;
+} Circle_Body;
+
+typedef struct Segment_Body {
+  struct Point _0;
+  struct Point _1;
+} Segment_Body;
+
+typedef struct Shape {
+  Shape_Tag tag;
+  union {
+    Circle_Body circle;
+    Segment_Body segment;
+  };
+} Shape;
+
+typedef enum Event_Tag {
+  Click,
+  Key,
+} Event_Tag;
+
+typedef struct Key_Body {
+  uint32_t code;
+  bool pressed;
+} Key_Body;
+
+typedef struct Event {
+  Event_Tag tag;
+  union {
+    struct {
+      struct Point click;
+    };
+    Key_Body key;
+  };
+} Event;
+
+typedef struct Fields {
+  const struct Point *point;
+  uint32_t *data;
+  const char *name;
+  int32_t (*callback)(int32_t);
+  uint16_t values[2];
+  bool flag;
+  uint8_t byte;
+} Fields;
+
+typedef struct Opaque {
+  uint64_t value;
+} Opaque;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Point point,
+          enum Color color,
+          struct Shape shape,
+          struct Event event,
+          struct Fields fields,
+          struct Opaque opaque);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#ifdef __cplusplus
+}  // namespace ffi
+#endif  // __cplusplus
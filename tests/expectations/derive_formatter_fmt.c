@@ -0,0 +1,78 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef enum {
+  Red,
+  Green,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+} Color;
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Point;
+
+enum Shape_Tag {
+  Circle,
+  Segment,
+  Empty,
+};
+typedef uint8_t Shape_Tag;
+
+typedef struct {
+  Point center;
+  float radius;
+} Circle_Body;
+
+typedef struct {
+  Point _0;
+  Point _1;
+} Segment_Body;
+
+typedef struct {
+  Shape_Tag tag;
+  union {
+    Circle_Body circle;
+    Segment_Body segment;
+  };
+} Shape;
+
+typedef enum {
+  Click,
+  Key,
+} Event_Tag;
+
+typedef struct {
+  uint32_t code;
+  bool pressed;
+} Key_Body;
+
+typedef struct {
+  Event_Tag tag;
+  union {
+    struct {
+      Point click;
+    };
+    Key_Body key;
+  };
+} Event;
+
+typedef struct {
+  const Point *point;
+  uint32_t *data;
+  const char *name;
+  int32_t (*callback)(int32_t);
+  uint16_t values[2];
+  bool flag;
+  uint8_t byte;
+} Fields;
+
+typedef struct {
+  uint64_t value;
+} Opaque;
+
+void root(Point point, Color color, Shape shape, Event event, Fields fields, Opaque opaque);
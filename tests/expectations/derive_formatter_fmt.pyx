@@ -0,0 +1,62 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef enum Color:
+    Red,
+    Green,
+    Blue,
+
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+  cdef enum:
+    Circle,
+    Segment,
+    Empty,
+  ctypedef uint8_t Shape_Tag;
+
+  ctypedef struct Circle_Body:
+    Point center;
+    float radius;
+
+  ctypedef struct Segment_Body:
+    Point _0;
+    Point _1;
+
+  ctypedef struct Shape:
+    Shape_Tag tag;
+    Circle_Body circle;
+    Segment_Body segment;
+
+  ctypedef enum Event_Tag:
+    Click,
+    Key,
+
+  ctypedef struct Key_Body:
+    uint32_t code;
+    bool pressed;
+
+  ctypedef struct Event:
+    Event_Tag tag;
+    Point click;
+    Key_Body key;
+
+  ctypedef struct Fields:
+    const Point *point;
+    uint32_t *data;
+    const char *name;
+    int32_t (*callback)(int32_t);
+    uint16_t values[2];
+    bool flag;
+    uint8_t byte;
+
+  ctypedef struct Opaque:
+    uint64_t value;
+
+  void root(Point point, Color color, Shape shape, Event event, Fields fields, Opaque opaque);
@@ -0,0 +1,105 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace ffi {
+#endif  // __cplusplus
+
+enum Color {
+  Red,
+  Green,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Circle,
+  Segment,
+  Empty,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+struct Circle_Body {
+  struct Point center;
+  float radius;
+};
+
+struct Segment_Body {
+  struct Point _0;
+  struct Point _1;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct Circle_Body circle;
+    struct Segment_Body segment;
+  };
+};
+
+enum Event_Tag {
+  Click,
+  Key,
+};
+
+struct Key_Body {
+  uint32_t code;
+  bool pressed;
+};
+
+struct Event {
+  enum Event_Tag tag;
+  union {
+    struct {
+      struct Point click;
+    };
+    struct Key_Body key;
+  };
+};
+
+struct Fields {
+  const struct Point *point;
+  uint32_t *data;
+  const char *name;
+  int32_t (*callback)(int32_t);
+  uint16_t values[2];
+  bool flag;
+  uint8_t byte;
+};
+
+struct Opaque {
+  uint64_t value;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Point point,
+          enum Color color,
+          struct Shape shape,
+          struct Event event,
+          struct Fields fields,
+          struct Opaque opaque);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#ifdef __cplusplus
+}  // namespace ffi
+#endif  // __cplusplus
@@ -0,0 +1,103 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace ffi {
+#endif  // __cplusplus
+
+typedef struct {
+  int32_t x;
+  int32_t y;
+} Point;
+
+typedef struct {
+  Point corners[4];
+  float matrix[2][2];
+  bool closed;
+} Polygon;
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Empty,
+  Dot,
+  Filled,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+typedef struct {
+  Polygon polygon;
+  uint32_t color;
+} Filled_Body;
+
+typedef struct {
+  Shape_Tag tag;
+  union {
+    struct {
+      Point dot;
+    };
+    Filled_Body filled;
+  };
+} Shape;
+
+enum Token_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Number,
+  End,
+};
+#ifndef __cplusplus
+typedef uint8_t Token_Tag;
+#endif // __cplusplus
+
+typedef union {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+} Token;
+
+typedef struct {
+  const uint8_t *name;
+} Named;
+
+typedef struct {
+  Named named;
+} HasNamed;
+
+typedef struct {
+  int32_t x;
+} Unhashed;
+
+#if defined(DEFINE_BLUE)
+typedef struct {
+  Shape shape;
+} Blue;
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Shape shape, Token token, HasNamed has_named, Unhashed unhashed);
+
+#if defined(DEFINE_BLUE)
+void blue(Blue blue);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#ifdef __cplusplus
+}  // namespace ffi
+#endif  // __cplusplus
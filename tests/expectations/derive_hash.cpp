@@ -0,0 +1,215 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <functional>
+
+namespace ffi {
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Polygon {
+  Point corners[4];
+  float matrix[2][2];
+  bool closed;
+};
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Empty,
+    Dot,
+    Filled,
+  };
+
+  struct Dot_Body {
+    Point _0;
+  };
+
+  struct Filled_Body {
+    Polygon polygon;
+    uint32_t color;
+  };
+
+  Tag tag;
+  union {
+    Dot_Body dot;
+    Filled_Body filled;
+  };
+};
+
+union Token {
+  enum class Tag : uint8_t {
+    Number,
+    End,
+  };
+
+  struct Number_Body {
+    Tag tag;
+    int64_t _0;
+  };
+
+  struct {
+    Tag tag;
+  };
+  Number_Body number;
+};
+
+struct Named {
+  const uint8_t *name;
+};
+
+struct HasNamed {
+  Named named;
+};
+
+struct Unhashed {
+  int32_t x;
+};
+
+#if defined(DEFINE_BLUE)
+struct Blue {
+  Shape shape;
+};
+#endif
+
+extern "C" {
+
+void root(Shape shape, Token token, HasNamed has_named, Unhashed unhashed);
+
+#if defined(DEFINE_BLUE)
+void blue(Blue blue);
+#endif
+
+}  // extern "C"
+
+}  // namespace ffi
+
+template<>
+struct std::hash<ffi::Point> {
+  template <class T>
+  static void combine(size_t& seed, const T& value) {
+    seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+  }
+
+  template <class T, size_t N>
+  static void combine(size_t& seed, const T (&values)[N]) {
+    for (const T& value : values) {
+      combine(seed, value);
+    }
+  }
+
+  size_t operator()(const ffi::Point& value) const {
+    size_t seed = 0;
+    combine(seed, value.x);
+    combine(seed, value.y);
+    return seed;
+  }
+};
+
+template<>
+struct std::hash<ffi::Polygon> {
+  template <class T>
+  static void combine(size_t& seed, const T& value) {
+    seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+  }
+
+  template <class T, size_t N>
+  static void combine(size_t& seed, const T (&values)[N]) {
+    for (const T& value : values) {
+      combine(seed, value);
+    }
+  }
+
+  size_t operator()(const ffi::Polygon& value) const {
+    size_t seed = 0;
+    combine(seed, value.corners);
+    combine(seed, value.matrix);
+    combine(seed, value.closed);
+    return seed;
+  }
+};
+
+template<>
+struct std::hash<ffi::Shape> {
+  template <class T>
+  static void combine(size_t& seed, const T& value) {
+    seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+  }
+
+  template <class T, size_t N>
+  static void combine(size_t& seed, const T (&values)[N]) {
+    for (const T& value : values) {
+      combine(seed, value);
+    }
+  }
+
+  size_t operator()(const ffi::Shape& value) const {
+    size_t seed = 0;
+    combine(seed, value.tag);
+    switch (value.tag) {
+      case ffi::Shape::Tag::Dot:
+        combine(seed, value.dot._0);
+        break;
+      case ffi::Shape::Tag::Filled:
+        combine(seed, value.filled.polygon);
+        combine(seed, value.filled.color);
+        break;
+      default: break;
+    }
+    return seed;
+  }
+};
+
+template<>
+struct std::hash<ffi::Token> {
+  template <class T>
+  static void combine(size_t& seed, const T& value) {
+    seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+  }
+
+  template <class T, size_t N>
+  static void combine(size_t& seed, const T (&values)[N]) {
+    for (const T& value : values) {
+      combine(seed, value);
+    }
+  }
+
+  size_t operator()(const ffi::Token& value) const {
+    size_t seed = 0;
+    combine(seed, value.tag);
+    switch (value.tag) {
+      case ffi::Token::Tag::Number:
+        combine(seed, value.number._0);
+        break;
+      default: break;
+    }
+    return seed;
+  }
+};
+
+#if defined(DEFINE_BLUE)
+template<>
+struct std::hash<ffi::Blue> {
+  template <class T>
+  static void combine(size_t& seed, const T& value) {
+    seed ^= std::hash<T>()(value) + 0x9e3779b9 + (seed << 6) + (seed >> 2);
+  }
+
+  template <class T, size_t N>
+  static void combine(size_t& seed, const T (&values)[N]) {
+    for (const T& value : values) {
+      combine(seed, value);
+    }
+  }
+
+  size_t operator()(const ffi::Blue& value) const {
+    size_t seed = 0;
+    combine(seed, value.shape);
+    return seed;
+  }
+};
+#endif
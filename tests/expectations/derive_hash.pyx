@@ -0,0 +1,58 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Point:
+    int32_t x;
+    int32_t y;
+
+  ctypedef struct Polygon:
+    Point corners[4];
+    float matrix[2][2];
+    bool closed;
+
+  cdef enum:
+    Empty,
+    Dot,
+    Filled,
+  ctypedef uint8_t Shape_Tag;
+
+  ctypedef struct Filled_Body:
+    Polygon polygon;
+    uint32_t color;
+
+  ctypedef struct Shape:
+    Shape_Tag tag;
+    Point dot;
+    Filled_Body filled;
+
+  cdef enum:
+    Number,
+    End,
+  ctypedef uint8_t Token_Tag;
+
+  ctypedef union Token:
+    Token_Tag tag;
+    int64_t number;
+
+  ctypedef struct Named:
+    const uint8_t *name;
+
+  ctypedef struct HasNamed:
+    Named named;
+
+  ctypedef struct Unhashed:
+    int32_t x;
+
+  IF DEFINE_BLUE:
+    ctypedef struct Blue:
+      Shape shape;
+
+  void root(Shape shape, Token token, HasNamed has_named, Unhashed unhashed);
+
+  IF DEFINE_BLUE:
+    void blue(Blue blue);
@@ -0,0 +1,78 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Point {
+  int32_t x;
+  int32_t y;
+} Point;
+
+typedef struct Polygon {
+  struct Point corners[4];
+  float matrix[2][2];
+  bool closed;
+} Polygon;
+
+enum Shape_Tag {
+  Empty,
+  Dot,
+  Filled,
+};
+typedef uint8_t Shape_Tag;
+
+typedef struct Filled_Body {
+  struct Polygon polygon;
+  uint32_t color;
+} Filled_Body;
+
+typedef struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      struct Point dot;
+    };
+    Filled_Body filled;
+  };
+} Shape;
+
+enum Token_Tag {
+  Number,
+  End,
+};
+typedef uint8_t Token_Tag;
+
+typedef union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+} Token;
+
+typedef struct Named {
+  const uint8_t *name;
+} Named;
+
+typedef struct HasNamed {
+  struct Named named;
+} HasNamed;
+
+typedef struct Unhashed {
+  int32_t x;
+} Unhashed;
+
+#if defined(DEFINE_BLUE)
+typedef struct Blue {
+  struct Shape shape;
+} Blue;
+#endif
+
+void root(struct Shape shape,
+          union Token token,
+          struct HasNamed has_named,
+          struct Unhashed unhashed);
+
+#if defined(DEFINE_BLUE)
+void blue(struct Blue blue);
+#endif
@@ -0,0 +1,78 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Polygon {
+  struct Point corners[4];
+  float matrix[2][2];
+  bool closed;
+};
+
+enum Shape_Tag {
+  Empty,
+  Dot,
+  Filled,
+};
+typedef uint8_t Shape_Tag;
+
+struct Filled_Body {
+  struct Polygon polygon;
+  uint32_t color;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      struct Point dot;
+    };
+    struct Filled_Body filled;
+  };
+};
+
+enum Token_Tag {
+  Number,
+  End,
+};
+typedef uint8_t Token_Tag;
+
+union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+};
+
+struct Named {
+  const uint8_t *name;
+};
+
+struct HasNamed {
+  struct Named named;
+};
+
+struct Unhashed {
+  int32_t x;
+};
+
+#if defined(DEFINE_BLUE)
+struct Blue {
+  struct Shape shape;
+};
+#endif
+
+void root(struct Shape shape,
+          union Token token,
+          struct HasNamed has_named,
+          struct Unhashed unhashed);
+
+#if defined(DEFINE_BLUE)
+void blue(struct Blue blue);
+#endif
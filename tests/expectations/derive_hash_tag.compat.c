@@ -0,0 +1,106 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+namespace ffi {
+#endif  // __cplusplus
+
+struct Point {
+  int32_t x;
+  int32_t y;
+};
+
+struct Polygon {
+  struct Point corners[4];
+  float matrix[2][2];
+  bool closed;
+};
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Empty,
+  Dot,
+  Filled,
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+struct Filled_Body {
+  struct Polygon polygon;
+  uint32_t color;
+};
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      struct Point dot;
+    };
+    struct Filled_Body filled;
+  };
+};
+
+enum Token_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Number,
+  End,
+};
+#ifndef __cplusplus
+typedef uint8_t Token_Tag;
+#endif // __cplusplus
+
+union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+};
+
+struct Named {
+  const uint8_t *name;
+};
+
+struct HasNamed {
+  struct Named named;
+};
+
+struct Unhashed {
+  int32_t x;
+};
+
+#if defined(DEFINE_BLUE)
+struct Blue {
+  struct Shape shape;
+};
+#endif
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Shape shape,
+          union Token token,
+          struct HasNamed has_named,
+          struct Unhashed unhashed);
+
+#if defined(DEFINE_BLUE)
+void blue(struct Blue blue);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#ifdef __cplusplus
+}  // namespace ffi
+#endif  // __cplusplus
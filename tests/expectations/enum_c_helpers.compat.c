@@ -0,0 +1,240 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <assert.h>
+
+enum Shape_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Point,
+  Circle,
+  Rect,
+  Polygon,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+#ifndef __cplusplus
+typedef uint8_t Shape_Tag;
+#endif // __cplusplus
+
+typedef struct {
+  float width;
+  float height;
+} Rect_Body;
+
+typedef struct {
+  uint8_t sides;
+  float lengths[4];
+} Polygon_Body;
+
+#if defined(DEFINE_BLUE)
+typedef struct {
+  uint32_t _0;
+  uint32_t _1;
+} Blue_Body;
+#endif
+
+typedef struct {
+  Shape_Tag tag;
+  union {
+    struct {
+      float circle;
+    };
+    Rect_Body rect;
+    Polygon_Body polygon;
+#if defined(DEFINE_BLUE)
+    Blue_Body blue;
+#endif
+  };
+} Shape;
+
+static inline Shape Shape_Point(void) {
+  Shape result;
+  result.tag = Point;
+  return result;
+}
+
+static inline bool Shape_is_Point(const Shape *value) {
+  return value->tag == Point;
+}
+
+static inline Shape Shape_Circle(float circle) {
+  Shape result;
+  result.circle = circle;
+  result.tag = Circle;
+  return result;
+}
+
+static inline bool Shape_is_Circle(const Shape *value) {
+  return value->tag == Circle;
+}
+
+static inline const float *Shape_as_Circle(const Shape *value) {
+  assert(value->tag == Circle);
+  return &value->circle;
+}
+
+static inline float *Shape_as_Circle_mut(Shape *value) {
+  assert(value->tag == Circle);
+  return &value->circle;
+}
+
+static inline Shape Shape_Rect(float width,
+                               float height) {
+  Shape result;
+  result.rect.width = width;
+  result.rect.height = height;
+  result.tag = Rect;
+  return result;
+}
+
+static inline bool Shape_is_Rect(const Shape *value) {
+  return value->tag == Rect;
+}
+
+static inline const Rect_Body *Shape_as_Rect(const Shape *value) {
+  assert(value->tag == Rect);
+  return &value->rect;
+}
+
+static inline Rect_Body *Shape_as_Rect_mut(Shape *value) {
+  assert(value->tag == Rect);
+  return &value->rect;
+}
+
+static inline Shape Shape_Polygon(uint8_t sides,
+                                  float lengths[4]) {
+  Shape result;
+  int i;
+  result.polygon.sides = sides;
+  for (i = 0; i < 4; i++) {
+    result.polygon.lengths[i] = lengths[i];
+  }
+  result.tag = Polygon;
+  return result;
+}
+
+static inline bool Shape_is_Polygon(const Shape *value) {
+  return value->tag == Polygon;
+}
+
+static inline const Polygon_Body *Shape_as_Polygon(const Shape *value) {
+  assert(value->tag == Polygon);
+  return &value->polygon;
+}
+
+static inline Polygon_Body *Shape_as_Polygon_mut(Shape *value) {
+  assert(value->tag == Polygon);
+  return &value->polygon;
+}
+
+#if defined(DEFINE_BLUE)
+static inline Shape Shape_Blue(uint32_t _0,
+                               uint32_t _1) {
+  Shape result;
+  result.blue._0 = _0;
+  result.blue._1 = _1;
+  result.tag = Blue;
+  return result;
+}
+
+static inline bool Shape_is_Blue(const Shape *value) {
+  return value->tag == Blue;
+}
+
+static inline const Blue_Body *Shape_as_Blue(const Shape *value) {
+  assert(value->tag == Blue);
+  return &value->blue;
+}
+
+static inline Blue_Body *Shape_as_Blue_mut(Shape *value) {
+  assert(value->tag == Blue);
+  return &value->blue;
+}
+#endif
+
+enum Token_Tag
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Number,
+  Word,
+  End,
+};
+#ifndef __cplusplus
+typedef uint8_t Token_Tag;
+#endif // __cplusplus
+
+typedef struct {
+  Token_Tag tag;
+  uint32_t start;
+  uint32_t len;
+} Word_Body;
+
+typedef union {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+  Word_Body word;
+} Token;
+
+static inline Token Token_Number(int64_t number) {
+  Token result;
+  result.number = number;
+  result.number_tag = Number;
+  return result;
+}
+
+static inline bool Token_is_Number(const Token *value) {
+  return value->tag == Number;
+}
+
+static inline const int64_t *Token_as_Number(const Token *value) {
+  assert(value->tag == Number);
+  return &value->number;
+}
+
+static inline Token Token_Word(uint32_t start,
+                               uint32_t len) {
+  Token result;
+  result.word.start = start;
+  result.word.len = len;
+  result.word.tag = Word;
+  return result;
+}
+
+static inline bool Token_is_Word(const Token *value) {
+  return value->tag == Word;
+}
+
+static inline const Word_Body *Token_as_Word(const Token *value) {
+  assert(value->tag == Word);
+  return &value->word;
+}
+
+static inline Token Token_End(void) {
+  Token result;
+  result.tag = End;
+  return result;
+}
+
+static inline bool Token_is_End(const Token *value) {
+  return value->tag == End;
+}
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Shape shape, Token token);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,228 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <cassert>
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Point,
+    Circle,
+    Rect,
+    Polygon,
+#if defined(DEFINE_BLUE)
+    Blue,
+#endif
+  };
+
+  struct Circle_Body {
+    float _0;
+  };
+
+  struct Rect_Body {
+    float width;
+    float height;
+  };
+
+  struct Polygon_Body {
+    uint8_t sides;
+    float lengths[4];
+  };
+
+#if defined(DEFINE_BLUE)
+  struct Blue_Body {
+    uint32_t _0;
+    uint32_t _1;
+  };
+#endif
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+    Rect_Body rect;
+    Polygon_Body polygon;
+#if defined(DEFINE_BLUE)
+    Blue_Body blue;
+#endif
+  };
+
+  static Shape Point() {
+    Shape result;
+    result.tag = Tag::Point;
+    return result;
+  }
+
+  bool IsPoint() const {
+    return tag == Tag::Point;
+  }
+
+  static Shape Circle(const float &_0) {
+    Shape result;
+    ::new (&result.circle._0) (float)(_0);
+    result.tag = Tag::Circle;
+    return result;
+  }
+
+  bool IsCircle() const {
+    return tag == Tag::Circle;
+  }
+
+  const float& AsCircle() const {
+    assert(IsCircle());
+    return circle._0;
+  }
+
+  float& AsCircle() {
+    assert(IsCircle());
+    return circle._0;
+  }
+
+  static Shape Rect(const float &width,
+                    const float &height) {
+    Shape result;
+    ::new (&result.rect.width) (float)(width);
+    ::new (&result.rect.height) (float)(height);
+    result.tag = Tag::Rect;
+    return result;
+  }
+
+  bool IsRect() const {
+    return tag == Tag::Rect;
+  }
+
+  const Rect_Body& AsRect() const {
+    assert(IsRect());
+    return rect;
+  }
+
+  Rect_Body& AsRect() {
+    assert(IsRect());
+    return rect;
+  }
+
+  static Shape Polygon(const uint8_t &sides,
+                       const float (&lengths)[4]) {
+    Shape result;
+    ::new (&result.polygon.sides) (uint8_t)(sides);
+    for (int i = 0; i < 4; i++) {
+      ::new (&result.polygon.lengths[i]) (float)(lengths[i]);
+    }
+    result.tag = Tag::Polygon;
+    return result;
+  }
+
+  bool IsPolygon() const {
+    return tag == Tag::Polygon;
+  }
+
+  const Polygon_Body& AsPolygon() const {
+    assert(IsPolygon());
+    return polygon;
+  }
+
+  Polygon_Body& AsPolygon() {
+    assert(IsPolygon());
+    return polygon;
+  }
+
+#if defined(DEFINE_BLUE)
+  static Shape Blue(const uint32_t &_0,
+                    const uint32_t &_1) {
+    Shape result;
+    ::new (&result.blue._0) (uint32_t)(_0);
+    ::new (&result.blue._1) (uint32_t)(_1);
+    result.tag = Tag::Blue;
+    return result;
+  }
+
+  bool IsBlue() const {
+    return tag == Tag::Blue;
+  }
+
+  const Blue_Body& AsBlue() const {
+    assert(IsBlue());
+    return blue;
+  }
+
+  Blue_Body& AsBlue() {
+    assert(IsBlue());
+    return blue;
+  }
+#endif
+};
+
+union Token {
+  enum class Tag : uint8_t {
+    Number,
+    Word,
+    End,
+  };
+
+  struct Number_Body {
+    Tag tag;
+    int64_t _0;
+  };
+
+  struct Word_Body {
+    Tag tag;
+    uint32_t start;
+    uint32_t len;
+  };
+
+  struct {
+    Tag tag;
+  };
+  Number_Body number;
+  Word_Body word;
+
+  static Token Number(const int64_t &_0) {
+    Token result;
+    ::new (&result.number._0) (int64_t)(_0);
+    result.tag = Tag::Number;
+    return result;
+  }
+
+  bool IsNumber() const {
+    return tag == Tag::Number;
+  }
+
+  const int64_t& AsNumber() const {
+    assert(IsNumber());
+    return number._0;
+  }
+
+  static Token Word(const uint32_t &start,
+                    const uint32_t &len) {
+    Token result;
+    ::new (&result.word.start) (uint32_t)(start);
+    ::new (&result.word.len) (uint32_t)(len);
+    result.tag = Tag::Word;
+    return result;
+  }
+
+  bool IsWord() const {
+    return tag == Tag::Word;
+  }
+
+  const Word_Body& AsWord() const {
+    assert(IsWord());
+    return word;
+  }
+
+  static Token End() {
+    Token result;
+    result.tag = Tag::End;
+    return result;
+  }
+
+  bool IsEnd() const {
+    return tag == Tag::End;
+  }
+};
+
+extern "C" {
+
+void root(Shape shape, Token token);
+
+}  // extern "C"
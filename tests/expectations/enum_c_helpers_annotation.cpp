@@ -0,0 +1,54 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <cassert>
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Point,
+    Circle,
+  };
+
+  struct Circle_Body {
+    float _0;
+  };
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+  };
+
+  static Shape Point() {
+    Shape result;
+    result.tag = Tag::Point;
+    return result;
+  }
+
+  bool IsPoint() const {
+    return tag == Tag::Point;
+  }
+
+  static Shape Circle(const float &_0) {
+    Shape result;
+    ::new (&result.circle._0) (float)(_0);
+    result.tag = Tag::Circle;
+    return result;
+  }
+
+  bool IsCircle() const {
+    return tag == Tag::Circle;
+  }
+
+  const float& AsCircle() const {
+    assert(IsCircle());
+    return circle._0;
+  }
+};
+
+extern "C" {
+
+void root(Shape shape);
+
+}  // extern "C"
@@ -0,0 +1,48 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <assert.h>
+
+enum Shape_Tag {
+  Point,
+  Circle,
+};
+typedef uint8_t Shape_Tag;
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      float circle;
+    };
+  };
+};
+
+static inline struct Shape Shape_Point(void) {
+  struct Shape result;
+  result.tag = Point;
+  return result;
+}
+
+static inline bool Shape_is_Point(const struct Shape *value) {
+  return value->tag == Point;
+}
+
+static inline struct Shape Shape_Circle(float circle) {
+  struct Shape result;
+  result.circle = circle;
+  result.tag = Circle;
+  return result;
+}
+
+static inline bool Shape_is_Circle(const struct Shape *value) {
+  return value->tag == Circle;
+}
+
+static inline const float *Shape_as_Circle(const struct Shape *value) {
+  assert(value->tag == Circle);
+  return &value->circle;
+}
+
+void root(struct Shape shape);
@@ -0,0 +1,220 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <assert.h>
+
+enum Shape_Tag {
+  Point,
+  Circle,
+  Rect,
+  Polygon,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+typedef uint8_t Shape_Tag;
+
+struct Rect_Body {
+  float width;
+  float height;
+};
+
+struct Polygon_Body {
+  uint8_t sides;
+  float lengths[4];
+};
+
+#if defined(DEFINE_BLUE)
+struct Blue_Body {
+  uint32_t _0;
+  uint32_t _1;
+};
+#endif
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      float circle;
+    };
+    struct Rect_Body rect;
+    struct Polygon_Body polygon;
+#if defined(DEFINE_BLUE)
+    struct Blue_Body blue;
+#endif
+  };
+};
+
+static inline struct Shape Shape_Point(void) {
+  struct Shape result;
+  result.tag = Point;
+  return result;
+}
+
+static inline bool Shape_is_Point(const struct Shape *value) {
+  return value->tag == Point;
+}
+
+static inline struct Shape Shape_Circle(float circle) {
+  struct Shape result;
+  result.circle = circle;
+  result.tag = Circle;
+  return result;
+}
+
+static inline bool Shape_is_Circle(const struct Shape *value) {
+  return value->tag == Circle;
+}
+
+static inline const float *Shape_as_Circle(const struct Shape *value) {
+  assert(value->tag == Circle);
+  return &value->circle;
+}
+
+static inline float *Shape_as_Circle_mut(struct Shape *value) {
+  assert(value->tag == Circle);
+  return &value->circle;
+}
+
+static inline struct Shape Shape_Rect(float width,
+                                      float height) {
+  struct Shape result;
+  result.rect.width = width;
+  result.rect.height = height;
+  result.tag = Rect;
+  return result;
+}
+
+static inline bool Shape_is_Rect(const struct Shape *value) {
+  return value->tag == Rect;
+}
+
+static inline const struct Rect_Body *Shape_as_Rect(const struct Shape *value) {
+  assert(value->tag == Rect);
+  return &value->rect;
+}
+
+static inline struct Rect_Body *Shape_as_Rect_mut(struct Shape *value) {
+  assert(value->tag == Rect);
+  return &value->rect;
+}
+
+static inline struct Shape Shape_Polygon(uint8_t sides,
+                                         float lengths[4]) {
+  struct Shape result;
+  int i;
+  result.polygon.sides = sides;
+  for (i = 0; i < 4; i++) {
+    result.polygon.lengths[i] = lengths[i];
+  }
+  result.tag = Polygon;
+  return result;
+}
+
+static inline bool Shape_is_Polygon(const struct Shape *value) {
+  return value->tag == Polygon;
+}
+
+static inline const struct Polygon_Body *Shape_as_Polygon(const struct Shape *value) {
+  assert(value->tag == Polygon);
+  return &value->polygon;
+}
+
+static inline struct Polygon_Body *Shape_as_Polygon_mut(struct Shape *value) {
+  assert(value->tag == Polygon);
+  return &value->polygon;
+}
+
+#if defined(DEFINE_BLUE)
+static inline struct Shape Shape_Blue(uint32_t _0,
+                                      uint32_t _1) {
+  struct Shape result;
+  result.blue._0 = _0;
+  result.blue._1 = _1;
+  result.tag = Blue;
+  return result;
+}
+
+static inline bool Shape_is_Blue(const struct Shape *value) {
+  return value->tag == Blue;
+}
+
+static inline const struct Blue_Body *Shape_as_Blue(const struct Shape *value) {
+  assert(value->tag == Blue);
+  return &value->blue;
+}
+
+static inline struct Blue_Body *Shape_as_Blue_mut(struct Shape *value) {
+  assert(value->tag == Blue);
+  return &value->blue;
+}
+#endif
+
+enum Token_Tag {
+  Number,
+  Word,
+  End,
+};
+typedef uint8_t Token_Tag;
+
+struct Word_Body {
+  Token_Tag tag;
+  uint32_t start;
+  uint32_t len;
+};
+
+union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+  struct Word_Body word;
+};
+
+static inline union Token Token_Number(int64_t number) {
+  union Token result;
+  result.number = number;
+  result.number_tag = Number;
+  return result;
+}
+
+static inline bool Token_is_Number(const union Token *value) {
+  return value->tag == Number;
+}
+
+static inline const int64_t *Token_as_Number(const union Token *value) {
+  assert(value->tag == Number);
+  return &value->number;
+}
+
+static inline union Token Token_Word(uint32_t start,
+                                     uint32_t len) {
+  union Token result;
+  result.word.start = start;
+  result.word.len = len;
+  result.word.tag = Word;
+  return result;
+}
+
+static inline bool Token_is_Word(const union Token *value) {
+  return value->tag == Word;
+}
+
+static inline const struct Word_Body *Token_as_Word(const union Token *value) {
+  assert(value->tag == Word);
+  return &value->word;
+}
+
+static inline union Token Token_End(void) {
+  union Token result;
+  result.tag = End;
+  return result;
+}
+
+static inline bool Token_is_End(const union Token *value) {
+  return value->tag == End;
+}
+
+void root(struct Shape shape, union Token token);
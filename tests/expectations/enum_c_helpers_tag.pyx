@@ -0,0 +1,52 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    Point,
+    Circle,
+    Rect,
+    Polygon,
+    Blue,
+  ctypedef uint8_t Shape_Tag;
+
+  cdef struct Rect_Body:
+    float width;
+    float height;
+
+  cdef struct Polygon_Body:
+    uint8_t sides;
+    float lengths[4];
+
+  cdef struct Blue_Body:
+    uint32_t _0;
+    uint32_t _1;
+
+  cdef struct Shape:
+    Shape_Tag tag;
+    float circle;
+    Rect_Body rect;
+    Polygon_Body polygon;
+    Blue_Body blue;
+
+  cdef enum:
+    Number,
+    Word,
+    End,
+  ctypedef uint8_t Token_Tag;
+
+  cdef struct Word_Body:
+    Token_Tag tag;
+    uint32_t start;
+    uint32_t len;
+
+  cdef union Token:
+    Token_Tag tag;
+    int64_t number;
+    Word_Body word;
+
+  void root(Shape shape, Token token);
@@ -0,0 +1,75 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <optional>
+#include <string_view>
+
+enum class Color : uint8_t {
+  Color_Red,
+  Color_DarkGreen,
+#if defined(DEFINE_BLUE)
+  Color_Blue,
+#endif
+};
+
+constexpr std::string_view Color_to_string(Color value) {
+  switch (value) {
+    case Color::Color_Red: return "Color_Red";
+    case Color::Color_DarkGreen: return "Color_DarkGreen";
+#if defined(DEFINE_BLUE)
+    case Color::Color_Blue: return "Color_Blue";
+#endif
+  }
+  return {};
+}
+
+constexpr std::optional<Color> Color_from_string(std::string_view string) {
+  if (string == "Color_Red") {
+    return Color::Color_Red;
+  }
+  if (string == "Color_DarkGreen") {
+    return Color::Color_DarkGreen;
+  }
+#if defined(DEFINE_BLUE)
+  if (string == "Color_Blue") {
+    return Color::Color_Blue;
+  }
+#endif
+  return std::nullopt;
+}
+
+enum class Plain {
+  Plain_A,
+  Plain_B,
+};
+
+enum class Shape {
+  SHAPE_CIRCLE,
+  SHAPE_ROUNDED_SQUARE,
+};
+
+constexpr std::string_view Shape_to_string(Shape value) {
+  switch (value) {
+    case Shape::SHAPE_CIRCLE: return "Circle";
+    case Shape::SHAPE_ROUNDED_SQUARE: return "RoundedSquare";
+  }
+  return {};
+}
+
+constexpr std::optional<Shape> Shape_from_string(std::string_view string) {
+  if (string == "Circle") {
+    return Shape::SHAPE_CIRCLE;
+  }
+  if (string == "RoundedSquare") {
+    return Shape::SHAPE_ROUNDED_SQUARE;
+  }
+  return std::nullopt;
+}
+
+extern "C" {
+
+void root(Color color, Shape shape, Plain plain);
+
+}  // extern "C"
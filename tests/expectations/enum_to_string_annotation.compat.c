@@ -0,0 +1,52 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+
+enum Color
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Red,
+  Green,
+};
+#ifndef __cplusplus
+typedef uint8_t Color;
+#endif // __cplusplus
+
+static inline const char *Color_to_string(Color value) {
+  switch (value) {
+    case Red: return "Red";
+    case Green: return "Green";
+  }
+  return NULL;
+}
+
+typedef enum {
+  Circle,
+  Square,
+} Shape;
+
+static inline bool Shape_from_string(const char *string, Shape *value) {
+  if (strcmp(string, "Circle") == 0) {
+    *value = Circle;
+    return true;
+  }
+  if (strcmp(string, "Square") == 0) {
+    *value = Square;
+    return true;
+  }
+  return false;
+}
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Color color, Shape shape);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
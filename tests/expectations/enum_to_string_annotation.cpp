@@ -0,0 +1,41 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <optional>
+#include <string_view>
+
+enum class Color : uint8_t {
+  Red,
+  Green,
+};
+
+constexpr std::string_view Color_to_string(Color value) {
+  switch (value) {
+    case Color::Red: return "Red";
+    case Color::Green: return "Green";
+  }
+  return {};
+}
+
+enum class Shape {
+  Circle,
+  Square,
+};
+
+constexpr std::optional<Shape> Shape_from_string(std::string_view string) {
+  if (string == "Circle") {
+    return Shape::Circle;
+  }
+  if (string == "Square") {
+    return Shape::Square;
+  }
+  return std::nullopt;
+}
+
+extern "C" {
+
+void root(Color color, Shape shape);
+
+}  // extern "C"
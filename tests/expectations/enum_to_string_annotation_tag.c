@@ -0,0 +1,38 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+
+enum Color {
+  Red,
+  Green,
+};
+typedef uint8_t Color;
+
+static inline const char *Color_to_string(Color value) {
+  switch (value) {
+    case Red: return "Red";
+    case Green: return "Green";
+  }
+  return NULL;
+}
+
+enum Shape {
+  Circle,
+  Square,
+};
+
+static inline bool Shape_from_string(const char *string, enum Shape *value) {
+  if (strcmp(string, "Circle") == 0) {
+    *value = Circle;
+    return true;
+  }
+  if (strcmp(string, "Square") == 0) {
+    *value = Square;
+    return true;
+  }
+  return false;
+}
+
+void root(Color color, enum Shape shape);
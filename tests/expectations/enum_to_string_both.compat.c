@@ -0,0 +1,89 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <string.h>
+
+enum Color
+#ifdef __cplusplus
+  : uint8_t
+#endif // __cplusplus
+ {
+  Color_Red,
+  Color_DarkGreen,
+#if defined(DEFINE_BLUE)
+  Color_Blue,
+#endif
+};
+#ifndef __cplusplus
+typedef uint8_t Color;
+#endif // __cplusplus
+
+static inline const char *Color_to_string(Color value) {
+  switch (value) {
+    case Color_Red: return "Color_Red";
+    case Color_DarkGreen: return "Color_DarkGreen";
+#if defined(DEFINE_BLUE)
+    case Color_Blue: return "Color_Blue";
+#endif
+  }
+  return NULL;
+}
+
+static inline bool Color_from_string(const char *string, Color *value) {
+  if (strcmp(string, "Color_Red") == 0) {
+    *value = Color_Red;
+    return true;
+  }
+  if (strcmp(string, "Color_DarkGreen") == 0) {
+    *value = Color_DarkGreen;
+    return true;
+  }
+#if defined(DEFINE_BLUE)
+  if (strcmp(string, "Color_Blue") == 0) {
+    *value = Color_Blue;
+    return true;
+  }
+#endif
+  return false;
+}
+
+typedef enum Plain {
+  Plain_A,
+  Plain_B,
+} Plain;
+
+typedef enum Shape {
+  SHAPE_CIRCLE,
+  SHAPE_ROUNDED_SQUARE,
+} Shape;
+
+static inline const char *Shape_to_string(Shape value) {
+  switch (value) {
+    case SHAPE_CIRCLE: return "Circle";
+    case SHAPE_ROUNDED_SQUARE: return "RoundedSquare";
+  }
+  return NULL;
+}
+
+static inline bool Shape_from_string(const char *string, Shape *value) {
+  if (strcmp(string, "Circle") == 0) {
+    *value = SHAPE_CIRCLE;
+    return true;
+  }
+  if (strcmp(string, "RoundedSquare") == 0) {
+    *value = SHAPE_ROUNDED_SQUARE;
+    return true;
+  }
+  return false;
+}
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Color color, enum Shape shape, enum Plain plain);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
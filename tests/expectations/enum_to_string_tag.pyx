@@ -0,0 +1,23 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef enum:
+    Color_Red,
+    Color_DarkGreen,
+    Color_Blue,
+  ctypedef uint8_t Color;
+
+  cdef enum Plain:
+    Plain_A,
+    Plain_B,
+
+  cdef enum Shape:
+    SHAPE_CIRCLE,
+    SHAPE_ROUNDED_SQUARE,
+
+  void root(Color color, Shape shape, Plain plain);
@@ -0,0 +1,168 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <type_traits>
+#include <utility>
+
+struct Shape {
+  enum class Tag : uint8_t {
+    Point,
+    Circle,
+    Rect,
+#if defined(DEFINE_BLUE)
+    Blue,
+#endif
+  };
+
+  struct Circle_Body {
+    float _0;
+  };
+
+  struct Rect_Body {
+    float width;
+    float height;
+  };
+
+#if defined(DEFINE_BLUE)
+  struct Blue_Body {
+    uint32_t _0;
+    uint32_t _1;
+  };
+#endif
+
+  Tag tag;
+  union {
+    Circle_Body circle;
+    Rect_Body rect;
+#if defined(DEFINE_BLUE)
+    Blue_Body blue;
+#endif
+  };
+
+  template <class F>
+  decltype(auto) visit(F&& f) const {
+    switch (tag) {
+      case Tag::Point: return std::forward<F>(f)(std::integral_constant<Tag, Tag::Point>());
+      case Tag::Circle: return std::forward<F>(f)(circle);
+      case Tag::Rect:
+      default: return std::forward<F>(f)(rect);
+#if defined(DEFINE_BLUE)
+      case Tag::Blue: return std::forward<F>(f)(blue);
+#endif
+    }
+  }
+
+  template <class F>
+  decltype(auto) visit(F&& f) {
+    switch (tag) {
+      case Tag::Point: return std::forward<F>(f)(std::integral_constant<Tag, Tag::Point>());
+      case Tag::Circle: return std::forward<F>(f)(circle);
+      case Tag::Rect:
+      default: return std::forward<F>(f)(rect);
+#if defined(DEFINE_BLUE)
+      case Tag::Blue: return std::forward<F>(f)(blue);
+#endif
+    }
+  }
+
+  size_t index() const {
+    switch (tag) {
+      case Tag::Point: return 0;
+      case Tag::Circle: return 1;
+      case Tag::Rect:
+      default: return 2;
+#if defined(DEFINE_BLUE)
+      case Tag::Blue: return 3;
+#endif
+    }
+  }
+
+  template <Tag T>
+  bool holds() const {
+    return tag == T;
+  }
+};
+
+union Token {
+  enum class Tag : uint8_t {
+    Number,
+    Word,
+    End,
+  };
+
+  struct Number_Body {
+    Tag tag;
+    int64_t _0;
+  };
+
+  struct Word_Body {
+    Tag tag;
+    uint32_t start;
+    uint32_t len;
+  };
+
+  struct {
+    Tag tag;
+  };
+  Number_Body number;
+  Word_Body word;
+
+  template <class F>
+  decltype(auto) visit(F&& f) const {
+    switch (tag) {
+      case Tag::Number: return std::forward<F>(f)(number);
+      case Tag::Word: return std::forward<F>(f)(word);
+      case Tag::End:
+      default: return std::forward<F>(f)(std::integral_constant<Tag, Tag::End>());
+    }
+  }
+
+  template <class F>
+  decltype(auto) visit(F&& f) {
+    switch (tag) {
+      case Tag::Number: return std::forward<F>(f)(number);
+      case Tag::Word: return std::forward<F>(f)(word);
+      case Tag::End:
+      default: return std::forward<F>(f)(std::integral_constant<Tag, Tag::End>());
+    }
+  }
+
+  size_t index() const {
+    switch (tag) {
+      case Tag::Number: return 0;
+      case Tag::Word: return 1;
+      case Tag::End:
+      default: return 2;
+    }
+  }
+
+  template <Tag T>
+  bool holds() const {
+    return tag == T;
+  }
+};
+
+union Unvisited {
+  enum class Tag : uint8_t {
+    A,
+    B,
+  };
+
+  struct A_Body {
+    Tag tag;
+    int32_t _0;
+  };
+
+  struct {
+    Tag tag;
+  };
+  A_Body a;
+};
+
+extern "C" {
+
+void root(Shape shape, Token token, Unvisited unvisited);
+
+}  // extern "C"
@@ -0,0 +1,77 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag {
+  Point,
+  Circle,
+  Rect,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+typedef uint8_t Shape_Tag;
+
+typedef struct Rect_Body {
+  float width;
+  float height;
+} Rect_Body;
+
+#if defined(DEFINE_BLUE)
+typedef struct Blue_Body {
+  uint32_t _0;
+  uint32_t _1;
+} Blue_Body;
+#endif
+
+typedef struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      float circle;
+    };
+    Rect_Body rect;
+#if defined(DEFINE_BLUE)
+    Blue_Body blue;
+#endif
+  };
+} Shape;
+
+enum Token_Tag {
+  Number,
+  Word,
+  End,
+};
+typedef uint8_t Token_Tag;
+
+typedef struct Word_Body {
+  Token_Tag tag;
+  uint32_t start;
+  uint32_t len;
+} Word_Body;
+
+typedef union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+  Word_Body word;
+} Token;
+
+enum Unvisited_Tag {
+  A,
+  B,
+};
+typedef uint8_t Unvisited_Tag;
+
+typedef union Unvisited {
+  Unvisited_Tag tag;
+  struct {
+    Unvisited_Tag a_tag;
+    int32_t a;
+  };
+} Unvisited;
+
+void root(struct Shape shape, union Token token, union Unvisited unvisited);
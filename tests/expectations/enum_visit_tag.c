@@ -0,0 +1,77 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+enum Shape_Tag {
+  Point,
+  Circle,
+  Rect,
+#if defined(DEFINE_BLUE)
+  Blue,
+#endif
+};
+typedef uint8_t Shape_Tag;
+
+struct Rect_Body {
+  float width;
+  float height;
+};
+
+#if defined(DEFINE_BLUE)
+struct Blue_Body {
+  uint32_t _0;
+  uint32_t _1;
+};
+#endif
+
+struct Shape {
+  Shape_Tag tag;
+  union {
+    struct {
+      float circle;
+    };
+    struct Rect_Body rect;
+#if defined(DEFINE_BLUE)
+    struct Blue_Body blue;
+#endif
+  };
+};
+
+enum Token_Tag {
+  Number,
+  Word,
+  End,
+};
+typedef uint8_t Token_Tag;
+
+struct Word_Body {
+  Token_Tag tag;
+  uint32_t start;
+  uint32_t len;
+};
+
+union Token {
+  Token_Tag tag;
+  struct {
+    Token_Tag number_tag;
+    int64_t number;
+  };
+  struct Word_Body word;
+};
+
+enum Unvisited_Tag {
+  A,
+  B,
+};
+typedef uint8_t Unvisited_Tag;
+
+union Unvisited {
+  Unvisited_Tag tag;
+  struct {
+    Unvisited_Tag a_tag;
+    int32_t a;
+  };
+};
+
+void root(struct Shape shape, union Token token, union Unvisited unvisited);
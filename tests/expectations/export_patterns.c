@@ -0,0 +1,24 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  float x;
+  float y;
+} Point;
+
+typedef struct {
+  float w;
+  float h;
+} Size;
+
+typedef struct {
+  int32_t x;
+} PublicState;
+
+typedef struct {
+  int32_t x;
+} OtherHelper;
+
+void root(const Point *a);
@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  float x;
+  float y;
+} Point;
+
+typedef struct {
+  float w;
+  float h;
+} Size;
+
+typedef struct {
+  int32_t x;
+} PublicState;
+
+typedef struct {
+  int32_t x;
+} OtherHelper;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(const Point *a);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,29 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Point {
+  float x;
+  float y;
+};
+
+struct Size {
+  float w;
+  float h;
+};
+
+struct PublicState {
+  int32_t x;
+};
+
+struct OtherHelper {
+  int32_t x;
+};
+
+extern "C" {
+
+void root(const Point *a);
+
+}  // extern "C"
@@ -0,0 +1,24 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Point {
+  float x;
+  float y;
+} Point;
+
+typedef struct Size {
+  float w;
+  float h;
+} Size;
+
+typedef struct PublicState {
+  int32_t x;
+} PublicState;
+
+typedef struct OtherHelper {
+  int32_t x;
+} OtherHelper;
+
+void root(const struct Point *a);
@@ -0,0 +1,24 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Point {
+  float x;
+  float y;
+};
+
+struct Size {
+  float w;
+  float h;
+};
+
+struct PublicState {
+  int32_t x;
+};
+
+struct OtherHelper {
+  int32_t x;
+};
+
+void root(const struct Point *a);
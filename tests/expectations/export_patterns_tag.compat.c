@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Point {
+  float x;
+  float y;
+};
+
+struct Size {
+  float w;
+  float h;
+};
+
+struct PublicState {
+  int32_t x;
+};
+
+struct OtherHelper {
+  int32_t x;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(const struct Point *a);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
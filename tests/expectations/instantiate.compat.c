@@ -0,0 +1,40 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  double value;
+} Foo_f64;
+
+typedef struct {
+  int32_t first;
+  int64_t second;
+} IntPair;
+
+typedef struct {
+  uint32_t value;
+} FooU32;
+
+typedef struct {
+  const FooU32 *inner;
+} Wrapper_Foo_u32;
+
+typedef struct {
+  uint8_t first;
+  float second;
+} Pair_u8__f32;
+
+typedef struct {
+  Pair_u8__f32 value;
+} Foo_Pair_u8__f32;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(Foo_f64 foo);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,35 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+template<typename T>
+struct Foo {
+  T value;
+};
+
+template<typename A, typename B>
+struct Pair {
+  A first;
+  B second;
+};
+
+using IntPair = Pair<int32_t, int64_t>;
+
+template<typename T>
+struct Wrapper {
+  const T *inner;
+};
+
+using Wrapper_Foo_u32 = Wrapper<Foo<uint32_t>>;
+
+using FooU32 = Foo<uint32_t>;
+
+using Foo_Pair_u8__f32 = Foo<Pair<uint8_t, float>>;
+
+extern "C" {
+
+void root(Foo<double> foo);
+
+}  // extern "C"
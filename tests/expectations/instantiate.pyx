@@ -0,0 +1,29 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct Foo_f64:
+    double value;
+
+  ctypedef struct IntPair:
+    int32_t first;
+    int64_t second;
+
+  ctypedef struct FooU32:
+    uint32_t value;
+
+  ctypedef struct Wrapper_Foo_u32:
+    const FooU32 *inner;
+
+  ctypedef struct Pair_u8__f32:
+    uint8_t first;
+    float second;
+
+  ctypedef struct Foo_Pair_u8__f32:
+    Pair_u8__f32 value;
+
+  void root(Foo_f64 foo);
@@ -0,0 +1,32 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Foo_f64 {
+  double value;
+} Foo_f64;
+
+typedef struct IntPair {
+  int32_t first;
+  int64_t second;
+} IntPair;
+
+typedef struct FooU32 {
+  uint32_t value;
+} FooU32;
+
+typedef struct Wrapper_Foo_u32 {
+  const struct FooU32 *inner;
+} Wrapper_Foo_u32;
+
+typedef struct Pair_u8__f32 {
+  uint8_t first;
+  float second;
+} Pair_u8__f32;
+
+typedef struct Foo_Pair_u8__f32 {
+  struct Pair_u8__f32 value;
+} Foo_Pair_u8__f32;
+
+void root(struct Foo_f64 foo);
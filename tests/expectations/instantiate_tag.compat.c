@@ -0,0 +1,40 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Foo_f64 {
+  double value;
+};
+
+struct IntPair {
+  int32_t first;
+  int64_t second;
+};
+
+struct FooU32 {
+  uint32_t value;
+};
+
+struct Wrapper_Foo_u32 {
+  const struct FooU32 *inner;
+};
+
+struct Pair_u8__f32 {
+  uint8_t first;
+  float second;
+};
+
+struct Foo_Pair_u8__f32 {
+  struct Pair_u8__f32 value;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(struct Foo_f64 foo);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
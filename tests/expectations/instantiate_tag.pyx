@@ -0,0 +1,29 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Foo_f64:
+    double value;
+
+  cdef struct IntPair:
+    int32_t first;
+    int64_t second;
+
+  cdef struct FooU32:
+    uint32_t value;
+
+  cdef struct Wrapper_Foo_u32:
+    const FooU32 *inner;
+
+  cdef struct Pair_u8__f32:
+    uint8_t first;
+    float second;
+
+  cdef struct Foo_Pair_u8__f32:
+    Pair_u8__f32 value;
+
+  void root(Foo_f64 foo);
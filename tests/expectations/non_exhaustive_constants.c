@@ -0,0 +1,47 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * The kind of an event.
+ *
+ * This enum is non-exhaustive, so values other than the ones below may appear.
+ */
+typedef uint8_t EventKind;
+/**
+ * A key was pressed.
+ */
+#define EventKind_Key 0
+#define EventKind_Mouse 4
+#define EventKind_Resize 5
+/**
+ * Stands for values that aren't variants.
+ */
+#define EventKind_UNKNOWN UINT8_MAX
+
+enum Kept {
+  Kept_A,
+  Kept_B,
+};
+typedef uint16_t Kept;
+
+/**
+ * This enum is non-exhaustive, so values other than the ones below may appear.
+ */
+typedef int32_t Status;
+#define Status_Failed -1
+#define Status_Ok 0
+#define Status_Pending 1
+/**
+ * Stands for values that aren't variants.
+ */
+#define Status_UNKNOWN INT32_MAX
+
+typedef struct {
+  EventKind kind;
+  Status status;
+  Kept kept;
+} Event;
+
+void root(Event event);
@@ -0,0 +1,41 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+/// The kind of an event.
+///
+/// This enum is non-exhaustive, so values other than the ones below may appear.
+using EventKind = uint8_t;
+/// A key was pressed.
+constexpr static const EventKind EventKind_Key = 0;
+constexpr static const EventKind EventKind_Mouse = 4;
+constexpr static const EventKind EventKind_Resize = 5;
+/// Stands for values that aren't variants.
+constexpr static const EventKind EventKind_UNKNOWN = UINT8_MAX;
+
+enum class Kept : uint16_t {
+  Kept_A,
+  Kept_B,
+};
+
+/// This enum is non-exhaustive, so values other than the ones below may appear.
+using Status = int32_t;
+constexpr static const Status Status_Failed = -1;
+constexpr static const Status Status_Ok = 0;
+constexpr static const Status Status_Pending = 1;
+/// Stands for values that aren't variants.
+constexpr static const Status Status_UNKNOWN = INT32_MAX;
+
+struct Event {
+  EventKind kind;
+  Status status;
+  Kept kept;
+};
+
+extern "C" {
+
+void root(Event event);
+
+}  // extern "C"
@@ -0,0 +1,38 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  # The kind of an event.
+  #
+  # This enum is non-exhaustive, so values other than the ones below may appear.
+  ctypedef uint8_t EventKind;
+  # A key was pressed.
+  const EventKind EventKind_Key # = 0
+  const EventKind EventKind_Mouse # = 4
+  const EventKind EventKind_Resize # = 5
+  # Stands for values that aren't variants.
+  const EventKind EventKind_UNKNOWN # = UINT8_MAX
+
+  cdef enum:
+    Kept_A,
+    Kept_B,
+  ctypedef uint16_t Kept;
+
+  # This enum is non-exhaustive, so values other than the ones below may appear.
+  ctypedef int32_t Status;
+  const Status Status_Failed # = -1
+  const Status Status_Ok # = 0
+  const Status Status_Pending # = 1
+  # Stands for values that aren't variants.
+  const Status Status_UNKNOWN # = INT32_MAX
+
+  ctypedef struct Event:
+    EventKind kind;
+    Status status;
+    Kept kept;
+
+  void root(Event event);
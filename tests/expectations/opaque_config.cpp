@@ -0,0 +1,23 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct CacheEntry;
+
+template<typename T = void>
+struct Handle;
+
+/// The parser's state.
+struct Parser;
+
+struct State;
+
+extern "C" {
+
+Parser *parser_new(const State *state);
+
+void cache_get(const CacheEntry *entry, Handle<uint32_t> *handle);
+
+}  // extern "C"
@@ -0,0 +1,27 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct CacheEntry CacheEntry;
+
+typedef struct Handle_u32 Handle_u32;
+
+/**
+ * The parser's state.
+ */
+typedef struct Parser Parser;
+
+typedef struct State State;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct Parser *parser_new(const struct State *state);
+
+void cache_get(const struct CacheEntry *entry, struct Handle_u32 *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,19 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct CacheEntry;
+
+struct Handle_u32;
+
+/**
+ * The parser's state.
+ */
+struct Parser;
+
+struct State;
+
+struct Parser *parser_new(const struct State *state);
+
+void cache_get(const struct CacheEntry *entry, struct Handle_u32 *handle);
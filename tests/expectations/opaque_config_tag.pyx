@@ -0,0 +1,24 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct CacheEntry:
+    pass
+
+  cdef struct Handle_u32:
+    pass
+
+  # The parser's state.
+  cdef struct Parser:
+    pass
+
+  cdef struct State:
+    pass
+
+  Parser *parser_new(const State *state);
+
+  void cache_get(const CacheEntry *entry, Handle_u32 *handle);
@@ -0,0 +1,17 @@
+/* Config from the package metadata */
+
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Meta_Orphan {
+  int32_t x;
+};
+
+extern "C" {
+
+void root(int32_t x);
+
+}  // extern "C"
@@ -0,0 +1,12 @@
+/* Config from the package metadata */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Meta_Orphan {
+  int32_t x;
+};
+
+void root(int32_t x);
@@ -0,0 +1,20 @@
+/* Config from the package metadata */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Meta_Orphan {
+  int32_t x;
+};
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+void root(int32_t x);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct {
+  int32_t y;
+  bool z;
+} Bar;
+
+/**
+ * Takes a bar.
+ */
+bool f(Bar bar);
+
+#if defined(UNIX)
+/**
+ * Unix.
+ */
+void h(int32_t x);
+#endif
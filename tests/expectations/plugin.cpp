@@ -0,0 +1,22 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+
+struct Bar {
+  int32_t y;
+  bool z;
+};
+
+extern "C" {
+
+/// Takes a bar.
+bool f(Bar bar);
+
+#if defined(UNIX)
+/// Unix.
+void h(int32_t x);
+#endif
+
+}  // extern "C"
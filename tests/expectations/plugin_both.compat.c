@@ -0,0 +1,29 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+typedef struct Bar {
+  int32_t y;
+  bool z;
+} Bar;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Takes a bar.
+ */
+bool f(struct Bar bar);
+
+#if defined(UNIX)
+/**
+ * Unix.
+ */
+void h(int32_t x);
+#endif
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
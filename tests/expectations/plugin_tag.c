@@ -0,0 +1,21 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+struct Bar {
+  int32_t y;
+  bool z;
+};
+
+/**
+ * Takes a bar.
+ */
+bool f(struct Bar bar);
+
+#if defined(UNIX)
+/**
+ * Unix.
+ */
+void h(int32_t x);
+#endif
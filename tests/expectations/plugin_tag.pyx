@@ -0,0 +1,18 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  cdef struct Bar:
+    int32_t y;
+    bool z;
+
+  # Takes a bar.
+  bool f(Bar bar);
+
+  IF UNIX:
+    # Unix.
+    void h(int32_t x);
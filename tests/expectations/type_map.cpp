@@ -0,0 +1,25 @@
+#include <cstdarg>
+#include <cstdint>
+#include <cstdlib>
+#include <ostream>
+#include <new>
+#include <stdio.h>
+#include <time.h>
+
+struct FfiEvent {
+  std::FILE* handle;
+  time_t time;
+  uint_least8_t kind;
+  uint_least8_t data[4];
+  uint_least8_t (*callback)(uint_least8_t);
+};
+
+constexpr static const uint_least8_t FfiMAX_KIND = 4;
+
+extern "C" {
+
+FfiEvent event_new(std::FILE* handle, const time_t *time);
+
+std::FILE* Handle();
+
+}  // extern "C"
@@ -0,0 +1,20 @@
+from libc.stdint cimport int8_t, int16_t, int32_t, int64_t, intptr_t
+from libc.stdint cimport uint8_t, uint16_t, uint32_t, uint64_t, uintptr_t
+cdef extern from *:
+  ctypedef bint bool
+  ctypedef struct va_list
+
+cdef extern from *:
+
+  ctypedef struct FfiEvent:
+    FILE* handle;
+    time_t time;
+    uint_least8_t kind;
+    uint_least8_t data[4];
+    uint_least8_t (*callback)(uint_least8_t);
+
+  const uint_least8_t FfiMAX_KIND # = 4
+
+  FfiEvent event_new(FILE* handle, const time_t *time);
+
+  FILE* Handle();
@@ -0,0 +1,28 @@
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+#include <stdio.h>
+#include <time.h>
+
+typedef struct FfiEvent {
+  FILE* handle;
+  time_t time;
+  uint_least8_t kind;
+  uint_least8_t data[4];
+  uint_least8_t (*callback)(uint_least8_t);
+} FfiEvent;
+
+#define FfiMAX_KIND 4
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+struct FfiEvent event_new(FILE* handle, const time_t *time);
+
+FILE* Handle(void);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
@@ -0,0 +1,17 @@
+// A minimal declaration of the parts of {fmt} used by the generated
+// formatters, so that the tests compile without {fmt} installed.
+#pragma once
+
+namespace fmt {
+
+template <class T, class Char = char, class Enable = void>
+struct formatter;
+
+struct format_parse_context {
+  constexpr const char* begin() const { return nullptr; }
+};
+
+template <class Out, class... Args>
+Out format_to(Out out, const char* format, const Args&... args);
+
+}  // namespace fmt
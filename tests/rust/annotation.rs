@@ -9,7 +9,7 @@ struct A(i32);
 #[repr(C)]
 struct B(i32, f32);
 
-/// cbindgen:trailing-values=[Z, W]
+/// cbindgen:enum-trailing-values=[Z, W]
 #[repr(u32)]
 enum C {
     X = 2,
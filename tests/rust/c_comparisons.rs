@@ -0,0 +1,67 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+#[repr(C)]
+pub struct Grid {
+    cells: [[u8; 3]; 2],
+    origin: Point,
+    corners: [Point; 4],
+    color: Color,
+    #[cfg(feature = "blue")]
+    blue: f32,
+    data: *const u8,
+}
+
+#[repr(C, u8)]
+pub enum Shape {
+    Circle { center: Point, radius: f32 },
+    Triangle([Point; 3]),
+    Empty,
+}
+
+#[repr(u8)]
+pub enum Token {
+    Number(i64),
+    Pair { first: Point, second: Point },
+    #[cfg(feature = "blue")]
+    Blue(u32),
+}
+
+/// cbindgen:derive-lt=false
+#[repr(C)]
+pub struct Scene {
+    origin: Point,
+    token: Token,
+    callback: extern "C" fn(),
+}
+
+/// `Range_eq` is exported below, so only `Range_cmp` is derived.
+#[repr(C)]
+pub struct Range {
+    start: u32,
+    end: u32,
+}
+
+/// cbindgen:derive-c-comparisons=false
+#[repr(C)]
+pub struct Size {
+    width: u32,
+    height: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(grid: Grid, shape: Shape, scene: Scene, range: Range, size: Size) {}
+
+#[no_mangle]
+pub extern "C" fn Range_eq(a: *const Range, b: *const Range) -> bool {
+    true
+}
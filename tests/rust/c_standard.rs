@@ -0,0 +1,30 @@
+pub const LIMIT: u32 = 16;
+
+#[repr(u8)]
+pub enum Small {
+    A,
+    B,
+}
+
+#[repr(C)]
+pub enum Plain {
+    X,
+    Y,
+}
+
+#[repr(C)]
+#[must_use]
+pub struct Pair {
+    first: Small,
+    second: Plain,
+    flag: bool,
+}
+
+#[no_mangle]
+#[deprecated(note = "use `other` instead")]
+pub extern "C" fn root(pair: Pair) {}
+
+#[no_mangle]
+pub extern "C" fn stop() -> ! {
+    loop {}
+}
@@ -0,0 +1,12 @@
+#[repr(C)]
+pub struct Orphan {
+    x: i32,
+}
+
+#[repr(C)]
+pub struct Used {
+    y: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(u: Used) {}
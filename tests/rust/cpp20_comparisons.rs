@@ -0,0 +1,35 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C)]
+pub struct Segment {
+    start: Point,
+    end: Point,
+    weights: [f32; 2],
+}
+
+/// cbindgen:derive-lt=false
+/// cbindgen:derive-lte=false
+/// cbindgen:derive-gt=false
+/// cbindgen:derive-gte=false
+/// cbindgen:eq-attributes=EQ_ATTRS
+#[repr(C)]
+pub struct Label {
+    id: u32,
+    visible: bool,
+}
+
+/// cbindgen:derive-eq=false
+/// cbindgen:derive-neq=false
+/// cbindgen:lt-attributes=CMP_ATTRS
+#[repr(C)]
+pub struct Version {
+    major: u32,
+    minor: u32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(segment: Segment, label: Label, version: Version) {}
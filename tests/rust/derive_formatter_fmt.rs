@@ -0,0 +1,56 @@
+use std::os::raw::c_char;
+
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C)]
+pub enum Color {
+    Red,
+    Green,
+    #[cfg(feature = "blue")]
+    Blue,
+}
+
+#[repr(C, u8)]
+pub enum Shape {
+    Circle { center: Point, radius: f32 },
+    Segment(Point, Point),
+    Empty,
+}
+
+#[repr(C)]
+pub enum Event {
+    Click(Point),
+    Key { code: u32, pressed: bool },
+}
+
+#[repr(C)]
+pub struct Fields {
+    point: *const Point,
+    data: *mut u32,
+    name: *const c_char,
+    callback: Option<extern "C" fn(i32) -> i32>,
+    values: [u16; 2],
+    flag: bool,
+    byte: u8,
+}
+
+/// cbindgen:derive-formatter=none
+#[repr(C)]
+pub struct Opaque {
+    value: u64,
+}
+
+#[no_mangle]
+pub extern "C" fn root(
+    point: Point,
+    color: Color,
+    shape: Shape,
+    event: Event,
+    fields: Fields,
+    opaque: Opaque,
+) {
+}
@@ -0,0 +1,60 @@
+#[repr(C)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[repr(C)]
+pub struct Polygon {
+    corners: [Point; 4],
+    matrix: [[f32; 2]; 2],
+    closed: bool,
+}
+
+#[repr(C, u8)]
+pub enum Shape {
+    Empty,
+    Dot(Point),
+    Filled { polygon: Polygon, color: u32 },
+}
+
+#[repr(u8)]
+pub enum Token {
+    Number(i64),
+    End,
+}
+
+#[repr(C)]
+pub struct Named {
+    name: *const u8,
+}
+
+#[repr(C)]
+pub struct HasNamed {
+    named: Named,
+}
+
+/// cbindgen:derive-hash=false
+#[repr(C)]
+pub struct Unhashed {
+    x: i32,
+}
+
+#[cfg(feature = "blue")]
+#[repr(C)]
+pub struct Blue {
+    shape: Shape,
+}
+
+#[no_mangle]
+pub extern "C" fn root(
+    shape: Shape,
+    token: Token,
+    has_named: HasNamed,
+    unhashed: Unhashed,
+) {
+}
+
+#[cfg(feature = "blue")]
+#[no_mangle]
+pub extern "C" fn blue(blue: Blue) {}
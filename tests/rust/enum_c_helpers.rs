@@ -0,0 +1,20 @@
+#[repr(C, u8)]
+pub enum Shape {
+    Point,
+    Circle(f32),
+    Rect { width: f32, height: f32 },
+    Polygon { sides: u8, lengths: [f32; 4] },
+    #[cfg(feature = "blue")]
+    Blue(u32, u32),
+}
+
+/// cbindgen:derive-mut-casts=false
+#[repr(u8)]
+pub enum Token {
+    Number(i64),
+    Word { start: u32, len: u32 },
+    End,
+}
+
+#[no_mangle]
+pub extern "C" fn root(shape: Shape, token: Token) {}
@@ -0,0 +1,11 @@
+/// cbindgen:derive-c-helpers
+/// cbindgen:derive-helper-methods
+/// cbindgen:derive-const-casts
+#[repr(C, u8)]
+pub enum Shape {
+    Point,
+    Circle(f32),
+}
+
+#[no_mangle]
+pub extern "C" fn root(shape: Shape) {}
@@ -0,0 +1,26 @@
+#[repr(u8)]
+pub enum Color {
+    Red,
+    DarkGreen,
+    #[cfg(feature = "blue")]
+    Blue,
+}
+
+/// cbindgen:to-string-rust-names
+/// cbindgen:rename-all=ScreamingSnakeCase
+#[repr(C)]
+pub enum Shape {
+    Circle,
+    RoundedSquare,
+}
+
+/// cbindgen:derive-to-string=false
+/// cbindgen:derive-from-string=false
+#[repr(C)]
+pub enum Plain {
+    A,
+    B,
+}
+
+#[no_mangle]
+pub extern "C" fn root(color: Color, shape: Shape, plain: Plain) {}
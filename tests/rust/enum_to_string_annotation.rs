@@ -0,0 +1,16 @@
+/// cbindgen:derive-to-string
+#[repr(u8)]
+pub enum Color {
+    Red,
+    Green,
+}
+
+/// cbindgen:derive-from-string
+#[repr(C)]
+pub enum Shape {
+    Circle,
+    Square,
+}
+
+#[no_mangle]
+pub extern "C" fn root(color: Color, shape: Shape) {}
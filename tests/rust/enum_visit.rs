@@ -0,0 +1,25 @@
+#[repr(C, u8)]
+pub enum Shape {
+    Point,
+    Circle(f32),
+    Rect { width: f32, height: f32 },
+    #[cfg(feature = "blue")]
+    Blue(u32, u32),
+}
+
+#[repr(u8)]
+pub enum Token {
+    Number(i64),
+    Word { start: u32, len: u32 },
+    End,
+}
+
+/// cbindgen:derive-visit=false
+#[repr(u8)]
+pub enum Unvisited {
+    A(i32),
+    B,
+}
+
+#[no_mangle]
+pub extern "C" fn root(shape: Shape, token: Token, unvisited: Unvisited) {}
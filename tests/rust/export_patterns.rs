@@ -0,0 +1,45 @@
+#[repr(C)]
+pub struct InternalState {
+    x: i32,
+}
+
+#[repr(C)]
+pub struct PublicState {
+    x: i32,
+}
+
+#[repr(C)]
+pub struct TestHelper {
+    x: i32,
+}
+
+#[repr(C)]
+pub struct OtherHelper {
+    x: i32,
+}
+
+pub mod ffi {
+    pub mod v1 {
+        #[repr(C)]
+        pub struct Legacy {
+            x: i32,
+        }
+    }
+
+    pub mod v2 {
+        #[repr(C)]
+        pub struct Point {
+            x: f32,
+            y: f32,
+        }
+
+        #[repr(C)]
+        pub struct Size {
+            w: f32,
+            h: f32,
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn root(a: *const ffi::v2::Point) {}
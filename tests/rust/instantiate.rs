@@ -0,0 +1,19 @@
+/// cbindgen:instantiate=[Foo<u32>, Foo<Pair<u8, f32>>]
+#[repr(C)]
+pub struct Foo<T> {
+    value: T,
+}
+
+#[repr(C)]
+pub struct Pair<A, B> {
+    first: A,
+    second: B,
+}
+
+#[repr(C)]
+pub struct Wrapper<T> {
+    inner: *const T,
+}
+
+#[no_mangle]
+pub extern "C" fn root(foo: Foo<f64>) {}
@@ -0,0 +1,35 @@
+/// The kind of an event.
+#[repr(u8)]
+#[non_exhaustive]
+pub enum EventKind {
+    /// A key was pressed.
+    Key,
+    Mouse = 4,
+    Resize,
+}
+
+#[repr(C)]
+#[non_exhaustive]
+pub enum Status {
+    Failed = -1,
+    Ok,
+    Pending,
+}
+
+/// cbindgen:non-exhaustive-as-constants=false
+#[repr(u16)]
+#[non_exhaustive]
+pub enum Kept {
+    A,
+    B,
+}
+
+#[repr(C)]
+pub struct Event {
+    kind: EventKind,
+    status: Status,
+    kept: Kept,
+}
+
+#[no_mangle]
+pub extern "C" fn root(event: Event) {}
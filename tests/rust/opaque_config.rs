@@ -0,0 +1,36 @@
+#[repr(C)]
+pub struct Config {
+    verbose: bool,
+}
+
+/// The parser's state.
+#[repr(C)]
+pub struct Parser {
+    config: Config,
+    depth: u32,
+}
+
+#[repr(C)]
+pub struct CacheEntry {
+    key: u64,
+}
+
+/// cbindgen:opaque
+#[repr(u8)]
+pub enum State {
+    Idle,
+    Running,
+}
+
+#[repr(C)]
+pub struct Handle<T> {
+    value: T,
+}
+
+#[no_mangle]
+pub extern "C" fn parser_new(state: *const State) -> *mut Parser {
+    todo!()
+}
+
+#[no_mangle]
+pub extern "C" fn cache_get(entry: *const CacheEntry, handle: *mut Handle<u32>) {}
@@ -0,0 +1,7 @@
+#[repr(C)]
+pub struct Orphan {
+    x: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn root(x: i32) {}
@@ -0,0 +1,18 @@
+#[repr(C)]
+pub struct Foo {
+    x: i32,
+}
+
+#[no_mangle]
+pub extern "C" fn f(foo: Foo) {}
+
+#[no_mangle]
+pub extern "C" fn g() {}
+
+#[cfg(unix)]
+#[no_mangle]
+pub extern "C" fn h(x: i32) {}
+
+#[cfg(windows)]
+#[no_mangle]
+pub extern "C" fn h(x: u32) {}
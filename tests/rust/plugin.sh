@@ -0,0 +1,22 @@
+#!/bin/sh
+# Renames `Foo` and its field and adds one, changes the return type of `f` and
+# documents it, drops `g`, and keeps and documents only the first `h`.
+cat > /dev/null
+cat <<'RESPONSE'
+{
+  "items": [
+    {
+      "id": 0,
+      "name": "Bar",
+      "fields": [{"id": 0, "name": "y"}, {"name": "z", "type": {"primitive": "bool"}}]
+    },
+    {
+      "id": 1,
+      "documentation": [" Takes a bar."],
+      "args": [{"id": 0, "name": "bar"}],
+      "ret": {"primitive": "bool"}
+    },
+    {"id": 3, "documentation": [" Unix."]}
+  ]
+}
+RESPONSE
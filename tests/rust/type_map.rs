@@ -0,0 +1,28 @@
+use std::os::raw::c_void;
+
+pub type Handle = *mut c_void;
+
+pub struct Timestamp(i64);
+
+#[repr(C)]
+pub struct Event {
+    handle: Handle,
+    time: Timestamp,
+    kind: u8,
+    data: [u8; 4],
+    callback: Option<extern "C" fn(u8) -> u8>,
+}
+
+pub const MAX_KIND: u8 = 4;
+
+#[no_mangle]
+pub extern "C" fn event_new(handle: Handle, time: *const Timestamp) -> Event {
+    todo!()
+}
+
+// Only types are mapped, so this function is kept.
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn Handle() -> Handle {
+    todo!()
+}
@@ -5,7 +5,8 @@ use std::collections::HashSet;
 use std::fs::File;
 use std::io::Read;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
+use std::sync::OnceLock;
 use std::{env, fs, str};
 use tempfile::NamedTempFile;
 
@@ -28,6 +29,7 @@ struct CBindgenOutput {
     symfile_content: Option<String>,
 }
 
+#[allow(clippy::too_many_arguments)]
 fn run_cbindgen(
     path: &Path,
     output: Option<&Path>,
@@ -91,6 +93,20 @@ fn run_cbindgen(
         command.arg("--config").arg(config);
     }
 
+    // The settings that only make sense for one language, like a trailer with
+    // C++ code, go in an overlay named after it.
+    let overlay = match language {
+        Language::Cxx => "cxx",
+        Language::C => "c",
+        Language::Cython => "cython",
+    };
+    let has_overlay = test_config(path)
+        .and_then(|config| config.get("overlay")?.get(overlay).cloned())
+        .is_some();
+    if has_overlay {
+        command.arg("--overlay").arg(overlay);
+    }
+
     command.arg(path);
 
     println!("Running: {command:?}");
@@ -128,6 +144,7 @@ fn run_cbindgen(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn compile(
     cbindgen_output: &Path,
     tests_path: &Path,
@@ -135,6 +152,8 @@ fn compile(
     language: Language,
     style: Option<Style>,
     skip_warning_as_error: bool,
+    cpp_standard: &str,
+    c_standard: Option<&str>,
 ) {
     let cc = match language {
         Language::Cxx => env::var("CXX").unwrap_or_else(|_| "g++".to_owned()),
@@ -170,14 +189,22 @@ fn compile(
             if let Language::Cxx = language {
                 // enum class is a c++11 extension which makes g++ on macos 10.14 error out
                 // inline variables are are a c++17 extension
-                command.arg("-std=c++17");
+                command.arg(format!("-std={cpp_standard}"));
                 // Prevents warnings when compiling .c files as c++.
                 command.arg("-x").arg("c++");
                 if let Ok(extra_flags) = env::var("CXXFLAGS") {
                     command.args(extra_flags.split_whitespace());
                 }
-            } else if let Ok(extra_flags) = env::var("CFLAGS") {
-                command.args(extra_flags.split_whitespace());
+            } else {
+                // Check that the bindings stick to the standard they're
+                // written for.
+                if let Some(c_standard) = c_standard {
+                    command.arg(format!("-std={c_standard}"));
+                    command.arg("-pedantic");
+                }
+                if let Ok(extra_flags) = env::var("CFLAGS") {
+                    command.args(extra_flags.split_whitespace());
+                }
             }
 
             if let Some(style) = style {
@@ -214,6 +241,83 @@ fn compile(
 }
 
 const SKIP_WARNING_AS_ERROR_SUFFIX: &str = ".skip_warning_as_error";
+const FORMAT_INCLUDE: &[u8] = b"#include <format>";
+
+/// The config of the test at `path`, if it has one.
+fn test_config(path: &Path) -> Option<toml::Table> {
+    fs::read_to_string(path.with_extension("toml"))
+        .ok()?
+        .parse::<toml::Table>()
+        .ok()
+}
+
+/// The `-std` to compile the C++ bindings of the test at `path` with, which is
+/// the `cpp_standard` of its config when that is newer than C++17.
+fn cpp_standard(path: &Path) -> &'static str {
+    let standard = test_config(path)
+        .and_then(|config| config.get("cpp_standard")?.as_str().map(str::to_lowercase));
+    match standard.as_deref() {
+        Some("c++20") => "c++20",
+        Some("c++23") => "c++23",
+        _ => "c++17",
+    }
+}
+
+/// The `-std` to compile the C bindings of the test at `path` with, which is
+/// the `c_standard` of its config if it has one.
+fn c_standard(path: &Path) -> Option<&'static str> {
+    let standard = test_config(path)
+        .and_then(|config| config.get("c_standard")?.as_str().map(str::to_lowercase))?;
+    match &*standard {
+        "c89" | "c90" => Some("c89"),
+        "c99" => Some("c99"),
+        "c11" => Some("c11"),
+        "c17" => Some("c17"),
+        // Older compilers only know C23 by its draft name.
+        "c23" => Some("c2x"),
+        _ => None,
+    }
+}
+
+/// Whether the C++ compiler has `<format>`, which the bindings with
+/// `std::formatter` specializations include. GCC only has it since 13.
+fn cxx_has_format(tmp_dir: &Path) -> bool {
+    static HAS_FORMAT: OnceLock<bool> = OnceLock::new();
+    *HAS_FORMAT.get_or_init(|| {
+        let source = tmp_dir.join("has_format.cpp");
+        fs::write(&source, "#include <format>\n").unwrap();
+        let cxx = env::var("CXX").unwrap_or_else(|_| "g++".to_owned());
+        Command::new(cxx)
+            .arg("-std=c++20")
+            .arg("-fsyntax-only")
+            .arg(&source)
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
+
+/// Whether the C compiler has the C23 features that the bindings for C23 use,
+/// typed enums and `constexpr`. GCC only has them since 13.
+fn cc_has_c23(tmp_dir: &Path) -> bool {
+    static HAS_C23: OnceLock<bool> = OnceLock::new();
+    *HAS_C23.get_or_init(|| {
+        let source = tmp_dir.join("has_c23.c");
+        fs::write(
+            &source,
+            "enum E : unsigned char { A };\nstatic constexpr int B = 1;\n",
+        )
+        .unwrap();
+        let cc = env::var("CC").unwrap_or_else(|_| "gcc".to_owned());
+        Command::new(cc)
+            .arg("-std=c2x")
+            .arg("-fsyntax-only")
+            .arg(&source)
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    })
+}
 
 #[allow(clippy::too_many_arguments)]
 fn run_compile_test(
@@ -256,6 +360,8 @@ fn run_compile_test(
     };
 
     let skip_warning_as_error = name.rfind(SKIP_WARNING_AS_ERROR_SUFFIX).is_some();
+    let cpp_standard = cpp_standard(path);
+    let c_standard = c_standard(path);
 
     let source_file =
         format!("{name}{style_ext}{lang_ext}").replace(SKIP_WARNING_AS_ERROR_SUFFIX, "");
@@ -326,11 +432,22 @@ fn run_compile_test(
             }
         }
 
+        let includes_format = bindings_content
+            .windows(FORMAT_INCLUDE.len())
+            .any(|line| line == FORMAT_INCLUDE);
         cbindgen_outputs.insert(bindings_content);
 
         if no_compile {
             return;
         }
+        if includes_format && !cxx_has_format(tmp_dir) {
+            eprintln!("Not compiling {generated_file:?}, the C++ compiler doesn't have <format>.");
+            return;
+        }
+        if language == Language::C && c_standard == Some("c2x") && !cc_has_c23(tmp_dir) {
+            eprintln!("Not compiling {generated_file:?}, the C compiler doesn't have C23.");
+            return;
+        }
 
         compile(
             &generated_file,
@@ -339,6 +456,8 @@ fn run_compile_test(
             language,
             style,
             skip_warning_as_error,
+            cpp_standard,
+            c_standard,
         );
 
         if language == Language::C && cpp_compat {
@@ -349,6 +468,8 @@ fn run_compile_test(
                 Language::Cxx,
                 style,
                 skip_warning_as_error,
+                cpp_standard,
+                None,
             );
         }
     }